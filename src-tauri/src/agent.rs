@@ -80,7 +80,7 @@ impl Agent {
                         "type": "object",
                         "properties": {
                             "name": { "type": "string", "description": "Nombre/valor del hallazgo (ej: 'wolftei.com.ar' o 'Juan Perez')." },
-                            "target_type": { "type": "string", "description": "Categoría: Domain, IP, Email, Person." },
+                            "target_type": { "type": "string", "description": "Categoría: Domain, IP, Email, Phone, Person." },
                             "attributes": { "type": "string", "description": "JSON string con info extra (ej: '{\"ISP\": \"Telecom\", \"Registrant\": \"...\"}')." }
                         },
                         "required": ["name", "target_type"]
@@ -116,6 +116,435 @@ impl Agent {
                     }
                 }
             }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "hacer_ping",
+                    "description": "Ping nativo multiplataforma (ICMP con fallback a TCP connect). Devuelve RTT, pérdida y TTL y los guarda en el tablero.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "IP o dominio a sondear." },
+                            "count": { "type": "string", "description": "Opcional. Cantidad de sondas (por defecto 4, máximo 20)." },
+                            "ports": { "type": "string", "description": "Opcional. Puertos para el fallback TCP separados por coma (ej: '443,80')." },
+                            "tcp_only": { "type": "string", "description": "Opcional. 'true' para omitir ICMP y medir sólo por TCP." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "escanear_puertos",
+                    "description": "Escaneo TCP connect nativo (no requiere nmap). Devuelve puertos abiertos, servicio probable y banners, y los guarda sobre el objetivo IP. Respeta el proxy SOCKS/Tor.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "IP o dominio a escanear." },
+                            "ports": { "type": "string", "description": "Opcional. 'top100' (por defecto y máximo del preset), 'top20', rangos '1-1024' o lista '22,80,443'." },
+                            "concurrency": { "type": "string", "description": "Opcional. Conexiones simultáneas (por defecto 200; 16 máximo vía proxy)." },
+                            "timeout_ms": { "type": "string", "description": "Opcional. Tiempo de espera por puerto en milisegundos (por defecto 1500, entre 100 y 10000)." },
+                            "banners": { "type": "string", "description": "Opcional. 'true' para capturar el banner de cada servicio abierto." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "inspeccionar_tls",
+                    "description": "Obtiene la cadena de certificados TLS de host:puerto: SANs, emisor, serie, vigencia, tipo de clave y huellas SHA-256. Sirve para pivotear a infraestructura relacionada.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Host o 'host:puerto' (ej: 'mail.ejemplo.com.ar:993')." },
+                            "port": { "type": "string", "description": "Opcional. Puerto TLS (por defecto 443)." },
+                            "crear_objetivos": { "type": "string", "description": "Opcional. 'true' para guardar cada dominio de los SANs como objetivo vinculado." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "huella_http",
+                    "description": "Analiza un sitio web: código de estado, cadena de redirecciones, cabeceras de seguridad, banners de servidor/framework, cookies, tecnologías e IDs de seguimiento (UA-, G-, GTM-, pub-). Los IDs compartidos vinculan dominios del mismo operador.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "URL o dominio (ej: 'https://ejemplo.com.ar/tienda')." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "buscar_usuario",
+                    "description": "Busca un nombre de usuario en decenas de sitios (redes sociales, código, gaming, blogs) usando reglas por sitio para evitar falsos positivos. Con 'person_id' las cuentas encontradas se agregan a las redes sociales de esa persona.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "username": { "type": "string", "description": "Nombre de usuario a buscar (sin '@')." },
+                            "person_id": { "type": "string", "description": "Opcional. ID de la persona a la que pertenecen las cuentas." },
+                            "categorias": { "type": "string", "description": "Opcional. Categorías separadas por coma: social, coding, tech, gaming, blog, video, music." },
+                            "manifest": { "type": "string", "description": "Opcional. Ruta a un manifiesto propio con formato WhatsMyName." }
+                        },
+                        "required": ["username"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "verificar_email",
+                    "description": "Verifica un email sin enviar mensajes: sintaxis, registros MX, sondeo SMTP (RCPT TO) con detección de catch-all y marcas de proveedor desechable, gratuito o cuenta de rol. Guarda el veredicto en el objetivo Email.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "email": { "type": "string", "description": "Dirección a verificar (ej: 'juan.perez@ejemplo.com.ar')." }
+                        },
+                        "required": ["email"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "buscar_emails_dominio",
+                    "description": "Emails públicos de un dominio según Hunter.io: tipo (personal o genérico), confianza, titular, cargo y páginas donde aparecieron, más el patrón de emails de la organización (ej: '{first}.{last}'). Crea los Emails vinculados al Dominio y las personas con su cargo. Requiere la API Key de Hunter.io.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio de la organización (ej: 'ejemplo.com.ar')." },
+                            "limite": { "type": "string", "description": "Opcional. Máximo de emails a traer (1 a 100, por defecto 10). Más de 10 requiere un plan pago de Hunter.io." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "encontrar_email",
+                    "description": "Email más probable de una persona en un dominio (Hunter.io). Con patron='true', si Hunter no lo conoce devuelve el patrón del dominio aplicado al nombre como candidato, que conviene confirmar con verificar_email; ese respaldo hace una búsqueda por dominio extra y consume otra consulta de la cuota.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "nombre": { "type": "string", "description": "Nombre de pila." },
+                            "apellido": { "type": "string", "description": "Apellido." },
+                            "dominio": { "type": "string", "description": "Dominio de la organización." },
+                            "patron": { "type": "string", "description": "Opcional. 'true' para proponer el patrón del dominio si no hay email conocido (una consulta extra)." }
+                        },
+                        "required": ["nombre", "apellido", "dominio"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "analizar_telefono",
+                    "description": "Interpreta un número telefónico en cualquier formato, lo normaliza a E.164 e informa país, tipo (móvil/fijo) y característica/localidad. Sin prefijo internacional se asume Argentina.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "number": { "type": "string", "description": "Número tal como aparece (ej: '011 15-4123-4567')." },
+                            "region": { "type": "string", "description": "Opcional. Código ISO del país a asumir si no trae prefijo (por defecto 'AR')." }
+                        },
+                        "required": ["number"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "extraer_metadatos",
+                    "description": "Extrae metadatos de un archivo local: PDF (diccionario Info, XMP e historial de revisiones), Office docx/xlsx/pptx (autor, último editor, revisión, empresa, plantilla) e imágenes (EXIF: posición GPS como ubicación del caso y cámara/número de serie para agrupar fotos del mismo dispositivo). Informa rutas y servidores embebidos y propone autores como personas.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "path": { "type": "string", "description": "Ruta absoluta del archivo (ej: '/home/analista/descargas/licitacion.pdf')." }
+                        },
+                        "required": ["path"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "calcular_hashes",
+                    "description": "Calcula MD5, SHA-1, SHA-256 y hash difuso (ssdeep) de un archivo local, lo registra junto a sus hashes como objetivos vinculados, lo compara con los demás archivos del caso y consulta el SHA-256 en VirusTotal si hay API key. Ideal para adjuntos sospechosos.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "path": { "type": "string", "description": "Ruta absoluta del archivo (ej: '/home/analista/adjuntos/factura.docm')." }
+                        },
+                        "required": ["path"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "agregar_imagen",
+                    "description": "Agrega una imagen al caso (foto de perfil, adjunto, captura) desde una URL o ruta local: calcula hashes perceptuales (aHash, dHash, pHash), guarda una copia y la compara con todas las imágenes del caso. Los casi-duplicados vinculan a los dueños de las imágenes (ej: la misma foto en dos cuentas).",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "source": { "type": "string", "description": "URL de la imagen o ruta local (ej: 'https://pbs.twimg.com/profile_images/.../foto.jpg')." },
+                            "owner": { "type": "string", "description": "Opcional. Objetivo al que pertenece la imagen (ej: el usuario 'jperez_ok')." },
+                            "owner_type": { "type": "string", "description": "Opcional. Tipo del dueño: Username (por defecto), Email, Person, Domain, Phone." }
+                        },
+                        "required": ["source"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "comparar_rostros",
+                    "description": "Detecta los rostros de una imagen (URL o ruta local) y los compara con todos los rostros ya guardados en el caso (similitud coseno de embeddings faciales). Las coincidencias fuertes vinculan las imágenes como 'mismo_rostro'. Requiere los modelos ONNX instalados por el usuario.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "source": { "type": "string", "description": "URL de la imagen o ruta local (ej: foto de perfil descargada)." }
+                        },
+                        "required": ["source"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "buscar_web",
+                    "description": "Busca en la web abierta con varios motores a la vez (DuckDuckGo, Bing, Brave, Mojeek y SearXNG si está configurado). Devuelve resultados deduplicados {title, url, snippet, engine, rank}. Acepta operadores (site:, filetype:, comillas).",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "query": { "type": "string", "description": "Consulta (ej: '\"Juan Pérez\" site:linkedin.com')." },
+                            "motores": { "type": "string", "description": "Opcional. Lista separada por comas: duckduckgo, bing, brave, mojeek, searxng. Vacío = todos." },
+                            "paginas": { "type": "string", "description": "Opcional. Páginas por motor (1 a 5, por defecto 1)." },
+                            "idioma": { "type": "string", "description": "Opcional. Idioma ISO de 2 letras (ej: 'es')." },
+                            "region": { "type": "string", "description": "Opcional. País ISO de 2 letras (ej: 'ar')." }
+                        },
+                        "required": ["query"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "historial_web",
+                    "description": "Consulta el archivo web (Wayback Machine) de un dominio o URL: lista las capturas con fecha y digest, marca cuándo cambió el contenido y resume cada URL (una página cuyo último código es 404 probablemente fue borrada).",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio (ej: ejemplo.com) o URL concreta." },
+                            "alcance": { "type": "string", "description": "Opcional. exact, prefix, host o domain. Por defecto domain para un dominio y exact para una URL." },
+                            "desde": { "type": "string", "description": "Opcional. Fecha inicial AAAA, AAAAMM o AAAAMMDD." },
+                            "hasta": { "type": "string", "description": "Opcional. Fecha final AAAA, AAAAMM o AAAAMMDD." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "leer_captura",
+                    "description": "Descarga una captura del archivo web (devuelta por historial_web) y la devuelve como texto limpio. Sirve para leer versiones viejas o páginas borradas.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "url": { "type": "string", "description": "URL original capturada." },
+                            "timestamp": { "type": "string", "description": "Fecha de la captura, AAAAMMDDhhmmss." }
+                        },
+                        "required": ["url", "timestamp"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "ejecutar_dorks",
+                    "description": "Ejecuta dorks (búsquedas con site:, filetype:, intext:) sobre una persona, dominio, email o teléfono y devuelve los resultados deduplicados, etiquetados por categoría (documentos, datos_contacto, filtraciones, redes_sociales, registros_publicos, archivos_expuestos, paneles, subdominios). Tarda: hay una pausa entre consultas. Los resultados útiles se guardan con promover_dork.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Nombre completo, dominio, email o teléfono." },
+                            "tipo": { "type": "string", "description": "Opcional. person, domain, email o phone. Se deduce si se omite." },
+                            "categorias": { "type": "string", "description": "Opcional. Categorías separadas por comas (ej: 'documentos,filtraciones')." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "promover_dork",
+                    "description": "Guarda como evidencia del caso un resultado devuelto por ejecutar_dorks (queda en el objetivo y vinculado al dominio donde apareció).",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "El mismo objetivo usado en ejecutar_dorks." },
+                            "tipo": { "type": "string", "description": "Opcional. person, domain, email o phone." },
+                            "url": { "type": "string", "description": "URL del resultado." },
+                            "title": { "type": "string", "description": "Título del resultado." },
+                            "snippet": { "type": "string", "description": "Opcional. Fragmento de texto del resultado." },
+                            "categoria": { "type": "string", "description": "Opcional. Categoría del dork que lo encontró." }
+                        },
+                        "required": ["target", "url"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "buscar_darkweb",
+                    "description": "Busca un término (alias, email, marca, dominio) en el índice de servicios .onion de Ahmia. Siempre va por Tor y falla si Tor no está conectado.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "query": { "type": "string", "description": "Término a buscar (ej: 'jperez_ok' o 'empresa.com.ar')." }
+                        },
+                        "required": ["query"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "leer_onion",
+                    "description": "Abre una página .onion por Tor y devuelve su título, el texto limpio y los enlaces a otros servicios .onion. Falla si Tor no está conectado.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "url": { "type": "string", "description": "Dirección .onion (ej: 'http://xxxx.onion/foro')." }
+                        },
+                        "required": ["url"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "geolocalizar_ip",
+                    "description": "Geolocaliza una IP con bases locales (sin revelar la consulta): país siempre; región, ciudad, coordenadas y ASN si hay una base MMDB configurada. Solo consulta servicios en línea si el analista habilitó el enriquecimiento.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dirección IPv4 o IPv6." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "infraestructura_ip",
+                    "description": "Infraestructura de una IP: ASN y titular, prefijo anunciado, red registrada (RDAP), nombres inversos (PTR) y otros dominios alojados en la misma IP. Guarda todo en el objetivo IP, crea los dominios vinculados y relaciona las IPs del caso que comparten ASN.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dirección IPv4 o IPv6." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "consultar_shodan",
+                    "description": "Servicios expuestos de una IP según Shodan: puertos, producto y versión de cada servicio, CPE, CVE conocidas, etiquetas, hostnames y dominios. Guarda el resumen en el objetivo IP y crea los dominios vinculados. Requiere la API Key de Shodan.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dirección IPv4 o IPv6." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "buscar_filtraciones",
+                    "description": "Filtraciones en Have I Been Pwned: brechas de un email o usuario con fecha, datos expuestos y si están verificadas, más publicaciones en sitios de pegado (sólo emails). Con alcance 'dominio' lista las cuentas filtradas de un dominio verificado en la cuenta de HIBP. Cada brecha queda como evento fechado en la cronología del caso.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Email, usuario o dominio." },
+                            "alcance": { "type": "string", "enum": ["cuenta", "dominio"], "description": "Opcional. 'cuenta' (por defecto) o 'dominio'." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "consultar_virustotal",
+                    "description": "Reputación en VirusTotal de un dominio, IP, URL o hash de archivo (el tipo se detecta solo): motores que lo marcan como malicioso, reputación, categorías y, para dominios e IPs, resoluciones DNS pasivas y archivos que se comunican con él. Guarda el resultado y vincula los pivotes. Requiere la API Key de VirusTotal.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio, IP, URL completa (http/https) o hash MD5/SHA-1/SHA-256." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "consultar_whois",
+                    "description": "Consulta WHOIS nativa (puerto 43) de un dominio, IP o ASN. Sigue las referencias hasta el registrador/RIR y guarda el resultado en el tablero.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio (ej: 'ejemplo.com.ar'), IP o ASN (ej: 'AS15169')." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "enumerar_dns",
+                    "description": "Enumera los registros DNS (A, AAAA, CNAME, MX, NS, TXT, SOA, CAA, SRV y PTR) de un dominio, o el PTR de una IP. Crea y vincula las IPs y servidores encontrados.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio (ej: 'ejemplo.com.ar') o IP para resolución inversa." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "descubrir_subdominios",
+                    "description": "Descubre subdominios combinando Certificate Transparency (crt.sh) y fuerza bruta DNS con detección de comodín. Cada subdominio se guarda como objetivo vinculado al dominio padre.",
+                    "parameters": {
+                        "type": "object",
+                        "properties": {
+                            "target": { "type": "string", "description": "Dominio raíz (ej: 'ejemplo.com.ar')." },
+                            "wordlist": { "type": "string", "description": "Opcional. Ruta a una wordlist propia (una etiqueta por línea)." },
+                            "concurrency": { "type": "string", "description": "Opcional. Consultas DNS simultáneas (por defecto 20)." }
+                        },
+                        "required": ["target"]
+                    }
+                }
+            }),
             json!({
                 "type": "function",
                 "function": {
@@ -552,6 +981,81 @@ impl Agent {
                                                     | "ejecutarherramienta" => {
                                                         "ejecutar_herramienta_linux"
                                                     }
+                                                    "hacerping" | "ping" => "hacer_ping",
+                                                    "escanearpuertos" | "portscan" | "nmap" => {
+                                                        "escanear_puertos"
+                                                    }
+                                                    "consultarwhois" | "whois" => "consultar_whois",
+                                                    "geolocalizarip" | "geoip" | "iplocation" => {
+                                                        "geolocalizar_ip"
+                                                    }
+                                                    "infraestructuraip" | "asn" | "ipinfra" => {
+                                                        "infraestructura_ip"
+                                                    }
+                                                    "consultarshodan" | "shodan" => {
+                                                        "consultar_shodan"
+                                                    }
+                                                    "consultarvirustotal" | "virustotal" | "vt" => {
+                                                        "consultar_virustotal"
+                                                    }
+                                                    "buscarfiltraciones" | "leaks" | "hibp" => {
+                                                        "buscar_filtraciones"
+                                                    }
+                                                    "extraermetadatos" | "metadata"
+                                                    | "metadatos" => "extraer_metadatos",
+                                                    "calcularhashes" | "hashfile" | "hashes" => {
+                                                        "calcular_hashes"
+                                                    }
+                                                    "agregarimagen" | "imagehash" | "imagen" => {
+                                                        "agregar_imagen"
+                                                    }
+                                                    "compararrostros" | "facecompare"
+                                                    | "rostros" => "comparar_rostros",
+                                                    "buscardarkweb" | "darksearch" | "darkweb" => {
+                                                        "buscar_darkweb"
+                                                    }
+                                                    "buscarweb" | "websearch" | "search" => {
+                                                        "buscar_web"
+                                                    }
+                                                    "ejecutardorks" | "dorks" | "rundorks" => {
+                                                        "ejecutar_dorks"
+                                                    }
+                                                    "promoverdork" | "promotedork" => {
+                                                        "promover_dork"
+                                                    }
+                                                    "historialweb" | "wayback" | "archivoweb" => {
+                                                        "historial_web"
+                                                    }
+                                                    "leercaptura" | "snapshot" => "leer_captura",
+                                                    "leeronion" | "onion" | "fetchonion" => {
+                                                        "leer_onion"
+                                                    }
+                                                    "analizartelefono" | "phone" | "telefono" => {
+                                                        "analizar_telefono"
+                                                    }
+                                                    "verificaremail" | "emailverify" => {
+                                                        "verificar_email"
+                                                    }
+                                                    "buscaremailsdominio"
+                                                    | "hunter"
+                                                    | "domainemails" => "buscar_emails_dominio",
+                                                    "encontraremail" | "emailfinder" => {
+                                                        "encontrar_email"
+                                                    }
+                                                    "buscarusuario" | "usernamesearch"
+                                                    | "searchusername" => "buscar_usuario",
+                                                    "huellahttp" | "httpfingerprint" => {
+                                                        "huella_http"
+                                                    }
+                                                    "inspeccionartls" | "tls" | "certificado" => {
+                                                        "inspeccionar_tls"
+                                                    }
+                                                    "enumerardns" | "dns" | "dnslookup" => {
+                                                        "enumerar_dns"
+                                                    }
+                                                    "descubrirsubdominios"
+                                                    | "subdominios"
+                                                    | "subdomains" => "descubrir_subdominios",
                                                     "upsertintelligence" | "guardarhallazgo"
                                                     | "guardar" => "guardar_hallazgo",
                                                     "reportactivity"
@@ -0,0 +1,526 @@
+// src-tauri/src/asn.rs
+//
+// Infraestructura de una IP: sistema autónomo (ASN), prefijo anunciado, red registrada,
+// nombres inversos y dominios alojados en la misma IP.
+//
+// Flujo:
+//   1. ASN y prefijo vía DNS de Team Cymru (`<ip invertida>.origin.asn.cymru.com` TXT);
+//      el nombre del titular sale de `AS<n>.asn.cymru.com`. Si hay varios anuncios se
+//      queda con el prefijo más específico.
+//   2. En paralelo se consulta el RDAP del RIR (vía rdap.org, que redirige al registro
+//      correcto) para la red: handle, nombre, rango, CIDR y titular. Si Cymru no respondió,
+//      el RDAP es lo único que queda y alcanza para identificar al dueño.
+//   3. PTR con el resolver configurado y dominios co-alojados con la búsqueda de IP inversa
+//      de HackerTarget (gratuita, con cupo diario).
+//   4. Cada fuente falla por separado: sus errores quedan en `errors` sin tirar el resto. Con
+//      un proxy configurado Cymru y PTR no se consultan (el DNS saldría directo) y el informe
+//      queda con lo que trae el HTTP por el proxy: RDAP y co-alojados.
+//   5. En el caso: atributos `asn`, `prefijo`, `titular_asn` y `red` sobre el objetivo IP,
+//      vínculos a PTR y co-alojados, y `mismo_asn` con las otras IPs del caso en ese ASN.
+
+use crate::cases::{CaseManager, RelatedTarget, TargetType};
+use crate::models::OsintConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+const CYMRU_ORIGIN: &str = "origin.asn.cymru.com";
+const CYMRU_ORIGIN6: &str = "origin6.asn.cymru.com";
+const CYMRU_ASN: &str = "asn.cymru.com";
+const RDAP_URL: &str = "https://rdap.org/ip";
+const REVERSE_IP_URL: &str = "https://api.hackertarget.com/reverseiplookup/";
+const REVERSE_IP_SOURCE: &str = "hackertarget";
+/// Tope de dominios co-alojados que se vuelven objetivos del caso (un hosting compartido
+/// puede devolver miles y no aportan nada al grafo)
+const MAX_COHOSTED_LINKS: usize = 50;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AsnInfo {
+    pub asn: u32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub prefix: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub country: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub registry: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub allocated: String,
+}
+
+/// Red registrada en el RIR según RDAP.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RdapNetwork {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub handle: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub start_address: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub end_address: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cidr: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub country: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IpInfraReport {
+    pub ip: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asn: Option<AsnInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<RdapNetwork>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ptr: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cohosted: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cohosted_source: Option<String>,
+    /// Fuentes que fallaron (timeouts, cupo agotado, etc.)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, String>,
+}
+
+impl IpInfraReport {
+    /// Detalle para `detalles_tecnicos.infraestructura`: el informe completo salvo los
+    /// co-alojados, que quedan como total más los primeros `MAX_COHOSTED_LINKS`.
+    pub fn case_details(&self) -> serde_json::Value {
+        let mut details = serde_json::to_value(self).unwrap_or_default();
+        if let Some(obj) = details
+            .as_object_mut()
+            .filter(|_| !self.cohosted.is_empty())
+        {
+            obj.insert(
+                "cohosted".to_string(),
+                serde_json::json!(self
+                    .cohosted
+                    .iter()
+                    .take(MAX_COHOSTED_LINKS)
+                    .collect::<Vec<_>>()),
+            );
+            obj.insert("cohosted_total".to_string(), self.cohosted.len().into());
+        }
+        details
+    }
+
+    /// Nombres inversos y dominios co-alojados como objetivos vinculados a la IP.
+    pub fn related_targets(&self) -> Vec<RelatedTarget> {
+        let from_ip = |name: &str, relation: &str| RelatedTarget {
+            source: self.ip.clone(),
+            source_type: TargetType::IP,
+            name: name.to_string(),
+            target_type: TargetType::Domain,
+            relation: relation.to_string(),
+        };
+        let mut related: Vec<RelatedTarget> =
+            self.ptr.iter().map(|name| from_ip(name, "ptr")).collect();
+        related.extend(
+            self.cohosted
+                .iter()
+                .filter(|d| !self.ptr.contains(d))
+                .take(MAX_COHOSTED_LINKS)
+                .map(|d| from_ip(d, "comparte_ip")),
+        );
+        related
+    }
+}
+
+/// Nombre de la consulta de origen de Cymru: octetos (IPv4) o nibbles (IPv6) invertidos.
+fn cymru_origin_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => {
+            let o = v4.octets();
+            format!("{}.{}.{}.{}.{}", o[3], o[2], o[1], o[0], CYMRU_ORIGIN)
+        }
+        IpAddr::V6(v6) => {
+            let hex: String = v6.octets().iter().map(|b| format!("{:02x}", b)).collect();
+            let nibbles: Vec<String> = hex.chars().rev().map(|c| c.to_string()).collect();
+            format!("{}.{}", nibbles.join("."), CYMRU_ORIGIN6)
+        }
+    }
+}
+
+fn pipe_fields(txt: &str) -> Vec<String> {
+    txt.trim_matches('"')
+        .split('|')
+        .map(|f| f.trim().to_string())
+        .collect()
+}
+
+/// "15169 | 8.8.8.0/24 | US | arin | 2023-12-28". Con varios registros (o varios ASN en
+/// uno, por anycast) gana el prefijo más largo y el primer ASN.
+fn parse_cymru_origin(records: &[String]) -> Option<AsnInfo> {
+    records
+        .iter()
+        .filter_map(|txt| {
+            let fields = pipe_fields(txt);
+            let asn = fields.first()?.split_whitespace().next()?.parse().ok()?;
+            let prefix = fields.get(1).filter(|p| p.contains('/'))?.clone();
+            let field = |i: usize| fields.get(i).cloned().unwrap_or_default();
+            Some(AsnInfo {
+                asn,
+                name: String::new(),
+                prefix,
+                country: field(2),
+                registry: field(3),
+                allocated: field(4),
+            })
+        })
+        .max_by_key(|info| {
+            info.prefix
+                .rsplit_once('/')
+                .and_then(|(_, len)| len.parse::<u8>().ok())
+                .unwrap_or(0)
+        })
+}
+
+/// "15169 | US | arin | 2000-03-30 | GOOGLE - Google LLC, US" -> "GOOGLE - Google LLC, US"
+fn parse_cymru_asn_name(records: &[String]) -> Option<String> {
+    records
+        .iter()
+        .filter_map(|txt| pipe_fields(txt).get(4).cloned())
+        .find(|name| !name.is_empty())
+}
+
+/// Nombre ("fn") del primer contacto con el rol pedido en las entidades RDAP.
+fn rdap_entity_name(entities: &serde_json::Value, role: &str) -> Option<String> {
+    entities.as_array()?.iter().find_map(|entity| {
+        let has_role = entity["roles"]
+            .as_array()
+            .is_some_and(|roles| roles.iter().any(|r| r.as_str() == Some(role)));
+        if !has_role {
+            return None;
+        }
+        entity["vcardArray"][1].as_array()?.iter().find_map(|prop| {
+            (prop[0].as_str() == Some("fn"))
+                .then(|| prop[3].as_str().map(str::to_string))
+                .flatten()
+                .filter(|n| !n.trim().is_empty())
+        })
+    })
+}
+
+fn parse_rdap(body: &str) -> Option<RdapNetwork> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    if json["objectClassName"].as_str() != Some("ip network") {
+        return None;
+    }
+    let text = |key: &str| json[key].as_str().unwrap_or_default().trim().to_string();
+    let cidr = json["cidr0_cidrs"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|c| {
+                    let prefix = c["v4prefix"].as_str().or_else(|| c["v6prefix"].as_str())?;
+                    Some(format!("{}/{}", prefix, c["length"].as_u64()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(RdapNetwork {
+        handle: text("handle"),
+        name: text("name"),
+        start_address: text("startAddress"),
+        end_address: text("endAddress"),
+        cidr,
+        country: text("country"),
+        holder: rdap_entity_name(&json["entities"], "registrant")
+            .or_else(|| rdap_entity_name(&json["entities"], "administrative")),
+    })
+}
+
+/// Un dominio por línea; los errores de la API vienen como texto plano en la primera.
+fn parse_reverse_ip(body: &str) -> Result<Vec<String>, String> {
+    let lines: Vec<&str> = body
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    if lines
+        .first()
+        .is_some_and(|l| l.starts_with("No DNS A records"))
+    {
+        return Ok(Vec::new());
+    }
+    if let Some(problem) = lines
+        .first()
+        .filter(|l| l.contains(' ') || l.to_lowercase().starts_with("error"))
+    {
+        return Err(problem.to_string());
+    }
+    let mut domains: Vec<String> = Vec::new();
+    for line in lines {
+        let domain = line.trim_end_matches('.').to_lowercase();
+        if domain.contains('.') && domain.parse::<IpAddr>().is_err() && !domains.contains(&domain) {
+            domains.push(domain);
+        }
+    }
+    Ok(domains)
+}
+
+async fn txt_records(
+    resolver: &hickory_resolver::TokioAsyncResolver,
+    name: String,
+) -> Result<Vec<String>, String> {
+    let lookup = resolver.txt_lookup(name).await.map_err(|e| e.to_string())?;
+    Ok(lookup
+        .iter()
+        .map(|txt| {
+            txt.txt_data()
+                .iter()
+                .map(|part| String::from_utf8_lossy(part).into_owned())
+                .collect::<String>()
+        })
+        .collect())
+}
+
+/// ASN y prefijo (y el nombre del titular) según Team Cymru.
+async fn cymru_lookup(ip: IpAddr, config: &OsintConfig) -> Result<Option<AsnInfo>, String> {
+    let (resolver, _) = crate::dns::build_resolver(config)?;
+    let origin = txt_records(&resolver, cymru_origin_name(ip)).await?;
+    let Some(mut info) = parse_cymru_origin(&origin) else {
+        return Ok(None);
+    };
+    if let Ok(records) = txt_records(&resolver, format!("AS{}.{}", info.asn, CYMRU_ASN)).await {
+        info.name = parse_cymru_asn_name(&records).unwrap_or_default();
+    }
+    Ok(Some(info))
+}
+
+async fn ptr_lookup(ip: IpAddr, config: &OsintConfig) -> Result<Vec<String>, String> {
+    let (resolver, _) = crate::dns::build_resolver(config)?;
+    match resolver.reverse_lookup(ip).await {
+        Ok(lookup) => Ok(lookup
+            .iter()
+            .map(|name| name.to_string().trim_end_matches('.').to_lowercase())
+            .collect()),
+        Err(e) => match e.kind() {
+            hickory_resolver::error::ResolveErrorKind::NoRecordsFound { .. } => Ok(Vec::new()),
+            _ => Err(e.to_string()),
+        },
+    }
+}
+
+async fn rdap_lookup(ip: IpAddr, client: &reqwest::Client) -> Result<Option<RdapNetwork>, String> {
+    let response = client
+        .get(format!("{}/{}", RDAP_URL, ip))
+        .header("Accept", "application/rdap+json")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let body = response
+        .error_for_status()
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(parse_rdap(&body))
+}
+
+async fn reverse_ip_lookup(ip: IpAddr, client: &reqwest::Client) -> Result<Vec<String>, String> {
+    let body = client
+        .get(REVERSE_IP_URL)
+        .query(&[("q", ip.to_string())])
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| e.to_string())?
+        .text()
+        .await
+        .map_err(|e| e.to_string())?;
+    parse_reverse_ip(&body)
+}
+
+/// Reúne ASN, red, PTR y dominios co-alojados de una IP.
+pub async fn lookup(ip: &str, config: &OsintConfig) -> Result<IpInfraReport, String> {
+    let ip: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| "Se espera una dirección IP (v4 o v6).".to_string())?;
+    let client = crate::tools::get_http_client(config).await;
+
+    let (cymru, rdap, ptr, cohosted) = tokio::join!(
+        cymru_lookup(ip, config),
+        rdap_lookup(ip, &client),
+        ptr_lookup(ip, config),
+        reverse_ip_lookup(ip, &client),
+    );
+
+    let mut report = IpInfraReport {
+        ip: ip.to_string(),
+        ..Default::default()
+    };
+    let mut fail = |source: &str, e: String| {
+        eprintln!("ERROR [asn]: {} lookup failed for {}: {}", source, ip, e);
+        report.errors.insert(source.to_string(), e);
+    };
+    let (asn, network, ptr, cohosted) = (
+        cymru.unwrap_or_else(|e| {
+            fail("cymru", e);
+            None
+        }),
+        rdap.unwrap_or_else(|e| {
+            fail("rdap", e);
+            None
+        }),
+        ptr.unwrap_or_else(|e| {
+            fail("ptr", e);
+            Vec::new()
+        }),
+        cohosted.map_err(|e| fail(REVERSE_IP_SOURCE, e)).ok(),
+    );
+    report.asn = asn;
+    report.network = network;
+    report.ptr = ptr;
+    if let Some(domains) = cohosted {
+        report.cohosted = domains;
+        report.cohosted_source = Some(REVERSE_IP_SOURCE.to_string());
+    }
+
+    if report.asn.is_none() && report.network.is_none() {
+        return Err("No se pudo determinar el ASN ni la red de la IP.".to_string());
+    }
+    Ok(report)
+}
+
+/// Guarda el informe en el objetivo IP: detalle técnico, atributos consultables, vínculos a
+/// dominios y `mismo_asn` con las otras IPs del caso. Devuelve esas IPs.
+pub fn register(
+    case_manager: &CaseManager,
+    case_name: &str,
+    report: &IpInfraReport,
+) -> Result<Vec<String>, String> {
+    let ip_id = case_manager.save_tool_details(
+        case_name,
+        &report.ip,
+        TargetType::IP,
+        "infraestructura",
+        report.case_details(),
+    )?;
+
+    let mut attributes: Vec<(&str, String)> = Vec::new();
+    if let Some(asn) = &report.asn {
+        attributes.push(("asn", format!("AS{}", asn.asn)));
+        attributes.push(("prefijo", asn.prefix.clone()));
+        attributes.push(("titular_asn", asn.name.clone()));
+    }
+    if let Some(network) = &report.network {
+        attributes.push(("red", network.name.clone()));
+        if let Some(holder) = &network.holder {
+            attributes.push(("titular_red", holder.clone()));
+        }
+    }
+
+    let mut same_asn = Vec::new();
+    for (key, value) in attributes.iter().filter(|(_, v)| !v.is_empty()) {
+        if *key == "asn" {
+            for (other_id, other_name) in
+                case_manager.find_targets_with_attribute(case_name, key, value)?
+            {
+                if other_id != ip_id {
+                    case_manager.add_link(case_name, &ip_id, &other_id, "mismo_asn")?;
+                    same_asn.push(other_name);
+                }
+            }
+        }
+        case_manager.add_attribute(case_name, &ip_id, key, value, "Technical")?;
+    }
+
+    case_manager.link_related(case_name, &report.related_targets())?;
+    Ok(same_asn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_cymru_y_arma_consultas() {
+        assert_eq!(
+            cymru_origin_name("8.8.4.4".parse().unwrap()),
+            "4.4.8.8.origin.asn.cymru.com"
+        );
+        let v6 = cymru_origin_name("2001:db8::1".parse().unwrap());
+        assert!(v6.starts_with("1.0.0.0.0.0.0.0."));
+        assert!(v6.ends_with("8.b.d.0.1.0.0.2.origin6.asn.cymru.com"));
+
+        let origin = vec![
+            "\"15169 | 8.8.0.0/16 | US | arin | 1992-12-01\"".to_string(),
+            "15169 19527 | 8.8.4.0/24 | US | arin | 2023-12-28".to_string(),
+        ];
+        let info = parse_cymru_origin(&origin).unwrap();
+        assert_eq!(info.asn, 15169);
+        assert_eq!(info.prefix, "8.8.4.0/24");
+        assert_eq!(info.allocated, "2023-12-28");
+        assert_eq!(parse_cymru_origin(&["basura".to_string()]), None);
+        assert_eq!(
+            parse_cymru_asn_name(&[
+                "15169 | US | arin | 2000-03-30 | GOOGLE - Google LLC, US".to_string()
+            ]),
+            Some("GOOGLE - Google LLC, US".to_string())
+        );
+
+        assert_eq!(
+            parse_reverse_ip("ejemplo.com\nWWW.Ejemplo.com.\nejemplo.com\n1.2.3.4\n").unwrap(),
+            vec!["ejemplo.com".to_string(), "www.ejemplo.com".to_string()]
+        );
+        assert!(parse_reverse_ip("API count exceeded - Increase Quota with Membership").is_err());
+        assert!(parse_reverse_ip("No DNS A records found for 10.0.0.1")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parsea_rdap_y_relaciones() {
+        let body = r#"{
+            "objectClassName": "ip network",
+            "handle": "NET-8-8-4-0-1",
+            "startAddress": "8.8.4.0",
+            "endAddress": "8.8.4.255",
+            "name": "GOGL",
+            "country": "US",
+            "cidr0_cidrs": [{"v4prefix": "8.8.4.0", "length": 24}],
+            "entities": [
+                {"roles": ["abuse"], "vcardArray": ["vcard", [["fn", {}, "text", "Abuse"]]]},
+                {"roles": ["registrant"], "vcardArray": ["vcard", [["version", {}, "text", "4.0"], ["fn", {}, "text", "Google LLC"]]]}
+            ]
+        }"#;
+        let network = parse_rdap(body).unwrap();
+        assert_eq!(network.handle, "NET-8-8-4-0-1");
+        assert_eq!(network.cidr, vec!["8.8.4.0/24".to_string()]);
+        assert_eq!(network.holder.as_deref(), Some("Google LLC"));
+        assert_eq!(parse_rdap(r#"{"objectClassName": "domain"}"#), None);
+
+        let report = IpInfraReport {
+            ip: "8.8.4.4".to_string(),
+            ptr: vec!["dns.google".to_string()],
+            cohosted: vec!["dns.google".to_string(), "otro.com".to_string()],
+            ..Default::default()
+        };
+        let related = report.related_targets();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].relation, "ptr");
+        assert_eq!(related[1].name, "otro.com");
+        assert_eq!(related[1].relation, "comparte_ip");
+        assert_eq!(related[1].source_type, TargetType::IP);
+
+        // Un hosting compartido no llena el caso de dominios
+        let shared = IpInfraReport {
+            cohosted: (0..500).map(|i| format!("d{}.com", i)).collect(),
+            ..report
+        };
+        let details = shared.case_details();
+        assert_eq!(details["cohosted_total"], 500);
+        assert_eq!(
+            details["cohosted"].as_array().unwrap().len(),
+            MAX_COHOSTED_LINKS
+        );
+    }
+}
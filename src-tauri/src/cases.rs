@@ -1,13 +1,13 @@
 use crate::models::{Address, Job, Nickname, Person, SocialProfile};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection, Result as SqlResult};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqlResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use uuid::Uuid;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum TargetType {
     Person,
     Domain,
@@ -17,9 +17,53 @@ pub enum TargetType {
     Phone,
     File,
     Hash,
+    /// Coordenadas "lat,lon" (ej: posición GPS de una foto)
+    Location,
     Other,
 }
 
+impl TargetType {
+    /// Tipo a partir de su nombre ("Domain", "Username"...); lo desconocido es `Other`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Domain" => TargetType::Domain,
+            "IP" => TargetType::IP,
+            "Email" => TargetType::Email,
+            "Username" => TargetType::Username,
+            "Phone" => TargetType::Phone,
+            "File" => TargetType::File,
+            "Hash" => TargetType::Hash,
+            "Location" => TargetType::Location,
+            "Person" => TargetType::Person,
+            _ => TargetType::Other,
+        }
+    }
+
+    /// Nombre de un objetivo Location: "lat,lon" con 6 decimales (~10 cm), para que la misma
+    /// posición caiga en el mismo objetivo venga de una foto o de una IP.
+    pub fn location_name(latitude: f64, longitude: f64) -> String {
+        format!("{:.6},{:.6}", latitude, longitude)
+    }
+
+    /// Nombre de un objetivo File. Los remotos se identifican por URL; los locales por nombre
+    /// de archivo más el inicio del SHA-256, para que dos `IMG_0001.jpg` distintas no se
+    /// mezclen y los resultados de hashes, metadatos, imagen y rostros caigan juntos.
+    pub fn file_name(source: &str, sha256: &str) -> String {
+        let source = source.trim();
+        if source.starts_with("http://") || source.starts_with("https://") {
+            return source.to_string();
+        }
+        let base = std::path::Path::new(source)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| source.to_string());
+        match sha256.get(..12) {
+            Some(prefix) => format!("{} ({})", base, prefix),
+            None => base,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ObjectiveStatus {
     Pending,
@@ -44,6 +88,16 @@ pub struct TargetLink {
     pub relation: String,
 }
 
+/// Relación descubierta por una herramienta: `source` -> `name`. Ambos objetivos se crean si faltan.
+#[derive(Debug, Clone)]
+pub struct RelatedTarget {
+    pub source: String,
+    pub source_type: TargetType,
+    pub name: String,
+    pub target_type: TargetType,
+    pub relation: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Target {
     pub id: String,
@@ -64,6 +118,20 @@ pub struct LogEntry {
     pub tool_name: Option<String>,
 }
 
+/// Hecho fechado de un objetivo o persona (una filtración, una publicación...) para la
+/// cronología del caso. `date` es la fecha del hecho, no la de su registro.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TimelineEvent {
+    pub id: String,
+    pub subject_id: String,
+    pub subject_name: String,
+    pub date: Option<String>,
+    pub kind: String,
+    pub title: String,
+    pub details: String,
+    pub source: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CaseMetadata {
     pub name: String,
@@ -73,6 +141,125 @@ pub struct CaseMetadata {
     pub targets: Vec<Target>,
 }
 
+/// Clave de búsqueda por nombre: sin espacios en los extremos y en minúsculas (Unicode,
+/// no solo ASCII como `lower()` de SQLite).
+fn name_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Arma un objetivo desde una fila `id, name, type, category, metadata, created_at`.
+fn target_from_row(row: &rusqlite::Row) -> SqlResult<Target> {
+    let id: String = row.get(0)?;
+    let name: String = row.get(1)?;
+    let type_str: String = row.get(2)?;
+    let category: String = row.get(3).unwrap_or_else(|_| "Technical".to_string());
+    let metadata_str: Option<String> = row.get(4).ok();
+    let created_at: String = row.get(5)?;
+
+    let target_type = TargetType::from_name(&type_str);
+
+    let mut data: HashMap<String, serde_json::Value> = HashMap::new();
+    if let Some(m_str) = metadata_str {
+        // Función recursiva para desempaquetar CUALQUIER nivel de serialización JSON en strings
+        fn deep_rescue(v: serde_json::Value) -> serde_json::Value {
+            match v {
+                serde_json::Value::String(s) => {
+                    let trimmed = s.trim();
+                    if (trimmed.starts_with('{') && trimmed.ends_with('}'))
+                        || (trimmed.starts_with('[') && trimmed.ends_with(']'))
+                    {
+                        if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(trimmed) {
+                            deep_rescue(parsed)
+                        } else {
+                            serde_json::Value::String(s)
+                        }
+                    } else {
+                        serde_json::Value::String(s)
+                    }
+                }
+                serde_json::Value::Object(map) => {
+                    let mut new_map = serde_json::Map::new();
+                    for (k, val) in map {
+                        new_map.insert(k, deep_rescue(val));
+                    }
+                    serde_json::Value::Object(new_map)
+                }
+                serde_json::Value::Array(arr) => {
+                    serde_json::Value::Array(arr.into_iter().map(deep_rescue).collect())
+                }
+                _ => v,
+            }
+        }
+
+        if let Ok(mut parsed) = serde_json::from_str::<HashMap<String, serde_json::Value>>(&m_str) {
+            // 1. Limpiar y rescatar todo el mapa
+            let mut rescued_map = HashMap::new();
+            for (k, v) in parsed.drain() {
+                rescued_map.insert(k, deep_rescue(v));
+            }
+
+            // 2. Consolidar herramientas técnicas en 'detalles_tecnicos'
+            let mut tech_map = rescued_map
+                .remove("detalles_tecnicos")
+                .and_then(|v| v.as_object().cloned())
+                .unwrap_or_default();
+
+            let tools = ["whois", "ping", "dns", "nmap", "traceroute", "curl", "http"];
+            for tool in tools {
+                if let Some(val) = rescued_map.remove(tool) {
+                    // Si el dato ya venía estructurado como { detalles_tecnicos: { tool: ... } } por error previo
+                    let inner = val
+                        .get("detalles_tecnicos")
+                        .and_then(|dt| dt.get(tool))
+                        .unwrap_or(&val)
+                        .clone();
+                    tech_map.insert(tool.to_string(), inner);
+                }
+            }
+
+            rescued_map.insert(
+                "detalles_tecnicos".to_string(),
+                serde_json::Value::Object(tech_map),
+            );
+            data = rescued_map;
+        }
+    }
+
+    Ok(Target {
+        id,
+        name,
+        target_type,
+        category,
+        data,
+        linked_targets: vec![],
+        created_at: DateTime::parse_from_rfc3339(&created_at)
+            .unwrap_or_else(|_| Utc::now().into())
+            .with_timezone(&Utc),
+    })
+}
+
+/// Carga los vínculos salientes del objetivo (donde es source).
+fn load_links(conn: &Connection, target: &mut Target) -> Result<(), String> {
+    let mut link_stmt = conn
+        .prepare("SELECT target_id, relation FROM links WHERE source_id = ?1")
+        .map_err(|e| e.to_string())?;
+    let link_rows = link_stmt
+        .query_map(params![target.id], |row| {
+            Ok(TargetLink {
+                target_id: row.get(0)?,
+                relation: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    for link in link_rows {
+        if let Ok(l) = link {
+            target.linked_targets.push(l);
+        }
+    }
+    Ok(())
+}
+
 pub struct CaseManager {
     base_path: PathBuf,
 }
@@ -110,6 +297,22 @@ impl CaseManager {
         // Intentar añadir columnas si la tabla ya existía (migración liviana)
         let _ = conn.execute("ALTER TABLE targets ADD COLUMN category TEXT", []);
         let _ = conn.execute("ALTER TABLE targets ADD COLUMN metadata TEXT", []);
+        let _ = conn.execute("ALTER TABLE targets ADD COLUMN name_key TEXT", []);
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_targets_name_key ON targets(name_key)",
+            [],
+        )?;
+        // Objetivos guardados antes de la columna: se completa su clave una sola vez
+        let pending: Vec<(String, String)> = conn
+            .prepare("SELECT id, name FROM targets WHERE name_key IS NULL")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<SqlResult<_>>()?;
+        for (id, name) in pending {
+            conn.execute(
+                "UPDATE targets SET name_key = ?1 WHERE id = ?2",
+                params![name_key(&name), id],
+            )?;
+        }
 
         conn.execute(
             "CREATE TABLE IF NOT EXISTS attributes (
@@ -144,6 +347,20 @@ impl CaseManager {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS events (
+                id TEXT PRIMARY KEY,
+                subject_id TEXT NOT NULL, -- objetivo o persona
+                subject_name TEXT NOT NULL,
+                date TEXT, -- fecha del hecho (AAAA-MM-DD), puede ser desconocida
+                kind TEXT NOT NULL,
+                title TEXT NOT NULL,
+                details TEXT,
+                source TEXT
+            )",
+            [],
+        )?;
+
         conn.execute(
             "CREATE TABLE IF NOT EXISTS objectives (
                 id TEXT PRIMARY KEY,
@@ -312,10 +529,18 @@ impl CaseManager {
             serde_json::to_string(&target.data).unwrap_or_else(|_| "{}".to_string());
 
         conn.execute(
-            "INSERT INTO targets (id, name, type, category, metadata, created_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)
-             ON CONFLICT(id) DO UPDATE SET name=?2, type=?3, category=?4, metadata=?5",
-            params![target.id, target.name, t_type, category, metadata_json, now],
+            "INSERT INTO targets (id, name, type, category, metadata, created_at, name_key) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(id) DO UPDATE SET name=?2, type=?3, category=?4, metadata=?5, name_key=?7",
+            params![
+                target.id,
+                target.name,
+                t_type,
+                category,
+                metadata_json,
+                now,
+                name_key(&target.name)
+            ],
         )
         .map_err(|e| {
             eprintln!("ERROR [cases]: Upsert target failure: {}", e);
@@ -325,6 +550,73 @@ impl CaseManager {
         Ok(())
     }
 
+    /// Busca un objetivo por nombre (sin distinguir mayúsculas).
+    pub fn find_target_by_name(
+        &self,
+        case_name: &str,
+        name: &str,
+    ) -> Result<Option<Target>, String> {
+        // Consulta puntual por índice: se llama una vez por cada objetivo vinculado
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let found = conn
+            .query_row(
+                "SELECT id, name, type, category, metadata, created_at FROM targets
+                 WHERE name_key = ?1 LIMIT 1",
+                params![name_key(name)],
+                target_from_row,
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+        match found {
+            Some(mut target) => {
+                load_links(&conn, &mut target)?;
+                Ok(Some(target))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Guarda el resultado estructurado de una herramienta bajo `detalles_tecnicos.<tool>`
+    /// del objetivo con ese nombre, creándolo si todavía no existe.
+    /// Devuelve el ID del objetivo para poder vincularlo.
+    pub fn save_tool_details(
+        &self,
+        case_name: &str,
+        name: &str,
+        target_type: TargetType,
+        tool: &str,
+        details: serde_json::Value,
+    ) -> Result<String, String> {
+        let mut target = match self.find_target_by_name(case_name, name)? {
+            Some(existing) => existing,
+            None => Target {
+                id: Uuid::new_v4().to_string(),
+                name: name.trim().to_string(),
+                target_type,
+                category: "Technical".to_string(),
+                data: HashMap::new(),
+                linked_targets: vec![],
+                created_at: Utc::now(),
+            },
+        };
+
+        let mut tech_map = target
+            .data
+            .remove("detalles_tecnicos")
+            .and_then(|v| v.as_object().cloned())
+            .unwrap_or_default();
+        tech_map.insert(tool.to_string(), details);
+        target.data.insert(
+            "detalles_tecnicos".to_string(),
+            serde_json::Value::Object(tech_map),
+        );
+
+        let id = target.id.clone();
+        let category = target.category.clone();
+        self.upsert_target_with_cat(case_name, target, &category)?;
+        Ok(id)
+    }
+
     pub fn delete_target(&self, case_name: &str, target_id: &str) -> Result<(), String> {
         let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
 
@@ -342,7 +634,14 @@ impl CaseManager {
         )
         .map_err(|e| format!("Error eliminando atributos del objetivo: {}", e))?;
 
-        // 3. Borrar el objetivo propiamente dicho
+        // 3. Borrar sus hechos de la cronología (la tabla no tiene FK)
+        conn.execute(
+            "DELETE FROM events WHERE subject_id = ?1",
+            params![target_id],
+        )
+        .map_err(|e| format!("Error eliminando la cronología del objetivo: {}", e))?;
+
+        // 4. Borrar el objetivo propiamente dicho
         conn.execute("DELETE FROM targets WHERE id = ?1", params![target_id])
             .map_err(|e| format!("Error eliminando el objetivo: {}", e))?;
 
@@ -357,141 +656,20 @@ impl CaseManager {
             .map_err(|e| e.to_string())?;
 
         let target_rows = stmt
-            .query_map([], |row| {
-                let id: String = row.get(0)?;
-                let name: String = row.get(1)?;
-                let type_str: String = row.get(2)?;
-                let category: String = row.get(3).unwrap_or_else(|_| "Technical".to_string());
-                let metadata_str: Option<String> = row.get(4).ok();
-                let created_at: String = row.get(5)?;
-
-                let target_type = match type_str.as_str() {
-                    "Domain" => TargetType::Domain,
-                    "IP" => TargetType::IP,
-                    "Email" => TargetType::Email,
-                    "Username" => TargetType::Username,
-                    "Phone" => TargetType::Phone,
-                    "File" => TargetType::File,
-                    "Hash" => TargetType::Hash,
-                    "Person" => TargetType::Person,
-                    _ => TargetType::Other,
-                };
-
-                let mut data: HashMap<String, serde_json::Value> = HashMap::new();
-                if let Some(m_str) = metadata_str {
-                    // Función recursiva para desempaquetar CUALQUIER nivel de serialización JSON en strings
-                    fn deep_rescue(v: serde_json::Value) -> serde_json::Value {
-                        match v {
-                            serde_json::Value::String(s) => {
-                                let trimmed = s.trim();
-                                if (trimmed.starts_with('{') && trimmed.ends_with('}'))
-                                    || (trimmed.starts_with('[') && trimmed.ends_with(']'))
-                                {
-                                    if let Ok(parsed) =
-                                        serde_json::from_str::<serde_json::Value>(trimmed)
-                                    {
-                                        deep_rescue(parsed)
-                                    } else {
-                                        serde_json::Value::String(s)
-                                    }
-                                } else {
-                                    serde_json::Value::String(s)
-                                }
-                            }
-                            serde_json::Value::Object(map) => {
-                                let mut new_map = serde_json::Map::new();
-                                for (k, val) in map {
-                                    new_map.insert(k, deep_rescue(val));
-                                }
-                                serde_json::Value::Object(new_map)
-                            }
-                            serde_json::Value::Array(arr) => {
-                                serde_json::Value::Array(arr.into_iter().map(deep_rescue).collect())
-                            }
-                            _ => v,
-                        }
-                    }
-
-                    if let Ok(mut parsed) =
-                        serde_json::from_str::<HashMap<String, serde_json::Value>>(&m_str)
-                    {
-                        // 1. Limpiar y rescatar todo el mapa
-                        let mut rescued_map = HashMap::new();
-                        for (k, v) in parsed.drain() {
-                            rescued_map.insert(k, deep_rescue(v));
-                        }
-
-                        // 2. Consolidar herramientas técnicas en 'detalles_tecnicos'
-                        let mut tech_map = rescued_map
-                            .remove("detalles_tecnicos")
-                            .and_then(|v| v.as_object().cloned())
-                            .unwrap_or_default();
-
-                        let tools = ["whois", "ping", "dns", "nmap", "traceroute", "curl", "http"];
-                        for tool in tools {
-                            if let Some(val) = rescued_map.remove(tool) {
-                                // Si el dato ya venía estructurado como { detalles_tecnicos: { tool: ... } } por error previo
-                                let inner = val
-                                    .get("detalles_tecnicos")
-                                    .and_then(|dt| dt.get(tool))
-                                    .unwrap_or(&val)
-                                    .clone();
-                                tech_map.insert(tool.to_string(), inner);
-                            }
-                        }
-
-                        rescued_map.insert(
-                            "detalles_tecnicos".to_string(),
-                            serde_json::Value::Object(tech_map),
-                        );
-                        data = rescued_map;
-                    }
-                }
-
-                Ok(Target {
-                    id,
-                    name,
-                    target_type,
-                    category,
-                    data,
-                    linked_targets: vec![],
-                    created_at: DateTime::parse_from_rfc3339(&created_at)
-                        .unwrap_or_else(|_| Utc::now().into())
-                        .with_timezone(&Utc),
-                })
-            })
+            .query_map([], target_from_row)
             .map_err(|e| e.to_string())?;
 
         let mut targets = Vec::new();
         for t_res in target_rows {
             let mut target = t_res.map_err(|e| e.to_string())?;
-
-            // Cargar links para este target (donde target es source)
-            let mut link_stmt = conn
-                .prepare("SELECT target_id, relation FROM links WHERE source_id = ?1")
-                .map_err(|e| e.to_string())?;
-            let link_rows = link_stmt
-                .query_map(params![target.id], |row| {
-                    Ok(TargetLink {
-                        target_id: row.get(0)?,
-                        relation: row.get(1)?,
-                    })
-                })
-                .map_err(|e| e.to_string())?;
-
-            for link in link_rows {
-                if let Ok(l) = link {
-                    target.linked_targets.push(l);
-                }
-            }
-
+            load_links(&conn, &mut target)?;
             targets.push(target);
         }
 
         Ok(targets)
     }
 
-    #[allow(dead_code)]
+    /// Crea el vínculo si no existe ya uno idéntico (misma dirección y relación).
     pub fn add_link(
         &self,
         case_name: &str,
@@ -501,13 +679,152 @@ impl CaseManager {
     ) -> Result<(), String> {
         let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
         conn.execute(
-            "INSERT INTO links (source_id, target_id, relation) VALUES (?1, ?2, ?3)",
+            "INSERT INTO links (source_id, target_id, relation)
+             SELECT ?1, ?2, ?3
+             WHERE NOT EXISTS (
+                SELECT 1 FROM links WHERE source_id = ?1 AND target_id = ?2 AND relation = ?3
+             )",
             params![source_id, target_id, relation],
         )
         .map_err(|e| e.to_string())?;
         Ok(())
     }
 
+    /// Devuelve el id del objetivo con ese nombre, creándolo vacío si no existe.
+    pub fn ensure_target(
+        &self,
+        case_name: &str,
+        name: &str,
+        target_type: TargetType,
+    ) -> Result<String, String> {
+        if let Some(existing) = self.find_target_by_name(case_name, name)? {
+            return Ok(existing.id);
+        }
+
+        let target = Target {
+            id: Uuid::new_v4().to_string(),
+            name: name.trim().to_string(),
+            target_type,
+            category: "Technical".to_string(),
+            data: HashMap::new(),
+            linked_targets: vec![],
+            created_at: Utc::now(),
+        };
+        let id = target.id.clone();
+        self.upsert_target_with_cat(case_name, target, "Technical")?;
+        Ok(id)
+    }
+
+    /// Materializa las relaciones descubiertas por una herramienta como objetivos vinculados.
+    /// Devuelve la cantidad de vínculos procesados.
+    pub fn link_related(
+        &self,
+        case_name: &str,
+        related: &[RelatedTarget],
+    ) -> Result<usize, String> {
+        let mut count = 0;
+        for rel in related {
+            if rel.source.eq_ignore_ascii_case(&rel.name) {
+                continue;
+            }
+            let source_id = self.ensure_target(case_name, &rel.source, rel.source_type.clone())?;
+            let target_id = self.ensure_target(case_name, &rel.name, rel.target_type.clone())?;
+            self.add_link(case_name, &source_id, &target_id, &rel.relation)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Registra un atributo clave/valor del objetivo (sin duplicar el mismo par).
+    pub fn add_attribute(
+        &self,
+        case_name: &str,
+        target_id: &str,
+        key: &str,
+        value: &str,
+        category: &str,
+    ) -> Result<(), String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT INTO attributes (target_id, key, value, category)
+             SELECT ?1, ?2, ?3, ?4
+             WHERE NOT EXISTS (
+                SELECT 1 FROM attributes WHERE target_id = ?1 AND key = ?2 AND value = ?3
+             )",
+            params![target_id, key, value, category],
+        )
+        .map_err(|e| format!("Error guardando atributo: {}", e))?;
+        Ok(())
+    }
+
+    /// Objetivos (id, nombre) que comparten el mismo valor para un atributo.
+    /// Es la base para correlacionar, por ejemplo, dominios con el mismo ID de analytics.
+    pub fn find_targets_with_attribute(
+        &self,
+        case_name: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<Vec<(String, String)>, String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT t.id, t.name FROM attributes a
+                 JOIN targets t ON t.id = a.target_id
+                 WHERE a.key = ?1 AND a.value = ?2",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![key, value], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }
+
+    /// Todos los valores (id, nombre, valor) de un atributo en el caso. Para comparaciones
+    /// por similitud (hashes difusos) donde no alcanza con la igualdad exacta.
+    pub fn list_attribute_values(
+        &self,
+        case_name: &str,
+        key: &str,
+    ) -> Result<Vec<(String, String, String)>, String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.name, a.value FROM attributes a
+                 JOIN targets t ON t.id = a.target_id
+                 WHERE a.key = ?1",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![key], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }
+
+    /// Objetivos (id, nombre) a los que apunta `source_id` con esa relación.
+    pub fn find_linked(
+        &self,
+        case_name: &str,
+        source_id: &str,
+        relation: &str,
+    ) -> Result<Vec<(String, String)>, String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT t.id, t.name FROM links l
+                 JOIN targets t ON t.id = l.target_id
+                 WHERE l.source_id = ?1 AND l.relation = ?2",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![source_id, relation], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }
+
     pub fn list_cases(&self) -> Result<Vec<String>, String> {
         let mut cases = Vec::new();
         if let Ok(entries) = fs::read_dir(&self.base_path) {
@@ -551,8 +868,9 @@ impl CaseManager {
 
     // --- PERSONS CRUD ---
 
-    pub fn create_person(&self, case_name: &str, person: Person) -> Result<Person, String> {
+    pub fn create_person(&self, case_name: &str, mut person: Person) -> Result<Person, String> {
         let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        person.phone = normalize_phone_field(person.phone);
 
         conn.execute(
             "INSERT INTO persons (id, first_name, last_name, dni, birth_date, phone, email, created_at)
@@ -760,11 +1078,18 @@ impl CaseManager {
         // Gracias a ON DELETE CASCADE, esto borrará direcciones, trabajos y redes sociales.
         conn.execute("DELETE FROM persons WHERE id = ?1", params![person_id])
             .map_err(|e| format!("Error deleting person: {}", e))?;
+        // La cronología no tiene FK: sus hechos se borran a mano
+        conn.execute(
+            "DELETE FROM events WHERE subject_id = ?1",
+            params![person_id],
+        )
+        .map_err(|e| format!("Error deleting person events: {}", e))?;
         Ok(())
     }
 
-    pub fn update_person_basic(&self, case_name: &str, person: Person) -> Result<(), String> {
+    pub fn update_person_basic(&self, case_name: &str, mut person: Person) -> Result<(), String> {
         let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        person.phone = normalize_phone_field(person.phone);
         conn.execute(
             "UPDATE persons SET first_name=?1, last_name=?2, dni=?3, birth_date=?4, phone=?5, email=?6 WHERE id=?7",
             params![
@@ -961,6 +1286,58 @@ impl CaseManager {
         Ok(logs)
     }
 
+    /// Agrega un hecho a la cronología si el sujeto no tiene ya uno del mismo tipo y título.
+    /// Devuelve `true` si se insertó.
+    pub fn add_event(&self, case_name: &str, event: &TimelineEvent) -> Result<bool, String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let inserted = conn
+            .execute(
+                "INSERT INTO events (id, subject_id, subject_name, date, kind, title, details, source)
+                 SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8
+                 WHERE NOT EXISTS (
+                    SELECT 1 FROM events WHERE subject_id = ?2 AND kind = ?5 AND title = ?6
+                 )",
+                params![
+                    event.id,
+                    event.subject_id,
+                    event.subject_name,
+                    event.date,
+                    event.kind,
+                    event.title,
+                    event.details,
+                    event.source
+                ],
+            )
+            .map_err(|e| format!("Error guardando evento: {}", e))?;
+        Ok(inserted > 0)
+    }
+
+    /// Cronología del caso, del hecho más antiguo al más reciente (sin fecha al final).
+    pub fn get_timeline(&self, case_name: &str) -> Result<Vec<TimelineEvent>, String> {
+        let conn = self.get_db_conn(case_name).map_err(|e| e.to_string())?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, subject_id, subject_name, date, kind, title, details, source FROM events
+                 ORDER BY date IS NULL, date ASC, subject_name ASC",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(TimelineEvent {
+                    id: row.get(0)?,
+                    subject_id: row.get(1)?,
+                    subject_name: row.get(2)?,
+                    date: row.get(3)?,
+                    kind: row.get(4)?,
+                    title: row.get(5)?,
+                    details: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                    source: row.get::<_, Option<String>>(7)?.unwrap_or_default(),
+                })
+            })
+            .map_err(|e| e.to_string())?;
+        Ok(rows.flatten().collect())
+    }
+
     // --- OBJECTIVES CRUD ---
 
     pub fn create_objective(
@@ -1060,3 +1437,68 @@ impl CaseManager {
         Ok(())
     }
 }
+
+/// Teléfono de una persona en E.164 para que el mismo número no quede guardado en varios
+/// formatos. Si no se puede interpretar se conserva tal cual (mejor eso que perder el dato).
+fn normalize_phone_field(phone: Option<String>) -> Option<String> {
+    let phone = phone
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())?;
+    Some(crate::phone::normalize(&phone).unwrap_or(phone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_busca_objetivo_por_nombre_sin_mayusculas() {
+        let dir = std::env::temp_dir().join(format!("sodiic_cases_{}", Uuid::new_v4()));
+        let manager = CaseManager::new(dir.clone());
+        manager.create_case("caso", "").unwrap();
+
+        let id = manager
+            .ensure_target("caso", " José.Núñez@Ejemplo.com ", TargetType::Email)
+            .unwrap();
+        let found = manager
+            .find_target_by_name("caso", "josé.núñez@ejemplo.com")
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, id);
+        assert_eq!(found.name, "José.Núñez@Ejemplo.com");
+        assert_eq!(
+            manager
+                .ensure_target("caso", "JOSÉ.NÚÑEZ@EJEMPLO.COM", TargetType::Email)
+                .unwrap(),
+            id
+        );
+        assert!(manager
+            .find_target_by_name("caso", "otro@ejemplo.com")
+            .unwrap()
+            .is_none());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_nombre_de_archivo_por_contenido() {
+        let sha = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(
+            TargetType::file_name(" /tmp/fotos/IMG_0001.jpg ", sha),
+            "IMG_0001.jpg (ba7816bf8f01)"
+        );
+        assert_ne!(
+            TargetType::file_name("/a/IMG_0001.jpg", sha),
+            TargetType::file_name("/b/IMG_0001.jpg", &sha.replace('b', "c"))
+        );
+        assert_eq!(
+            TargetType::file_name("https://ejemplo.com/a/perfil.jpg", sha),
+            "https://ejemplo.com/a/perfil.jpg"
+        );
+        assert_eq!(TargetType::file_name("perfil.jpg", ""), "perfil.jpg");
+        assert_eq!(
+            TargetType::location_name(-34.6, -58.38),
+            "-34.600000,-58.380000"
+        );
+    }
+}
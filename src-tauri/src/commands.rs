@@ -19,20 +19,39 @@ pub async fn log_info(msg: String) {
 pub async fn run_osint_lookup(
     target: String,
     tool: String,
+    app: AppHandle,
     config: State<'_, Arc<Mutex<OsintConfig>>>,
 ) -> Result<OsintResult, String> {
     let conf = config.lock().await;
     match tool.as_str() {
-        "ping" => Ok(tools::perform_ping(&target).await),
+        "ping" => Ok(tools::perform_ping(&target, &Default::default()).await),
         "whois" => Ok(tools::perform_whois(&target, &*conf).await),
-        "dns" => Ok(tools::perform_dns_lookup(&target).await),
+        "tls" => Ok(tools::inspect_tls(&target, None, &*conf).await),
+        "http" => Ok(tools::http_fingerprint(&target, &*conf).await),
+        "portscan" => Ok(tools::port_scan(&target, &Default::default(), &*conf).await),
+        "dns" => Ok(tools::perform_dns_lookup(&target, &*conf).await),
+        "subdomains" => Ok(tools::discover_subdomains(&target, &Default::default(), &*conf).await),
         "shodan" => Ok(tools::shodan_intel(target, &*conf).await),
         "virustotal" => Ok(tools::virus_total_scan(target, &*conf).await),
-        "ip_intel" => Ok(tools::ip_intel(target, &*conf).await),
-        "username" => Ok(tools::search_username(target, &*conf).await),
+        "hash" => Ok(tools::hash_file(&target, &*conf).await),
+        "image_hash" => Ok(tools::analyze_image(&target, None, &*conf).await),
+        "ip_intel" => {
+            let resource_dir = app.path().resource_dir().unwrap_or_default();
+            Ok(tools::ip_intel(target, &resource_dir, &conf).await)
+        }
+        "ip_infra" => Ok(tools::ip_infrastructure(&target, &*conf).await),
+        "username" => Ok(tools::search_username(&target, &Default::default(), &*conf).await),
+        "email_verify" => Ok(tools::verify_email(&target, &*conf).await),
+        "hunter" => Ok(tools::hunter_domain_search(&target, None, &*conf).await),
+        "phone" => Ok(tools::analyze_phone(&target, None)),
         "dorks" => Ok(tools::generate_dorks(target).await),
         "social" => Ok(tools::social_search(target, &*conf).await),
         "leaks" => Ok(tools::search_leaks(target, &*conf).await),
+        "leaks_domain" => Ok(tools::search_domain_leaks(&target, &*conf).await),
+        "darkweb" => Ok(tools::dark_search(&target).await),
+        "web_search" => Ok(tools::web_scrape_search(&target, &Default::default(), &*conf).await),
+        "onion" => Ok(tools::fetch_onion(&target).await),
+        "wayback" => Ok(tools::wayback_history(&target, &Default::default(), &*conf).await),
         _ => Err("Herramienta no implementada".into()),
     }
 }
@@ -42,21 +61,348 @@ pub async fn extract_metadata(path: String) -> Result<OsintResult, String> {
     Ok(tools::extract_metadata(path).await)
 }
 
+/// Verificación de email desde la interfaz. Con un caso abierto, la dirección queda como
+/// objetivo Email con el veredicto y se registra en la bitácora.
+#[tauri::command]
+pub async fn verify_email_cmd(
+    email: String,
+    case_name: Option<String>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let res = tools::verify_email(&email, &conf).await;
+
+    let report = serde_json::from_str::<crate::email::EmailReport>(&res.data).ok();
+    if let (Some(case_name), Some(report)) = (case_name, report.filter(|r| r.syntax_valid)) {
+        let details = serde_json::to_value(&report).unwrap_or_default();
+        if let Err(e) = case_manager.save_tool_details(
+            &case_name,
+            &report.email,
+            crate::cases::TargetType::Email,
+            "verificacion",
+            details,
+        ) {
+            eprintln!(
+                "ERROR [commands]: No se pudo guardar la verificación: {}",
+                e
+            );
+        }
+        let verdict = serde_json::to_value(report.verdict).unwrap_or_default();
+        let _ = case_manager.log_event(
+            &case_name,
+            "INFO",
+            &format!(
+                "Email {} verificado: {}",
+                report.email,
+                verdict.as_str().unwrap_or_default()
+            ),
+            Some("verificar_email"),
+        );
+    }
+    Ok(res)
+}
+
 #[tauri::command]
 pub async fn web_scrape_search(
     query: String,
+    engines: Option<Vec<String>>,
+    pages: Option<u32>,
+    language: Option<String>,
+    region: Option<String>,
     config: State<'_, Arc<Mutex<OsintConfig>>>,
 ) -> Result<OsintResult, String> {
-    let conf = config.lock().await;
-    Ok(tools::web_scrape_search(query, &*conf).await)
+    let conf = config.lock().await.clone();
+    let options = crate::websearch::SearchOptions {
+        engines: engines
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|e| crate::websearch::SearchEngine::from_name(e))
+            .collect(),
+        pages,
+        language,
+        region,
+    };
+    Ok(tools::web_scrape_search(&query, &options, &conf).await)
+}
+
+#[tauri::command]
+pub async fn run_dorks(
+    target: String,
+    kind: Option<String>,
+    categories: Option<Vec<String>>,
+    engines: Option<Vec<String>>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let options = crate::dorks::DorkOptions {
+        kind: kind.as_deref().and_then(crate::dorks::DorkKind::from_name),
+        categories: categories.unwrap_or_default(),
+        engines: engines
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|e| crate::websearch::SearchEngine::from_name(e))
+            .collect(),
+        delay_ms: None,
+    };
+    Ok(tools::run_dorks(&target, &options, &conf).await)
+}
+
+/// ASN, red, PTR y dominios co-alojados de una IP. Con un caso abierto queda en el objetivo IP.
+#[tauri::command]
+pub async fn ip_infrastructure(
+    ip: String,
+    case_name: Option<String>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let res = tools::ip_infrastructure(&ip, &conf).await;
+
+    let report = serde_json::from_str::<crate::asn::IpInfraReport>(&res.data).ok();
+    if let (Some(case_name), Some(report)) = (case_name, report) {
+        if let Err(e) = crate::asn::register(&case_manager, &case_name, &report) {
+            eprintln!(
+                "ERROR [commands]: No se pudo guardar la infraestructura: {}",
+                e
+            );
+        }
+    }
+    Ok(res)
+}
+
+/// Emails de un dominio (Hunter.io). Con un caso abierto se crean los Emails vinculados al
+/// Dominio y las personas con su cargo.
+#[tauri::command]
+pub async fn hunter_domain_search(
+    domain: String,
+    limit: Option<u32>,
+    case_name: Option<String>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let res = tools::hunter_domain_search(&domain, limit, &conf).await;
+
+    let report = serde_json::from_str::<crate::hunter::DomainEmails>(&res.data).ok();
+    if let (Some(case_name), Some(report)) = (case_name, report) {
+        if let Err(e) = crate::hunter::register(
+            &case_manager,
+            &case_name,
+            &report.domain,
+            report.organization.as_deref(),
+            &report.emails,
+        ) {
+            eprintln!("ERROR [commands]: No se pudieron guardar los emails: {}", e);
+        }
+    }
+    Ok(res)
+}
+
+/// Email de una persona en un dominio (Hunter.io). Con un caso abierto queda guardado.
+/// `with_pattern` pide el patrón del dominio como candidato si Hunter no conoce el email.
+#[tauri::command]
+pub async fn hunter_email_finder(
+    domain: String,
+    first_name: String,
+    last_name: String,
+    with_pattern: Option<bool>,
+    case_name: Option<String>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let res = tools::hunter_email_finder(
+        &domain,
+        &first_name,
+        &last_name,
+        with_pattern.unwrap_or(false),
+        &conf,
+    )
+    .await;
+
+    let result = serde_json::from_str::<crate::hunter::EmailFinderResult>(&res.data).ok();
+    if let (Some(case_name), Some(result)) = (case_name, result) {
+        if let Some(email) = &result.email {
+            if let Err(e) = crate::hunter::register(
+                &case_manager,
+                &case_name,
+                &result.domain,
+                result.organization.as_deref(),
+                std::slice::from_ref(email),
+            ) {
+                eprintln!("ERROR [commands]: No se pudo guardar el email: {}", e);
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Historial del archivo web. Con un caso abierto queda guardado en el objetivo Dominio.
+#[tauri::command]
+pub async fn wayback_history(
+    target: String,
+    match_type: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    case_name: Option<String>,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let options = crate::wayback::WaybackOptions {
+        match_type: match_type
+            .as_deref()
+            .and_then(crate::wayback::MatchType::from_name),
+        from,
+        to,
+        limit: None,
+    };
+    let res = tools::wayback_history(&target, &options, &conf).await;
+
+    let report = serde_json::from_str::<crate::wayback::WaybackReport>(&res.data).ok();
+    if let (Some(case_name), Some(report)) = (case_name, report) {
+        if let Err(e) = case_manager.save_tool_details(
+            &case_name,
+            &crate::wayback::target_host(&target),
+            crate::cases::TargetType::Domain,
+            "wayback",
+            report.case_details(),
+        ) {
+            eprintln!("ERROR [commands]: No se pudo guardar el historial: {}", e);
+        }
+    }
+    Ok(res)
+}
+
+#[tauri::command]
+pub async fn wayback_snapshot(
+    url: String,
+    timestamp: String,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    Ok(tools::wayback_snapshot(&url, &timestamp, &conf).await)
 }
 
+/// Guarda en el caso un resultado de dork elegido por el analista.
 #[tauri::command]
-pub async fn download_face_models(_app: AppHandle) -> Result<OsintResult, String> {
-    // Implementación real pendiente, por ahora avisamos
+pub async fn promote_dork_hit(
+    case_name: String,
+    target: String,
+    kind: String,
+    hit: crate::dorks::DorkHit,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let kind = crate::dorks::DorkKind::from_name(&kind)
+        .unwrap_or_else(|| crate::dorks::DorkKind::detect(&target));
+    match crate::dorks::promote(&case_manager, &case_name, &target, kind, &hit) {
+        Ok(()) => Ok(OsintResult {
+            success: true,
+            data: format!("Resultado guardado en {}.", target.trim()),
+            error: None,
+        }),
+        Err(e) => {
+            eprintln!("ERROR [dorks]: Promotion failed: {}", e);
+            Ok(OsintResult {
+                success: false,
+                data: "".into(),
+                error: Some("No se pudo guardar el resultado en el caso.".into()),
+            })
+        }
+    }
+}
+
+/// Estado de los modelos faciales. No descarga nada: los modelos y ONNX Runtime los copia
+/// el usuario en la carpeta indicada.
+#[tauri::command]
+pub async fn face_models_status(app: AppHandle) -> Result<OsintResult, String> {
+    let dir = crate::faces::models_dir(&app.path().app_data_dir().unwrap_or_default());
+    let status = crate::faces::status(&dir);
+    Ok(OsintResult {
+        success: status.ready,
+        data: serde_json::to_string(&status).unwrap_or_else(|_| "{}".to_string()),
+        error: (!status.ready).then(|| status.missing_message()),
+    })
+}
+
+/// Compara todas las imágenes entre sí (matriz de similitud coseno). Con `case_name`, cada
+/// imagen queda como evidencia en el caso y se vincula con los rostros que ya tenía.
+#[tauri::command]
+pub async fn compare_faces(
+    sources: Vec<String>,
+    case_name: Option<String>,
+    app: AppHandle,
+    config: State<'_, Arc<Mutex<OsintConfig>>>,
+    case_manager: State<'_, Arc<CaseManager>>,
+) -> Result<OsintResult, String> {
+    let conf = config.lock().await.clone();
+    let dir = crate::faces::models_dir(&app.path().app_data_dir().unwrap_or_default());
+
+    let mut reports = Vec::new();
+    for source in &sources {
+        reports.push(crate::faces::analyze(source, &dir, &conf).await);
+    }
+
+    let case_name = case_name.filter(|c| !c.trim().is_empty());
+    let mut images = Vec::new();
+    for report in &reports {
+        let entry = match report {
+            Ok(report) => {
+                if let Some(case) = &case_name {
+                    let name = crate::cases::TargetType::file_name(&report.source, &report.sha256);
+                    if let Err(e) = crate::faces::register(&case_manager, case, &name, report) {
+                        eprintln!("ERROR [faces]: No se pudo guardar la evidencia: {}", e);
+                    }
+                }
+                serde_json::json!({
+                    "source": report.source,
+                    "faces": report.faces.iter().map(|f| &f.bbox).collect::<Vec<_>>(),
+                })
+            }
+            Err(e) => serde_json::json!({ "error": e }),
+        };
+        images.push(entry);
+    }
+
+    // Matriz en porcentaje; None cuando alguna de las dos imágenes no tiene rostros
+    let matrix: Vec<Vec<Option<f32>>> = reports
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            reports
+                .iter()
+                .enumerate()
+                .map(|(j, b)| match (a, b) {
+                    (Ok(a), _) if i == j && !a.faces.is_empty() => Some(100.0),
+                    (Ok(a), Ok(b)) => crate::faces::best_match(a, b)
+                        .map(|m| (m.similarity.max(0.0) * 1000.0).round() / 10.0),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect();
+
+    if reports.iter().all(|r| r.is_err()) {
+        let error = reports
+            .into_iter()
+            .find_map(|r| r.err())
+            .unwrap_or_else(|| "No se indicaron imágenes.".to_string());
+        return Ok(OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(error),
+        });
+    }
     Ok(OsintResult {
         success: true,
-        data: "Modelos listos (Simulado)".into(),
+        data: serde_json::json!({
+            "images": images,
+            "matrix": matrix,
+            "match_threshold": crate::faces::MATCH_THRESHOLD * 100.0,
+            "likely_threshold": crate::faces::LIKELY_THRESHOLD * 100.0,
+        })
+        .to_string(),
         error: None,
     })
 }
@@ -885,6 +1231,26 @@ pub fn get_activity_log_cmd(
     }
 }
 
+/// Cronología del caso: hechos fechados (filtraciones, publicaciones...) de objetivos y personas.
+#[tauri::command]
+pub fn get_case_timeline(
+    case_manager: State<'_, Arc<CaseManager>>,
+    case_name: String,
+) -> Result<OsintResult, String> {
+    match case_manager.get_timeline(&case_name) {
+        Ok(events) => Ok(OsintResult {
+            success: true,
+            data: serde_json::to_string(&events).unwrap_or_default(),
+            error: None,
+        }),
+        Err(e) => Ok(OsintResult {
+            success: false,
+            data: "".to_string(),
+            error: Some(e),
+        }),
+    }
+}
+
 // --- MEMORY COMMANDS ---
 #[tauri::command]
 pub async fn add_memory_cmd(
@@ -0,0 +1,401 @@
+// src-tauri/src/darkweb.rs
+//
+// Búsqueda en la Dark Web (Ahmia) y lectura de páginas .onion, siempre a través de Tor.
+//
+// Flujo:
+//   1. Antes de cualquier petición se exige que el sidecar de Tor haya llegado a
+//      "Bootstrapped 100%". Si no, se rechaza: nada sale por clearnet.
+//   2. El cliente HTTP usa el SOCKS de Tor (socks5h, resolución dentro de la red) sin mirar
+//      `proxy_url`: la configuración puede apuntar a otro proxy o estar vacía.
+//   3. Ahmia se consulta por su servicio .onion. El formulario de búsqueda lleva un campo
+//      oculto anti-bots que se copia de la portada.
+//   4. Las páginas .onion se devuelven como título + texto limpio + enlaces a otros .onion.
+
+use crate::tor_manager;
+use reqwest::{Client, Proxy, Url};
+use scraper::{Html, Node, Selector};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const AHMIA_URL: &str = "http://juhanurmihxlp77nkq76byazcldy2hlmovfu2epvl5ankdibsot4csyd.onion";
+/// Tor es lento: los circuitos hacia un .onion pueden tardar bastante en armarse
+const TOR_TIMEOUT: Duration = Duration::from_secs(90);
+const MAX_TEXT_CHARS: usize = 20_000;
+const MAX_PAGE_BYTES: usize = 5 * 1024 * 1024;
+/// Lo que se guarda en el caso de una página leída: un extracto, no el texto completo
+const CASE_EXCERPT_CHARS: usize = 500;
+const CASE_LINK_LIMIT: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DarkResult {
+    pub title: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub snippet: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DarkSearchReport {
+    pub query: String,
+    pub engine: String,
+    pub results: Vec<DarkResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OnionPage {
+    pub url: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub onion_links: Vec<String>,
+}
+
+impl OnionPage {
+    /// Resumen para `detalles_tecnicos.onion`: título, estado, un extracto corto y los
+    /// primeros enlaces. El texto completo sólo vuelve al agente en la respuesta de la tool.
+    pub fn case_details(&self) -> serde_json::Value {
+        let mut excerpt: String = self.text.chars().take(CASE_EXCERPT_CHARS).collect();
+        if excerpt.len() < self.text.len() {
+            excerpt.push('…');
+        }
+        serde_json::json!({
+            "url": self.url,
+            "estado_http": self.status,
+            "titulo": self.title,
+            "caracteres": self.text.chars().count(),
+            "extracto": excerpt,
+            "enlaces_onion": self.onion_links.len(),
+            "primeros_enlaces": self.onion_links.iter().take(CASE_LINK_LIMIT).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Cliente HTTP forzado por Tor. Falla si Tor no terminó el bootstrap.
+pub fn tor_client() -> Result<Client, String> {
+    if !tor_manager::is_bootstrapped() {
+        return Err(
+            "Tor no está conectado. Active la Red Tor en Configuración y espere a que termine de conectar."
+                .to_string(),
+        );
+    }
+    let proxy = Proxy::all(tor_manager::SOCKS_PROXY).map_err(|e| e.to_string())?;
+    Client::builder()
+        .proxy(proxy)
+        .timeout(TOR_TIMEOUT)
+        // El mismo User-Agent que Tor Browser, para no destacar entre los visitantes
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; rv:128.0) Gecko/20100101 Firefox/128.0")
+        .build()
+        .map_err(|e| {
+            eprintln!("ERROR [darkweb]: Failed to build Tor client: {}", e);
+            "No se pudo preparar la conexión por Tor.".to_string()
+        })
+}
+
+fn is_onion(url: &Url) -> bool {
+    url.host_str()
+        .is_some_and(|h| h.to_ascii_lowercase().ends_with(".onion"))
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Campos ocultos del formulario de búsqueda de Ahmia (token anti-bots).
+fn search_form_fields(html: &str) -> Vec<(String, String)> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("form input[type=hidden]").unwrap();
+    document
+        .select(&selector)
+        .filter_map(|input| {
+            let name = input.value().attr("name")?;
+            Some((
+                name.to_string(),
+                input.value().attr("value").unwrap_or("").to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Extrae los resultados de la página de búsqueda de Ahmia.
+fn parse_ahmia(html: &str) -> Vec<DarkResult> {
+    let document = Html::parse_document(html);
+    let result_sel = Selector::parse("li.result").unwrap();
+    let link_sel = Selector::parse("h4 a").unwrap();
+    let snippet_sel = Selector::parse("p").unwrap();
+    let cite_sel = Selector::parse("cite").unwrap();
+    let seen_sel = Selector::parse(".lastSeen").unwrap();
+    let base = Url::parse(AHMIA_URL).ok();
+
+    let mut results: Vec<DarkResult> = Vec::new();
+    for element in document.select(&result_sel) {
+        let link = element.select(&link_sel).next();
+        // Los enlaces pasan por /search/redirect?redirect_url=...; el destino real va en el
+        // parámetro o, si no, en el <cite>
+        let url = link
+            .and_then(|a| a.value().attr("href"))
+            .and_then(|href| base.as_ref()?.join(href).ok())
+            .and_then(|href| {
+                href.query_pairs()
+                    .find(|(k, _)| k == "redirect_url")
+                    .map(|(_, v)| v.into_owned())
+            })
+            .or_else(|| {
+                element
+                    .select(&cite_sel)
+                    .next()
+                    .map(|c| collapse_whitespace(&c.text().collect::<String>()))
+            })
+            .unwrap_or_default();
+        if url.is_empty() || results.iter().any(|r| r.url == url) {
+            continue;
+        }
+        let title = link
+            .map(|a| collapse_whitespace(&a.text().collect::<String>()))
+            .unwrap_or_default();
+        let snippet = element
+            .select(&snippet_sel)
+            .find(|p| p.value().attr("class").is_none())
+            .map(|p| collapse_whitespace(&p.text().collect::<String>()))
+            .unwrap_or_default();
+        let last_seen = element.select(&seen_sel).next().map(|s| {
+            s.value()
+                .attr("data-timestamp")
+                .map(str::to_string)
+                .unwrap_or_else(|| collapse_whitespace(&s.text().collect::<String>()))
+        });
+        results.push(DarkResult {
+            title,
+            url,
+            snippet,
+            last_seen,
+        });
+    }
+    results
+}
+
+/// Título y texto visible (sin scripts ni estilos) de un documento HTML.
+pub fn page_text(document: &Html) -> (Option<String>, String) {
+    let title = Selector::parse("title")
+        .ok()
+        .and_then(|sel| document.select(&sel).next())
+        .map(|t| collapse_whitespace(&t.text().collect::<String>()))
+        .filter(|t| !t.is_empty());
+
+    let body = Selector::parse("body")
+        .ok()
+        .and_then(|sel| document.select(&sel).next())
+        .map(|b| *b)
+        .unwrap_or_else(|| *document.root_element());
+    let mut text = String::new();
+    for node in body.descendants() {
+        let Node::Text(fragment) = node.value() else {
+            continue;
+        };
+        let hidden = node.ancestors().any(|a| {
+            a.value()
+                .as_element()
+                .is_some_and(|e| matches!(e.name(), "script" | "style" | "noscript" | "template"))
+        });
+        if !hidden && !fragment.trim().is_empty() {
+            text.push_str(fragment);
+            text.push(' ');
+        }
+    }
+    (title, collapse_whitespace(&text))
+}
+
+/// Título, texto visible y enlaces a otros servicios .onion de una página.
+fn parse_page(base: &Url, html: &str) -> (Option<String>, String, Vec<String>) {
+    let document = Html::parse_document(html);
+    let (title, text) = page_text(&document);
+
+    let mut links: Vec<String> = Vec::new();
+    if let Ok(sel) = Selector::parse("a[href]") {
+        for a in document.select(&sel) {
+            let Some(mut url) = a.value().attr("href").and_then(|h| base.join(h).ok()) else {
+                continue;
+            };
+            if !is_onion(&url) || url.host_str() == base.host_str() {
+                continue;
+            }
+            url.set_fragment(None);
+            let url = url.to_string();
+            if !links.contains(&url) {
+                links.push(url);
+            }
+        }
+    }
+    (title, text, links)
+}
+
+/// Busca en Ahmia (índice de servicios .onion) a través de Tor.
+pub async fn search(query: &str) -> Result<DarkSearchReport, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Debe indicar un término de búsqueda.".to_string());
+    }
+    let client = tor_client()?;
+
+    let home = client
+        .get(AHMIA_URL)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| {
+            eprintln!("ERROR [darkweb]: Ahmia connection failed: {}", e);
+            "La conexión con el nodo de búsqueda Onion falló.".to_string()
+        })?
+        .text()
+        .await
+        .unwrap_or_default();
+
+    let mut params = vec![("q".to_string(), query.to_string())];
+    params.extend(search_form_fields(&home));
+    let html = client
+        .get(format!("{}/search/", AHMIA_URL))
+        .query(&params)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| {
+            eprintln!("ERROR [darkweb]: Ahmia search failed: {}", e);
+            "La búsqueda en Ahmia falló.".to_string()
+        })?
+        .text()
+        .await
+        .map_err(|_| "Error leyendo respuesta de Ahmia".to_string())?;
+
+    Ok(DarkSearchReport {
+        query: query.to_string(),
+        engine: "ahmia".to_string(),
+        results: parse_ahmia(&html),
+    })
+}
+
+/// Descarga una página .onion por Tor y la devuelve como texto limpio.
+pub async fn fetch_page(url: &str) -> Result<OnionPage, String> {
+    let url = url.trim();
+    let with_scheme = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    };
+    let parsed = Url::parse(&with_scheme)
+        .ok()
+        .filter(|u| matches!(u.scheme(), "http" | "https") && is_onion(u))
+        .ok_or_else(|| {
+            "La dirección debe ser un servicio .onion (http://....onion/).".to_string()
+        })?;
+    let client = tor_client()?;
+
+    let mut response = client.get(parsed.clone()).send().await.map_err(|e| {
+        eprintln!("ERROR [darkweb]: Onion fetch failed for {}: {}", parsed, e);
+        "No se pudo abrir el servicio .onion (caído o inalcanzable).".to_string()
+    })?;
+    let status = response.status().as_u16();
+    let final_url = response.url().clone();
+    let mut bytes = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_PAGE_BYTES {
+            break;
+        }
+    }
+
+    let html = String::from_utf8_lossy(&bytes);
+    let (title, mut text, onion_links) = parse_page(&final_url, &html);
+    let truncated = text.chars().count() > MAX_TEXT_CHARS;
+    if truncated {
+        text = text.chars().take(MAX_TEXT_CHARS).collect();
+    }
+    Ok(OnionPage {
+        url: final_url.to_string(),
+        status,
+        title,
+        text,
+        truncated,
+        onion_links,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_resultados_ahmia() {
+        let html = r#"<html><body>
+            <form id="searchForm" action="/search/"><input name="q"><input type="hidden" name="b7e1c2" value="9f3a"></form>
+            <ol class="searchResults">
+              <li class="result">
+                <h4><a href="/search/redirect?search_term=foro&redirect_url=http://abcdefghijklmnopqrstuvwxyz234567abcdefghijklmnopqrstuvwx.onion/">Foro  Privado</a></h4>
+                <p>Foro de intercambio
+                   de datos</p>
+                <p class="urlinfo"><cite>http://abcdefghijklmnopqrstuvwxyz234567abcdefghijklmnopqrstuvwx.onion/</cite>
+                   <span class="lastSeen" data-timestamp="1728000000">hace 2 días</span></p>
+              </li>
+              <li class="result">
+                <h4><a href="http://otro.onion/">Sin redirect</a></h4>
+                <p class="urlinfo"><cite>http://otro.onion/</cite></p>
+              </li>
+            </ol></body></html>"#;
+        let results = parse_ahmia(html);
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].url,
+            "http://abcdefghijklmnopqrstuvwxyz234567abcdefghijklmnopqrstuvwx.onion/"
+        );
+        assert_eq!(results[0].title, "Foro Privado");
+        assert_eq!(results[0].snippet, "Foro de intercambio de datos");
+        assert_eq!(results[0].last_seen.as_deref(), Some("1728000000"));
+        assert_eq!(results[1].url, "http://otro.onion/");
+        assert_eq!(
+            search_form_fields(html),
+            vec![("b7e1c2".to_string(), "9f3a".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_limpia_pagina_onion_y_exige_tor() {
+        let base = Url::parse("http://sitio.onion/index.html").unwrap();
+        let html = r#"<html><head><title> Mercado
+            X </title><style>body{color:red}</style></head>
+            <body><script>var a = 1;</script><h1>Bienvenido</h1><p>Contacto:   admin@sitio.onion</p>
+            <a href="/login">login</a> <a href="http://espejo.onion/#top">espejo</a>
+            <a href="https://clearnet.example.com/">afuera</a></body></html>"#;
+        let (title, text, links) = parse_page(&base, html);
+        assert_eq!(title.as_deref(), Some("Mercado X"));
+        assert_eq!(
+            text,
+            "Bienvenido Contacto: admin@sitio.onion login espejo afuera"
+        );
+        assert_eq!(links, vec!["http://espejo.onion/".to_string()]);
+
+        // Al caso sólo llega un extracto del texto
+        let page = OnionPage {
+            url: base.to_string(),
+            status: 200,
+            title,
+            text: "a".repeat(MAX_TEXT_CHARS),
+            truncated: true,
+            onion_links: links,
+        };
+        let details = page.case_details();
+        assert_eq!(details["caracteres"], MAX_TEXT_CHARS);
+        assert_eq!(
+            details["extracto"].as_str().unwrap().chars().count(),
+            CASE_EXCERPT_CHARS + 1
+        );
+        assert_eq!(details["enlaces_onion"], 1);
+
+        // Sin bootstrap de Tor no se arma ningún cliente
+        assert!(!tor_manager::is_bootstrapped());
+        assert!(tor_client().is_err());
+    }
+}
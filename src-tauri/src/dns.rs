@@ -0,0 +1,521 @@
+// src-tauri/src/dns.rs
+//
+// Enumeración DNS completa (más allá de A/AAAA) con resolver configurable.
+//
+// Flujo:
+//   1. Se arma el resolver: servidor elegido en la configuración (`dns_server`) o el del sistema,
+//      por UDP o por TCP (`dns_over_tcp`). Con un proxy configurado se rechaza: las consultas
+//      saldrían directo, por fuera del proxy, y delatarían al objetivo.
+//   2. Para un dominio se consultan en paralelo A, AAAA, CNAME, MX, NS, TXT, SOA, CAA y los SRV
+//      más comunes; luego se hace PTR de las IPs obtenidas.
+//   3. Para una IP sólo se hace la resolución inversa (PTR).
+//   4. El resultado es un `DnsReport` indexado por tipo de registro, listo para
+//      `detalles_tecnicos.dns`.
+
+use crate::cases::{RelatedTarget, TargetType};
+use crate::models::OsintConfig;
+use hickory_resolver::config::{
+    NameServerConfig, NameServerConfigGroup, Protocol, ResolverConfig, ResolverOpts,
+};
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::proto::rr::{RData, RecordType};
+use hickory_resolver::TokioAsyncResolver;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+const DNS_PORT: u16 = 53;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Tope de resoluciones inversas por consulta para no martillar al resolver
+const MAX_PTR_LOOKUPS: usize = 10;
+pub const PROXY_DNS_ERROR: &str =
+    "Con un proxy configurado no se hacen consultas DNS: saldrían directo, por fuera del proxy. Desactive el proxy para usar esta herramienta.";
+
+/// Servicios SRV que suelen delatar infraestructura (correo, VoIP, directorio, chat).
+const SRV_SERVICES: &[&str] = &[
+    "_autodiscover._tcp",
+    "_sip._tcp",
+    "_sip._udp",
+    "_sipfederationtls._tcp",
+    "_xmpp-client._tcp",
+    "_xmpp-server._tcp",
+    "_ldap._tcp",
+    "_kerberos._tcp",
+    "_submission._tcp",
+    "_imaps._tcp",
+    "_caldavs._tcp",
+    "_minecraft._tcp",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MxRecord {
+    pub preference: u16,
+    pub exchange: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SoaRecord {
+    pub mname: String,
+    pub rname: String,
+    pub serial: u32,
+    pub refresh: i32,
+    pub retry: i32,
+    pub expire: i32,
+    pub minimum: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CaaRecord {
+    pub critical: bool,
+    pub tag: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SrvRecord {
+    pub service: String,
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    pub target: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PtrRecord {
+    pub ip: String,
+    pub name: String,
+}
+
+/// Resultado DNS normalizado e indexado por tipo de registro.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DnsReport {
+    pub query: String,
+    /// Resolver usado (ej: "sistema/udp", "tcp:1.1.1.1:53")
+    pub resolver: String,
+    #[serde(rename = "A", skip_serializing_if = "Vec::is_empty", default)]
+    pub a: Vec<String>,
+    #[serde(rename = "AAAA", skip_serializing_if = "Vec::is_empty", default)]
+    pub aaaa: Vec<String>,
+    #[serde(rename = "CNAME", skip_serializing_if = "Vec::is_empty", default)]
+    pub cname: Vec<String>,
+    #[serde(rename = "MX", skip_serializing_if = "Vec::is_empty", default)]
+    pub mx: Vec<MxRecord>,
+    #[serde(rename = "NS", skip_serializing_if = "Vec::is_empty", default)]
+    pub ns: Vec<String>,
+    #[serde(rename = "TXT", skip_serializing_if = "Vec::is_empty", default)]
+    pub txt: Vec<String>,
+    #[serde(rename = "SOA", skip_serializing_if = "Option::is_none", default)]
+    pub soa: Option<SoaRecord>,
+    #[serde(rename = "CAA", skip_serializing_if = "Vec::is_empty", default)]
+    pub caa: Vec<CaaRecord>,
+    #[serde(rename = "SRV", skip_serializing_if = "Vec::is_empty", default)]
+    pub srv: Vec<SrvRecord>,
+    #[serde(rename = "PTR", skip_serializing_if = "Vec::is_empty", default)]
+    pub ptr: Vec<PtrRecord>,
+    /// Errores de consulta que no son "sin registros" (timeouts, SERVFAIL, etc.)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub errors: BTreeMap<String, String>,
+}
+
+impl DnsReport {
+    fn push_unique<T: PartialEq>(list: &mut Vec<T>, value: T) {
+        if !list.contains(&value) {
+            list.push(value);
+        }
+    }
+
+    /// Incorpora un registro de respuesta según su tipo. `service` sólo aplica a SRV.
+    fn absorb(&mut self, rdata: &RData, service: Option<&str>) {
+        match rdata {
+            RData::A(ip) => Self::push_unique(&mut self.a, ip.to_string()),
+            RData::AAAA(ip) => Self::push_unique(&mut self.aaaa, ip.to_string()),
+            RData::CNAME(name) => Self::push_unique(&mut self.cname, fqdn(&name.0.to_utf8())),
+            RData::NS(name) => Self::push_unique(&mut self.ns, fqdn(&name.0.to_utf8())),
+            RData::MX(mx) => Self::push_unique(
+                &mut self.mx,
+                MxRecord {
+                    preference: mx.preference(),
+                    exchange: fqdn(&mx.exchange().to_utf8()),
+                },
+            ),
+            RData::TXT(txt) => {
+                let text: String = txt
+                    .txt_data()
+                    .iter()
+                    .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+                    .collect();
+                Self::push_unique(&mut self.txt, text);
+            }
+            RData::SOA(soa) => {
+                self.soa = Some(SoaRecord {
+                    mname: fqdn(&soa.mname().to_utf8()),
+                    rname: fqdn(&soa.rname().to_utf8()),
+                    serial: soa.serial(),
+                    refresh: soa.refresh(),
+                    retry: soa.retry(),
+                    expire: soa.expire(),
+                    minimum: soa.minimum(),
+                })
+            }
+            RData::CAA(caa) => Self::push_unique(
+                &mut self.caa,
+                CaaRecord {
+                    critical: caa.issuer_critical(),
+                    tag: caa.tag().as_str().to_string(),
+                    value: caa.value().to_string().trim_matches('"').to_string(),
+                },
+            ),
+            RData::SRV(srv) => Self::push_unique(
+                &mut self.srv,
+                SrvRecord {
+                    service: service.unwrap_or_default().to_string(),
+                    priority: srv.priority(),
+                    weight: srv.weight(),
+                    port: srv.port(),
+                    target: fqdn(&srv.target().to_utf8()),
+                },
+            ),
+            _ => {}
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.a.is_empty()
+            && self.aaaa.is_empty()
+            && self.cname.is_empty()
+            && self.mx.is_empty()
+            && self.ns.is_empty()
+            && self.txt.is_empty()
+            && self.soa.is_none()
+            && self.caa.is_empty()
+            && self.srv.is_empty()
+            && self.ptr.is_empty()
+    }
+
+    /// Objetivos derivados de la enumeración (IPs resueltas, servidores de correo/nombres,
+    /// alias y nombres inversos) con la relación que los une al objetivo consultado.
+    pub fn related_targets(&self) -> Vec<RelatedTarget> {
+        let query_type = if self.query.parse::<IpAddr>().is_ok() {
+            TargetType::IP
+        } else {
+            TargetType::Domain
+        };
+        let from_query = |name: &str, target_type: TargetType, relation: &str| RelatedTarget {
+            source: self.query.clone(),
+            source_type: query_type.clone(),
+            name: name.to_string(),
+            target_type,
+            relation: relation.to_string(),
+        };
+
+        let mut related = Vec::new();
+        for ip in self.a.iter().chain(self.aaaa.iter()) {
+            related.push(from_query(ip, TargetType::IP, "resuelve_a"));
+        }
+        for name in &self.cname {
+            related.push(from_query(name, TargetType::Domain, "alias_de"));
+        }
+        for mx in &self.mx {
+            related.push(from_query(
+                &mx.exchange,
+                TargetType::Domain,
+                "servidor_correo",
+            ));
+        }
+        for ns in &self.ns {
+            related.push(from_query(ns, TargetType::Domain, "servidor_nombres"));
+        }
+        for ptr in &self.ptr {
+            related.push(RelatedTarget {
+                source: ptr.ip.clone(),
+                source_type: TargetType::IP,
+                name: ptr.name.clone(),
+                target_type: TargetType::Domain,
+                relation: "ptr".to_string(),
+            });
+        }
+        related
+    }
+}
+
+/// Nombre DNS sin el punto final de la raíz, en minúsculas.
+fn fqdn(name: &str) -> String {
+    name.trim_end_matches('.').to_lowercase()
+}
+
+/// Normaliza la consulta: IP tal cual, o dominio sin esquema, ruta ni punto final.
+fn normalize_query(query: &str) -> Option<String> {
+    let q = query.trim();
+    if let Ok(ip) = q.parse::<IpAddr>() {
+        return Some(ip.to_string());
+    }
+
+    let domain = q
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .split('/')
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('.')
+        .to_lowercase();
+
+    let valid = domain.contains('.')
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_');
+    if valid {
+        Some(domain)
+    } else {
+        None
+    }
+}
+
+/// Interpreta `dns_server` ("8.8.8.8", "8.8.8.8:5353", "[2606:4700::1111]:53").
+fn parse_nameserver(value: &str) -> Result<SocketAddr, String> {
+    let v = value.trim();
+    if let Ok(addr) = v.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    v.trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+        .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        .map_err(|_| format!("Servidor DNS inválido en la configuración: '{}'", value))
+}
+
+/// Arma el resolver según la configuración y devuelve también su descripción. Falla si hay un
+/// proxy configurado: el resolver no puede pasar por él.
+pub fn build_resolver(config: &OsintConfig) -> Result<(TokioAsyncResolver, String), String> {
+    if !config.proxy_url.trim().is_empty() {
+        eprintln!(
+            "ERROR [dns]: Consulta DNS cancelada, no puede pasar por el proxy '{}'",
+            config.proxy_url
+        );
+        return Err(PROXY_DNS_ERROR.to_string());
+    }
+
+    let protocol = if config.dns_over_tcp {
+        Protocol::Tcp
+    } else {
+        Protocol::Udp
+    };
+
+    let mut opts = ResolverOpts::default();
+    opts.timeout = QUERY_TIMEOUT;
+    opts.attempts = 2;
+
+    let server = config.dns_server.trim();
+    let (resolver_config, label) = if server.is_empty() {
+        let (system, _) = hickory_resolver::system_conf::read_system_conf().map_err(|e| {
+            eprintln!("ERROR [dns]: Failed to read system resolver config: {}", e);
+            "No se pudo leer la configuración DNS del sistema.".to_string()
+        })?;
+        let mut group = NameServerConfigGroup::new();
+        for ns in system.name_servers() {
+            if ns.protocol == protocol {
+                group.push(ns.clone());
+            }
+        }
+        (
+            ResolverConfig::from_parts(None, vec![], group),
+            format!("sistema/{}", protocol),
+        )
+    } else {
+        let addr = parse_nameserver(server)?;
+        let mut group = NameServerConfigGroup::new();
+        group.push(NameServerConfig::new(addr, protocol));
+        (
+            ResolverConfig::from_parts(None, vec![], group),
+            format!("{}:{}", protocol, addr),
+        )
+    };
+
+    Ok((TokioAsyncResolver::tokio(resolver_config, opts), label))
+}
+
+/// Ejecuta una consulta y devuelve los datos de respuesta; "sin registros" no es un error.
+async fn query_records(
+    resolver: &TokioAsyncResolver,
+    name: String,
+    record_type: RecordType,
+) -> Result<Vec<RData>, String> {
+    match resolver.lookup(name, record_type).await {
+        Ok(lookup) => Ok(lookup
+            .record_iter()
+            .filter_map(|r| r.data().cloned())
+            .collect()),
+        Err(e) => match e.kind() {
+            ResolveErrorKind::NoRecordsFound { .. } => Ok(vec![]),
+            _ => Err(e.to_string()),
+        },
+    }
+}
+
+/// Enumera todos los registros de un dominio (o el PTR de una IP).
+pub async fn lookup(query: &str, config: &OsintConfig) -> Result<DnsReport, String> {
+    let normalized = normalize_query(query)
+        .ok_or_else(|| "Consulta DNS inválida: se espera un dominio o una IP.".to_string())?;
+    let (resolver, label) = build_resolver(config)?;
+
+    let mut report = DnsReport {
+        query: normalized.clone(),
+        resolver: label,
+        ..Default::default()
+    };
+
+    let mut ptr_ips: Vec<IpAddr> = Vec::new();
+    let mut attempted = 0;
+
+    if let Ok(ip) = normalized.parse::<IpAddr>() {
+        ptr_ips.push(ip);
+    } else {
+        let mut set = JoinSet::new();
+        let base_types = [
+            RecordType::A,
+            RecordType::AAAA,
+            RecordType::CNAME,
+            RecordType::MX,
+            RecordType::NS,
+            RecordType::TXT,
+            RecordType::SOA,
+            RecordType::CAA,
+        ];
+        for record_type in base_types {
+            let resolver = resolver.clone();
+            let name = normalized.clone();
+            set.spawn(async move {
+                let res = query_records(&resolver, name, record_type).await;
+                (record_type.to_string(), None, res)
+            });
+        }
+        for service in SRV_SERVICES {
+            let resolver = resolver.clone();
+            let name = format!("{}.{}", service, normalized);
+            set.spawn(async move {
+                let res = query_records(&resolver, name, RecordType::SRV).await;
+                (RecordType::SRV.to_string(), Some(*service), res)
+            });
+        }
+
+        while let Some(joined) = set.join_next().await {
+            let Ok((record_type, service, res)) = joined else {
+                continue;
+            };
+            attempted += 1;
+            match res {
+                Ok(rdatas) => {
+                    for rdata in &rdatas {
+                        report.absorb(rdata, service);
+                    }
+                }
+                Err(e) => {
+                    let key = match service {
+                        Some(s) => format!("SRV {}", s),
+                        None => record_type,
+                    };
+                    report.errors.insert(key, e);
+                }
+            }
+        }
+
+        ptr_ips.extend(
+            report
+                .a
+                .iter()
+                .chain(report.aaaa.iter())
+                .filter_map(|ip| ip.parse::<IpAddr>().ok())
+                .take(MAX_PTR_LOOKUPS),
+        );
+    }
+
+    for ip in ptr_ips {
+        attempted += 1;
+        match resolver.reverse_lookup(ip).await {
+            Ok(names) => {
+                for name in names.iter() {
+                    DnsReport::push_unique(
+                        &mut report.ptr,
+                        PtrRecord {
+                            ip: ip.to_string(),
+                            name: fqdn(&name.0.to_utf8()),
+                        },
+                    );
+                }
+            }
+            Err(e) => {
+                if !matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) {
+                    report.errors.insert(format!("PTR {}", ip), e.to_string());
+                }
+            }
+        }
+    }
+
+    // Si todas las consultas fallaron el resolver no está disponible: no es un "sin datos"
+    if report.is_empty() && attempted > 0 && report.errors.len() == attempted {
+        eprintln!(
+            "ERROR [dns]: All queries failed for {}: {:?}",
+            normalized, report.errors
+        );
+        return Err(format!(
+            "El resolver DNS ({}) no respondió ninguna consulta.",
+            report.resolver
+        ));
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_servidor_dns() {
+        assert_eq!(
+            parse_nameserver("8.8.8.8").unwrap(),
+            "8.8.8.8:53".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(
+            parse_nameserver("1.1.1.1:5353").unwrap(),
+            "1.1.1.1:5353".parse::<SocketAddr>().unwrap()
+        );
+        assert_eq!(parse_nameserver("[2606:4700::1111]").unwrap().port(), 53);
+        assert!(parse_nameserver("dns.google").is_err());
+
+        // Con proxy no se arma el resolver: las consultas no pasarían por él
+        let config = OsintConfig {
+            proxy_url: "socks5h://127.0.0.1:9050".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_resolver(&config).err().as_deref(),
+            Some(PROXY_DNS_ERROR)
+        );
+    }
+
+    #[test]
+    fn test_objetivos_relacionados() {
+        let report = DnsReport {
+            query: "ejemplo.com.ar".into(),
+            a: vec!["200.1.2.3".into()],
+            mx: vec![MxRecord {
+                preference: 10,
+                exchange: "mail.ejemplo.com.ar".into(),
+            }],
+            ptr: vec![PtrRecord {
+                ip: "200.1.2.3".into(),
+                name: "host3.isp.net".into(),
+            }],
+            ..Default::default()
+        };
+
+        let related = report.related_targets();
+        assert_eq!(related.len(), 3);
+        assert_eq!(related[0].name, "200.1.2.3");
+        assert_eq!(related[0].target_type, TargetType::IP);
+        assert_eq!(related[1].relation, "servidor_correo");
+        assert_eq!(related[2].source, "200.1.2.3");
+        assert_eq!(related[2].target_type, TargetType::Domain);
+    }
+}
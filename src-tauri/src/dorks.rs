@@ -0,0 +1,452 @@
+// src-tauri/src/dorks.rs
+//
+// Ejecución de dorks (búsquedas con operadores) contra el buscador multi-motor.
+//
+// Flujo:
+//   1. Según el tipo de objetivo (persona, dominio, email o teléfono) se arman las consultas
+//      de cada categoría: documentos, datos de contacto, filtraciones, redes sociales, etc.
+//   2. Las consultas se ejecutan de a una con una pausa entre ellas: los motores bloquean
+//      ráfagas de búsquedas con operadores mucho antes que búsquedas normales.
+//   3. Los resultados se deduplican por URL. Cada hallazgo conserva las categorías y las
+//      consultas que lo encontraron.
+//   4. Nada se guarda solo: el analista (o el agente) promueve al caso los hallazgos útiles.
+
+use crate::cases::{CaseManager, RelatedTarget, TargetType};
+use crate::models::OsintConfig;
+use crate::websearch::{SearchEngine, SearchOptions};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Pausa por defecto entre consultas
+pub const DEFAULT_DELAY_MS: u64 = 3000;
+const MIN_DELAY_MS: u64 = 1000;
+/// Detalle técnico donde se acumulan los hallazgos promovidos
+const EVIDENCE_KEY: &str = "dorks";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DorkKind {
+    Person,
+    Domain,
+    Email,
+    Phone,
+}
+
+impl DorkKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "person" | "persona" | "nombre" => Some(Self::Person),
+            "domain" | "dominio" => Some(Self::Domain),
+            "email" | "correo" => Some(Self::Email),
+            "phone" | "telefono" | "teléfono" => Some(Self::Phone),
+            _ => None,
+        }
+    }
+
+    /// Deduce el tipo a partir del propio objetivo.
+    pub fn detect(target: &str) -> Self {
+        let target = target.trim();
+        if target.contains('@') {
+            Self::Email
+        } else if crate::phone::normalize(target).is_some() {
+            Self::Phone
+        } else if !target.contains(' ') && target.contains('.') {
+            Self::Domain
+        } else {
+            Self::Person
+        }
+    }
+
+    pub fn target_type(&self) -> TargetType {
+        match self {
+            Self::Person => TargetType::Person,
+            Self::Domain => TargetType::Domain,
+            Self::Email => TargetType::Email,
+            Self::Phone => TargetType::Phone,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Dork {
+    pub category: String,
+    pub query: String,
+}
+
+/// Parámetros de la ejecución (todos opcionales desde el agente).
+#[derive(Debug, Clone, Default)]
+pub struct DorkOptions {
+    /// Tipo de objetivo; sin él se deduce
+    pub kind: Option<DorkKind>,
+    /// Categorías a ejecutar (vacío = todas)
+    pub categories: Vec<String>,
+    /// Motores (vacío = los que respetan site: y filetype:)
+    pub engines: Vec<SearchEngine>,
+    pub delay_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DorkHit {
+    pub title: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub snippet: String,
+    pub engines: Vec<String>,
+    pub categories: Vec<String>,
+    pub dorks: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DorkError {
+    pub query: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DorkReport {
+    pub target: String,
+    pub kind: DorkKind,
+    pub queries: Vec<Dork>,
+    pub hits: Vec<DorkHit>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<DorkError>,
+}
+
+fn dork(category: &str, query: String) -> Dork {
+    Dork {
+        category: category.to_string(),
+        query,
+    }
+}
+
+/// Consultas para un objetivo, agrupadas por categoría.
+pub fn build(target: &str, kind: DorkKind) -> Vec<Dork> {
+    let t = target.trim();
+    match kind {
+        DorkKind::Person => vec![
+            dork("redes_sociales", format!("site:linkedin.com/in \"{}\"", t)),
+            dork("redes_sociales", format!("site:facebook.com \"{}\"", t)),
+            dork("redes_sociales", format!("site:instagram.com \"{}\"", t)),
+            dork("redes_sociales", format!("(site:twitter.com OR site:x.com) \"{}\"", t)),
+            dork(
+                "documentos",
+                format!("\"{}\" (filetype:pdf OR filetype:doc OR filetype:docx OR filetype:xls OR filetype:xlsx)", t),
+            ),
+            dork("documentos", format!("\"{}\" (\"currículum\" OR \"CV\" OR \"resume\")", t)),
+            dork(
+                "datos_contacto",
+                format!("\"{}\" (intext:\"dni\" OR intext:\"cuil\" OR intext:\"email\" OR intext:\"correo\")", t),
+            ),
+            dork(
+                "datos_contacto",
+                format!("\"{}\" (intext:\"teléfono\" OR intext:\"celular\" OR intext:\"whatsapp\")", t),
+            ),
+            dork("datos_contacto", format!("\"{}\" (\"dirección\" OR \"domicilio\")", t)),
+            dork(
+                "filtraciones",
+                format!("\"{}\" (\"password\" OR \"leak\" OR \"breach\" OR \"filtración\")", t),
+            ),
+            dork("filtraciones", format!("\"{}\" (site:pastebin.com OR site:ghostbin.com)", t)),
+            dork(
+                "registros_publicos",
+                format!("\"{}\" (site:boletinoficial.gob.ar OR site:argentina.gob.ar OR site:cij.gov.ar)", t),
+            ),
+        ],
+        DorkKind::Domain => {
+            let d = t.trim_start_matches("www.");
+            vec![
+                dork("subdominios", format!("site:*.{} -site:www.{}", d, d)),
+                dork(
+                    "documentos",
+                    format!("site:{} (filetype:pdf OR filetype:docx OR filetype:xlsx OR filetype:pptx)", d),
+                ),
+                dork(
+                    "archivos_expuestos",
+                    format!("site:{} (ext:sql OR ext:bak OR ext:env OR ext:log OR ext:conf OR ext:ini)", d),
+                ),
+                dork("archivos_expuestos", format!("site:{} intitle:\"index of\"", d)),
+                dork(
+                    "paneles",
+                    format!("site:{} (inurl:login OR inurl:admin OR inurl:wp-admin OR inurl:panel)", d),
+                ),
+                dork("datos_contacto", format!("\"@{}\" -site:{}", d, d)),
+                dork(
+                    "filtraciones",
+                    format!("\"{}\" (site:pastebin.com OR site:github.com OR site:trello.com)", d),
+                ),
+                dork("filtraciones", format!("\"{}\" (\"password\" OR \"leak\" OR \"dump\")", d)),
+            ]
+        }
+        DorkKind::Email => {
+            let domain = t.rsplit_once('@').map(|(_, d)| d).unwrap_or("");
+            vec![
+                dork("datos_contacto", format!("\"{}\" -site:{}", t, domain)),
+                dork(
+                    "redes_sociales",
+                    format!("\"{}\" (site:linkedin.com OR site:facebook.com OR site:github.com)", t),
+                ),
+                dork(
+                    "documentos",
+                    format!("\"{}\" (filetype:pdf OR filetype:doc OR filetype:xls OR filetype:xlsx)", t),
+                ),
+                dork(
+                    "filtraciones",
+                    format!("\"{}\" (site:pastebin.com OR \"leak\" OR \"breach\" OR \"password\")", t),
+                ),
+            ]
+        }
+        DorkKind::Phone => {
+            // El mismo número se publica con formatos distintos: se buscan todos a la vez
+            let formats = match crate::phone::analyze(t, None) {
+                Ok(report) => {
+                    let mut formats = vec![report.e164, report.international, report.national];
+                    formats.dedup();
+                    formats
+                }
+                Err(_) => vec![t.to_string()],
+            };
+            let any = format!(
+                "({})",
+                formats
+                    .iter()
+                    .map(|f| format!("\"{}\"", f))
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            );
+            vec![
+                dork("datos_contacto", any.clone()),
+                dork(
+                    "redes_sociales",
+                    format!("{} (site:facebook.com OR site:instagram.com OR site:mercadolibre.com.ar)", any),
+                ),
+                dork(
+                    "documentos",
+                    format!("{} (filetype:pdf OR filetype:xls OR filetype:xlsx)", any),
+                ),
+                dork("filtraciones", format!("{} (site:pastebin.com OR \"leak\")", any)),
+            ]
+        }
+    }
+}
+
+/// Suma los resultados de una consulta a los hallazgos, deduplicando por URL.
+fn collect_hits(
+    hits: &mut Vec<DorkHit>,
+    dork: &Dork,
+    results: Vec<crate::websearch::SearchResult>,
+) {
+    for result in results {
+        let key = crate::websearch::normalize_url(&result.url);
+        let hit = match hits
+            .iter_mut()
+            .position(|h| crate::websearch::normalize_url(&h.url) == key)
+        {
+            Some(i) => &mut hits[i],
+            None => {
+                hits.push(DorkHit {
+                    title: result.title.clone(),
+                    url: result.url.clone(),
+                    snippet: result.snippet.clone(),
+                    engines: Vec::new(),
+                    categories: Vec::new(),
+                    dorks: Vec::new(),
+                });
+                hits.last_mut().expect("recién agregado")
+            }
+        };
+        for engine in std::iter::once(result.engine).chain(result.also_found_by) {
+            if !hit.engines.contains(&engine) {
+                hit.engines.push(engine);
+            }
+        }
+        if !hit.categories.contains(&dork.category) {
+            hit.categories.push(dork.category.clone());
+        }
+        if !hit.dorks.contains(&dork.query) {
+            hit.dorks.push(dork.query.clone());
+        }
+    }
+}
+
+/// Ejecuta los dorks del objetivo y devuelve los hallazgos deduplicados.
+pub async fn run(
+    target: &str,
+    options: &DorkOptions,
+    config: &OsintConfig,
+) -> Result<DorkReport, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Debe indicar un objetivo (nombre, dominio, email o teléfono).".to_string());
+    }
+    let kind = options.kind.unwrap_or_else(|| DorkKind::detect(target));
+    let queries: Vec<Dork> = build(target, kind)
+        .into_iter()
+        .filter(|d| {
+            options.categories.is_empty()
+                || options
+                    .categories
+                    .iter()
+                    .any(|c| c.trim().eq_ignore_ascii_case(&d.category))
+        })
+        .collect();
+    if queries.is_empty() {
+        return Err(
+            "Ninguna de las categorías pedidas aplica a este tipo de objetivo.".to_string(),
+        );
+    }
+
+    let search_options = SearchOptions {
+        engines: if options.engines.is_empty() {
+            vec![
+                SearchEngine::DuckDuckGo,
+                SearchEngine::Bing,
+                SearchEngine::Brave,
+            ]
+        } else {
+            options.engines.clone()
+        },
+        ..Default::default()
+    };
+    let delay = Duration::from_millis(
+        options
+            .delay_ms
+            .unwrap_or(DEFAULT_DELAY_MS)
+            .max(MIN_DELAY_MS),
+    );
+
+    let mut hits = Vec::new();
+    let mut errors = Vec::new();
+    for (i, dork) in queries.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(delay).await;
+        }
+        match crate::websearch::search(&dork.query, &search_options, config).await {
+            Ok(report) => collect_hits(&mut hits, dork, report.results),
+            Err(error) => errors.push(DorkError {
+                query: dork.query.clone(),
+                error,
+            }),
+        }
+    }
+
+    // Primero lo que apareció en más categorías y motores
+    hits.sort_by_key(|h| std::cmp::Reverse(h.categories.len() + h.engines.len()));
+    Ok(DorkReport {
+        target: target.to_string(),
+        kind,
+        queries,
+        hits,
+        errors,
+    })
+}
+
+/// Guarda un hallazgo como evidencia del objetivo: se acumula en `detalles_tecnicos.dorks`
+/// y el objetivo queda vinculado ("mencionado_en") con el dominio del resultado.
+pub fn promote(
+    case_manager: &CaseManager,
+    case_name: &str,
+    target: &str,
+    kind: DorkKind,
+    hit: &DorkHit,
+) -> Result<(), String> {
+    let target = target.trim();
+    let mut evidence: Vec<serde_json::Value> = case_manager
+        .find_target_by_name(case_name, target)?
+        .and_then(|t| t.data.get("detalles_tecnicos")?.get(EVIDENCE_KEY).cloned())
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default();
+    let key = crate::websearch::normalize_url(&hit.url);
+    if !evidence.iter().any(|e| {
+        e["url"]
+            .as_str()
+            .is_some_and(|u| crate::websearch::normalize_url(u) == key)
+    }) {
+        let mut entry = serde_json::to_value(hit).map_err(|e| e.to_string())?;
+        entry["promovido"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
+        evidence.push(entry);
+    }
+    case_manager.save_tool_details(
+        case_name,
+        target,
+        kind.target_type(),
+        EVIDENCE_KEY,
+        serde_json::Value::Array(evidence),
+    )?;
+
+    let host = reqwest::Url::parse(&hit.url).ok().and_then(|u| {
+        u.host_str()
+            .map(|h| h.trim_start_matches("www.").to_string())
+    });
+    if let Some(host) = host {
+        case_manager.link_related(
+            case_name,
+            &[RelatedTarget {
+                source: target.to_string(),
+                source_type: kind.target_type(),
+                name: host,
+                target_type: TargetType::Domain,
+                relation: "mencionado_en".to_string(),
+            }],
+        )?;
+    }
+    case_manager.log_event(
+        case_name,
+        "INFO",
+        &format!("Resultado de dork promovido a evidencia: {}", hit.url),
+        Some("dorks"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detecta_tipo_y_arma_consultas() {
+        assert_eq!(DorkKind::detect("Juan Pérez"), DorkKind::Person);
+        assert_eq!(DorkKind::detect("ejemplo.com.ar"), DorkKind::Domain);
+        assert_eq!(DorkKind::detect("jperez@ejemplo.com"), DorkKind::Email);
+        assert_eq!(DorkKind::detect("+54 9 11 2345-6789"), DorkKind::Phone);
+
+        let domain = build("www.ejemplo.com.ar", DorkKind::Domain);
+        assert!(domain.iter().any(|d| d.category == "subdominios"
+            && d.query == "site:*.ejemplo.com.ar -site:www.ejemplo.com.ar"));
+        let phone = build("+54 9 11 2345-6789", DorkKind::Phone);
+        assert!(phone[0].query.contains("\"+5491123456789\" OR "));
+        assert!(build("Juan Pérez", DorkKind::Person)
+            .iter()
+            .all(|d| d.query.contains("\"Juan Pérez\"")));
+    }
+
+    #[test]
+    fn test_deduplica_y_etiqueta_hallazgos() {
+        let result = |url: &str, engine: &str| crate::websearch::SearchResult {
+            title: "Doc".to_string(),
+            url: url.to_string(),
+            snippet: String::new(),
+            engine: engine.to_string(),
+            rank: 1,
+            also_found_by: Vec::new(),
+        };
+        let docs = dork("documentos", "q1".to_string());
+        let leaks = dork("filtraciones", "q2".to_string());
+        let mut hits = Vec::new();
+        collect_hits(
+            &mut hits,
+            &docs,
+            vec![result("https://www.a.com/cv.pdf", "bing")],
+        );
+        collect_hits(
+            &mut hits,
+            &leaks,
+            vec![
+                result("https://a.com/cv.pdf", "duckduckgo"),
+                result("https://b.com/", "bing"),
+            ],
+        );
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].categories, vec!["documentos", "filtraciones"]);
+        assert_eq!(hits[0].engines, vec!["bing", "duckduckgo"]);
+        assert_eq!(hits[0].dorks, vec!["q1", "q2"]);
+    }
+}
@@ -0,0 +1,405 @@
+// src-tauri/src/email.rs
+//
+// Verificación de direcciones de correo sin enviar mensajes.
+//
+// Flujo:
+//   1. Sintaxis: validación pragmática (la parte local y el dominio como los aceptan los
+//      proveedores reales, no todo lo que permite el RFC 5322).
+//   2. MX del dominio con el resolver configurado; sin MX se usa el propio dominio si resuelve
+//      (MX implícito, RFC 5321) y un "null MX" indica que el dominio no recibe correo. Con un
+//      proxy configurado no hay consulta DNS: quedan sólo las comprobaciones locales (1 y 4).
+//   3. Sondeo SMTP: EHLO, `MAIL FROM:<>` y `RCPT TO` de la dirección, y luego de una dirección
+//      aleatoria del mismo dominio para detectar catch-all. Nunca se llega a DATA.
+//   4. Se marcan proveedores desechables, gratuitos y cuentas de rol, y se emite un veredicto.
+
+use crate::dns::MxRecord;
+use crate::models::OsintConfig;
+use chrono::Utc;
+use hickory_resolver::error::ResolveErrorKind;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use uuid::Uuid;
+
+const SMTP_PORT: u16 = 25;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(15);
+/// Servidores MX a intentar antes de dar el sondeo por fallido
+const MAX_MX_ATTEMPTS: usize = 2;
+const HELO_NAME: &str = "localhost";
+
+/// Dominios de correo temporal más comunes.
+const DISPOSABLE_DOMAINS: &str = "\
+    10minutemail.com 10minutemail.net 20minutemail.com 33mail.com anonaddy.me burnermail.io \
+    discard.email dispostable.com dropmail.me emailondeck.com fakeinbox.com getairmail.com \
+    getnada.com guerrillamail.biz guerrillamail.com guerrillamail.de guerrillamail.info \
+    guerrillamail.net guerrillamail.org guerrillamailblock.com harakirimail.com inboxbear.com \
+    maildrop.cc mailcatch.com mailinator.com mailinator.net mailnesia.com mailsac.com \
+    mintemail.com moakt.com mohmal.com mytemp.email sharklasers.com spamgourmet.com \
+    temp-mail.io temp-mail.org tempail.com tempmail.dev tempmail.net tempmailo.com \
+    tempr.email throwawaymail.com trashmail.com trashmail.de trashmail.net yopmail.com \
+    yopmail.fr yopmail.net";
+
+/// Proveedores de correo gratuito (internacionales y argentinos).
+const FREE_PROVIDERS: &str = "\
+    gmail.com googlemail.com outlook.com outlook.com.ar hotmail.com hotmail.com.ar hotmail.es \
+    live.com live.com.ar msn.com yahoo.com yahoo.com.ar yahoo.es ymail.com rocketmail.com \
+    aol.com icloud.com me.com mac.com protonmail.com proton.me pm.me tutanota.com tuta.io \
+    gmx.com gmx.net gmx.de mail.com zoho.com yandex.com yandex.ru mail.ru inbox.com \
+    fibertel.com.ar arnet.com.ar speedy.com.ar ciudad.com.ar uolsinectis.com.ar";
+
+/// Buzones genéricos que no identifican a una persona.
+const ROLE_ACCOUNTS: &str = "\
+    admin administrator abuse billing compras contact contacto contabilidad facturacion \
+    hello help hola info informes hostmaster jobs legal mail marketing no-reply noreply \
+    office postmaster prensa press rrhh sales security soporte support ventas webmaster";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// El servidor acepta la dirección y rechaza direcciones inventadas
+    Deliverable,
+    /// El servidor rechaza la dirección (5xx)
+    Undeliverable,
+    /// El servidor acepta cualquier dirección del dominio: no se puede confirmar
+    CatchAll,
+    /// Sin respuesta concluyente (puerto 25 bloqueado, greylisting, timeout...)
+    Unknown,
+    /// Sintaxis inválida o dominio sin servidor de correo
+    Invalid,
+}
+
+/// Resultado del diálogo SMTP con un servidor MX.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SmtpProbe {
+    pub server: String,
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub greeting: String,
+    pub rcpt_code: u16,
+    pub rcpt_message: String,
+    /// `None` si no se llegó a probar (la dirección real fue rechazada)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catch_all: Option<bool>,
+}
+
+/// Resultado de la verificación, listo para `detalles_tecnicos.verificacion` del objetivo Email.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmailReport {
+    pub email: String,
+    pub domain: String,
+    pub syntax_valid: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub mx: Vec<MxRecord>,
+    /// El dominio no publica MX pero resuelve: recibe correo en su propia dirección
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub implicit_mx: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpProbe>,
+    pub disposable: bool,
+    pub free_provider: bool,
+    pub role_account: bool,
+    pub verdict: Verdict,
+    pub checked_at: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub notes: Vec<String>,
+}
+
+/// Separa y valida la dirección. Devuelve (parte local, dominio en minúsculas).
+pub fn parse_address(email: &str) -> Option<(String, String)> {
+    let email = email.trim();
+    let email = email.strip_prefix("mailto:").unwrap_or(email);
+    let email = email.trim_start_matches('<').trim_end_matches('>');
+    if email.len() > 254 {
+        return None;
+    }
+
+    let (local, domain) = email.rsplit_once('@')?;
+    let local_ok = (1..=64).contains(&local.len())
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~-.".contains(c));
+    if !local_ok {
+        return None;
+    }
+
+    let domain = domain.trim_end_matches('.').to_lowercase();
+    let labels: Vec<&str> = domain.split('.').collect();
+    let labels_ok = labels.len() >= 2
+        && domain.len() <= 253
+        && labels.iter().all(|l| {
+            (1..=63).contains(&l.len())
+                && !l.starts_with('-')
+                && !l.ends_with('-')
+                && l.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    let tld = labels.last().copied().unwrap_or_default();
+    let tld_ok =
+        tld.starts_with("xn--") || (tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic()));
+    if !labels_ok || !tld_ok {
+        return None;
+    }
+
+    Some((local.to_string(), domain))
+}
+
+fn in_list(list: &str, value: &str) -> bool {
+    list.split_whitespace().any(|item| item == value)
+}
+
+/// Veredicto a partir del sondeo SMTP (ya validadas sintaxis y MX).
+fn classify(probe: Option<&SmtpProbe>) -> Verdict {
+    let Some(probe) = probe else {
+        return Verdict::Unknown;
+    };
+    match probe.rcpt_code {
+        200..=299 if probe.catch_all == Some(true) => Verdict::CatchAll,
+        200..=299 => Verdict::Deliverable,
+        500..=599 => Verdict::Undeliverable,
+        _ => Verdict::Unknown,
+    }
+}
+
+/// Conexión SMTP mínima: sólo los comandos necesarios para el sondeo.
+struct SmtpSession {
+    stream: BufReader<TcpStream>,
+}
+
+impl SmtpSession {
+    /// Lee una respuesta completa (incluidas las multilínea `250-...`).
+    async fn read_reply(&mut self) -> Result<(u16, String), String> {
+        let mut message = String::new();
+        loop {
+            let mut line = String::new();
+            let read = tokio::time::timeout(REPLY_TIMEOUT, self.stream.read_line(&mut line))
+                .await
+                .map_err(|_| "Tiempo de espera agotado en el diálogo SMTP".to_string())?
+                .map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Err("El servidor cerró la conexión".to_string());
+            }
+
+            let line = line.trim_end();
+            let code = line
+                .get(..3)
+                .and_then(|c| c.parse::<u16>().ok())
+                .ok_or_else(|| format!("Respuesta SMTP inválida: {}", line))?;
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(line.get(4..).unwrap_or_default().trim());
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok((code, message));
+            }
+        }
+    }
+
+    async fn command(&mut self, command: &str) -> Result<(u16, String), String> {
+        self.stream
+            .get_mut()
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+        self.read_reply().await
+    }
+}
+
+/// Dialoga con un servidor MX hasta el RCPT TO (y el de catch-all), sin enviar nada.
+async fn probe_server(
+    server: &str,
+    email: &str,
+    domain: &str,
+    config: &OsintConfig,
+) -> Result<SmtpProbe, String> {
+    let stream = crate::tools::open_tcp_stream(server, SMTP_PORT, config, CONNECT_TIMEOUT).await?;
+    let mut session = SmtpSession {
+        stream: BufReader::new(stream),
+    };
+
+    let (code, greeting) = session.read_reply().await?;
+    if !(200..300).contains(&code) {
+        return Err(format!("Saludo rechazado ({}): {}", code, greeting));
+    }
+
+    let (code, _) = session.command(&format!("EHLO {}", HELO_NAME)).await?;
+    if !(200..300).contains(&code) {
+        let (code, message) = session.command(&format!("HELO {}", HELO_NAME)).await?;
+        if !(200..300).contains(&code) {
+            return Err(format!("HELO rechazado ({}): {}", code, message));
+        }
+    }
+
+    // Remitente nulo (el de los rebotes): no hace falta inventar un dominio propio
+    let (code, message) = session.command("MAIL FROM:<>").await?;
+    if !(200..300).contains(&code) {
+        return Err(format!("MAIL FROM rechazado ({}): {}", code, message));
+    }
+
+    let (rcpt_code, rcpt_message) = session.command(&format!("RCPT TO:<{}>", email)).await?;
+    let mut catch_all = None;
+    if (200..300).contains(&rcpt_code) {
+        let random = format!("{}@{}", Uuid::new_v4().simple(), domain);
+        if let Ok((code, _)) = session.command(&format!("RCPT TO:<{}>", random)).await {
+            catch_all = Some((200..300).contains(&code));
+        }
+    }
+    let _ = session.command("QUIT").await;
+
+    Ok(SmtpProbe {
+        server: server.to_string(),
+        greeting: greeting.chars().take(200).collect(),
+        rcpt_code,
+        rcpt_message: rcpt_message.chars().take(300).collect(),
+        catch_all,
+    })
+}
+
+/// Verifica una dirección de correo: sintaxis, MX, sondeo SMTP y listas de proveedores.
+pub async fn verify(email: &str, config: &OsintConfig) -> Result<EmailReport, String> {
+    let checked_at = Utc::now().to_rfc3339();
+    let Some((local, domain)) = parse_address(email) else {
+        return Ok(EmailReport {
+            email: email.trim().to_string(),
+            domain: String::new(),
+            syntax_valid: false,
+            mx: vec![],
+            implicit_mx: false,
+            smtp: None,
+            disposable: false,
+            free_provider: false,
+            role_account: false,
+            verdict: Verdict::Invalid,
+            checked_at,
+            notes: vec!["Sintaxis inválida".to_string()],
+        });
+    };
+
+    let address = format!("{}@{}", local, domain);
+    let mut report = EmailReport {
+        email: address.clone(),
+        domain: domain.clone(),
+        syntax_valid: true,
+        mx: vec![],
+        implicit_mx: false,
+        smtp: None,
+        disposable: in_list(DISPOSABLE_DOMAINS, &domain),
+        free_provider: in_list(FREE_PROVIDERS, &domain),
+        role_account: in_list(ROLE_ACCOUNTS, &local.to_lowercase()),
+        verdict: Verdict::Unknown,
+        checked_at,
+        notes: vec![],
+    };
+
+    let resolver = match crate::dns::build_resolver(config) {
+        Ok((resolver, _)) => resolver,
+        Err(e) => {
+            report.notes.push(e);
+            return Ok(report);
+        }
+    };
+    match resolver.mx_lookup(domain.as_str()).await {
+        Ok(lookup) => {
+            report.mx = lookup
+                .iter()
+                .map(|mx| MxRecord {
+                    preference: mx.preference(),
+                    exchange: mx.exchange().to_utf8().trim_end_matches('.').to_lowercase(),
+                })
+                .collect();
+            report.mx.sort_by_key(|mx| mx.preference);
+        }
+        Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => {
+            report.implicit_mx = resolver.lookup_ip(domain.as_str()).await.is_ok();
+        }
+        Err(e) => {
+            eprintln!("ERROR [email]: MX lookup failure for {}: {}", domain, e);
+            report
+                .notes
+                .push(format!("No se pudieron consultar los MX: {}", e));
+            return Ok(report);
+        }
+    }
+
+    // Null MX (RFC 7505): el dominio declara que no recibe correo
+    report.mx.retain(|mx| !mx.exchange.is_empty());
+    let servers: Vec<String> = if report.implicit_mx {
+        vec![domain.clone()]
+    } else {
+        report.mx.iter().map(|mx| mx.exchange.clone()).collect()
+    };
+    if servers.is_empty() {
+        report.verdict = Verdict::Invalid;
+        report
+            .notes
+            .push("El dominio no tiene servidor de correo".to_string());
+        return Ok(report);
+    }
+
+    for server in servers.iter().take(MAX_MX_ATTEMPTS) {
+        match probe_server(server, &address, &domain, config).await {
+            Ok(probe) => {
+                report.smtp = Some(probe);
+                break;
+            }
+            Err(e) => {
+                eprintln!("WARN [email]: SMTP probe failed on {}: {}", server, e);
+                report.notes.push(format!("{}: {}", server, e));
+            }
+        }
+    }
+    if report.smtp.is_none() {
+        report
+            .notes
+            .push("Sin sondeo SMTP: muchas redes y Tor bloquean el puerto 25".to_string());
+    }
+    report.verdict = classify(report.smtp.as_ref());
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valida_sintaxis() {
+        assert_eq!(
+            parse_address(" <Juan.Perez+osint@Ejemplo.COM.ar> "),
+            Some(("Juan.Perez+osint".to_string(), "ejemplo.com.ar".to_string()))
+        );
+        assert!(parse_address("mailto:info@xn--espaa-rta.es").is_some());
+        for invalid in [
+            "sin-arroba.com",
+            ".juan@ejemplo.com",
+            "juan..perez@ejemplo.com",
+            "juan@localhost",
+            "juan@-ejemplo.com",
+            "juan@ejemplo.c0m",
+            "juan perez@ejemplo.com",
+        ] {
+            assert_eq!(parse_address(invalid), None, "{}", invalid);
+        }
+        assert!(in_list(DISPOSABLE_DOMAINS, "mailinator.com"));
+        assert!(in_list(FREE_PROVIDERS, "hotmail.com.ar"));
+    }
+
+    #[test]
+    fn test_veredicto_smtp() {
+        let probe = |code: u16, catch_all: Option<bool>| SmtpProbe {
+            rcpt_code: code,
+            catch_all,
+            ..Default::default()
+        };
+        assert_eq!(classify(None), Verdict::Unknown);
+        assert_eq!(
+            classify(Some(&probe(250, Some(false)))),
+            Verdict::Deliverable
+        );
+        assert_eq!(classify(Some(&probe(250, Some(true)))), Verdict::CatchAll);
+        assert_eq!(classify(Some(&probe(550, None))), Verdict::Undeliverable);
+        // Greylisting: no se puede concluir nada
+        assert_eq!(classify(Some(&probe(451, None))), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,607 @@
+// src-tauri/src/faces.rs
+//
+// Detección y comparación de rostros en el backend, con ONNX Runtime sobre CPU.
+//
+// Flujo:
+//   1. El usuario deja los modelos en `<app_data>/models/faces/`:
+//        - detector.onnx: UltraFace (RFB-320 o slim-320), salida `scores` [1,N,2] y `boxes` [1,N,4].
+//        - embedder.onnx: red tipo ArcFace con entrada 112x112 y salida de 512 valores.
+//        - la biblioteca de ONNX Runtime (libonnxruntime.so / onnxruntime.dll /
+//          libonnxruntime.dylib), o la ruta en la variable ORT_DYLIB_PATH.
+//   2. Se detectan los rostros (umbral de confianza + NMS) y cada uno se recorta en cuadrado,
+//      con margen, a 112x112. No hay alineación por landmarks: las fotos muy de perfil
+//      dan similitudes más bajas de lo que deberían.
+//   3. Cada rostro se convierte en un embedding normalizado; dos rostros se comparan por
+//      similitud coseno.
+//   4. Los embeddings quedan como atributos del objetivo File para comparar contra cualquier
+//      imagen que se agregue después al caso.
+
+use crate::cases::{CaseManager, TargetType};
+use crate::models::OsintConfig;
+use base64::{engine::general_purpose, Engine as _};
+use image::imageops::FilterType;
+use image::{DynamicImage, RgbImage};
+use ort::session::Session;
+use ort::value::{Tensor, ValueType};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Similitud coseno mínima para considerar dos rostros la misma persona
+pub const MATCH_THRESHOLD: f32 = 0.5;
+/// Por debajo de esto se descarta la coincidencia; entre ambos umbrales es "posible"
+pub const LIKELY_THRESHOLD: f32 = 0.35;
+
+pub const DETECTOR_FILE: &str = "detector.onnx";
+pub const EMBEDDER_FILE: &str = "embedder.onnx";
+#[cfg(target_os = "windows")]
+pub const RUNTIME_FILE: &str = "onnxruntime.dll";
+#[cfg(target_os = "macos")]
+pub const RUNTIME_FILE: &str = "libonnxruntime.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const RUNTIME_FILE: &str = "libonnxruntime.so";
+
+const SCORE_THRESHOLD: f32 = 0.7;
+const NMS_IOU: f32 = 0.3;
+const MAX_FACES: usize = 20;
+const EMBED_SIZE: u32 = 112;
+/// Margen alrededor del recuadro detectado antes de recortar (proporción del lado)
+const CROP_MARGIN: f32 = 0.15;
+const ATTRIBUTE_KEY: &str = "rostro_embedding";
+
+/// Carpeta de modelos dentro del directorio de datos de la aplicación.
+pub fn models_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("models").join("faces")
+}
+
+/// Qué archivos hay y cuáles faltan para poder analizar rostros.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModelStatus {
+    pub dir: String,
+    pub ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    pub detector: bool,
+    pub embedder: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+}
+
+impl ModelStatus {
+    pub fn missing_message(&self) -> String {
+        format!(
+            "Faltan archivos para el análisis facial en {}: {}.",
+            self.dir,
+            self.missing.join(", ")
+        )
+    }
+}
+
+pub fn status(dir: &Path) -> ModelStatus {
+    let runtime = Some(dir.join(RUNTIME_FILE))
+        .filter(|p| p.is_file())
+        .or_else(|| {
+            std::env::var_os("ORT_DYLIB_PATH")
+                .map(PathBuf::from)
+                .filter(|p| p.is_file())
+        })
+        .map(|p| p.to_string_lossy().into_owned());
+    let detector = dir.join(DETECTOR_FILE).is_file();
+    let embedder = dir.join(EMBEDDER_FILE).is_file();
+
+    let mut missing = Vec::new();
+    if runtime.is_none() {
+        missing.push(format!("{} (o ORT_DYLIB_PATH)", RUNTIME_FILE));
+    }
+    if !detector {
+        missing.push(DETECTOR_FILE.to_string());
+    }
+    if !embedder {
+        missing.push(EMBEDDER_FILE.to_string());
+    }
+    ModelStatus {
+        dir: dir.to_string_lossy().into_owned(),
+        ready: missing.is_empty(),
+        runtime,
+        detector,
+        embedder,
+        missing,
+    }
+}
+
+/// Recuadro de un rostro en píxeles de la imagen original.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FaceBox {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DetectedFace {
+    pub bbox: FaceBox,
+    /// Embedding en base64 (f32 little-endian), tal como se guarda en el caso
+    pub embedding: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FaceReport {
+    pub source: String,
+    pub sha256: String,
+    pub width: u32,
+    pub height: u32,
+    pub faces: Vec<DetectedFace>,
+}
+
+/// Mejor coincidencia entre los rostros de dos imágenes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FaceMatch {
+    pub image: String,
+    pub face: usize,
+    pub other_face: usize,
+    pub similarity: f32,
+    pub verdict: String,
+}
+
+impl FaceMatch {
+    fn new(image: &str, face: usize, other_face: usize, similarity: f32) -> Self {
+        let verdict = if similarity >= MATCH_THRESHOLD {
+            "misma_persona"
+        } else if similarity >= LIKELY_THRESHOLD {
+            "posible"
+        } else {
+            "distinta"
+        };
+        Self {
+            image: image.to_string(),
+            face,
+            other_face,
+            similarity,
+            verdict: verdict.to_string(),
+        }
+    }
+}
+
+pub fn encode_embedding(embedding: &[f32]) -> String {
+    let bytes: Vec<u8> = embedding.iter().flat_map(|v| v.to_le_bytes()).collect();
+    general_purpose::STANDARD.encode(bytes)
+}
+
+pub fn decode_embedding(encoded: &str) -> Option<Vec<f32>> {
+    let bytes = general_purpose::STANDARD.decode(encoded.trim()).ok()?;
+    if bytes.is_empty() || bytes.len() % 4 != 0 {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+    )
+}
+
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
+
+/// Compara todos los rostros de `a` contra todos los de `b` y devuelve el par más parecido.
+pub fn best_match(a: &FaceReport, b: &FaceReport) -> Option<FaceMatch> {
+    let decoded_b: Vec<Option<Vec<f32>>> = b
+        .faces
+        .iter()
+        .map(|f| decode_embedding(&f.embedding))
+        .collect();
+    let mut best: Option<FaceMatch> = None;
+    for (i, face) in a.faces.iter().enumerate() {
+        let Some(embedding) = decode_embedding(&face.embedding) else {
+            continue;
+        };
+        for (j, other) in decoded_b.iter().enumerate() {
+            let Some(similarity) = other
+                .as_ref()
+                .and_then(|o| cosine_similarity(&embedding, o))
+            else {
+                continue;
+            };
+            if best.as_ref().is_none_or(|m| similarity > m.similarity) {
+                best = Some(FaceMatch::new(&b.source, i, j, similarity));
+            }
+        }
+    }
+    best
+}
+
+fn iou(a: &[f32; 4], b: &[f32; 4]) -> f32 {
+    let width = (a[2].min(b[2]) - a[0].max(b[0])).max(0.0);
+    let height = (a[3].min(b[3]) - a[1].max(b[1])).max(0.0);
+    let intersection = width * height;
+    let area = |r: &[f32; 4]| (r[2] - r[0]).max(0.0) * (r[3] - r[1]).max(0.0);
+    let union = area(a) + area(b) - intersection;
+    if union <= 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
+
+/// Supresión de no-máximos sobre (recuadro normalizado x1,y1,x2,y2, score).
+fn non_max_suppression(mut candidates: Vec<([f32; 4], f32)>) -> Vec<([f32; 4], f32)> {
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut kept: Vec<([f32; 4], f32)> = Vec::new();
+    for candidate in candidates {
+        if kept.iter().all(|k| iou(&k.0, &candidate.0) < NMS_IOU) {
+            kept.push(candidate);
+            if kept.len() == MAX_FACES {
+                break;
+            }
+        }
+    }
+    kept
+}
+
+/// Pasa la imagen a tensor CHW normalizado como `(píxel - mean) / std`.
+fn to_chw(image: &RgbImage, mean: f32, std: f32) -> Vec<f32> {
+    let plane = (image.width() * image.height()) as usize;
+    let mut data = vec![0.0; plane * 3];
+    for (i, pixel) in image.pixels().enumerate() {
+        for c in 0..3 {
+            data[c * plane + i] = (pixel.0[c] as f32 - mean) / std;
+        }
+    }
+    data
+}
+
+fn ort_error(e: ort::Error) -> String {
+    eprintln!("ERROR [faces]: ONNX Runtime failure: {}", e);
+    "Falló la inferencia del modelo facial.".to_string()
+}
+
+struct Engine {
+    detector: Session,
+    embedder: Session,
+    /// Tamaño de entrada del detector (ancho, alto)
+    detector_size: (u32, u32),
+}
+
+static ENGINE: Mutex<Option<Engine>> = Mutex::new(None);
+
+fn open_session(path: &Path) -> Result<Session, String> {
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    Session::builder()
+        .and_then(|b| b.with_intra_threads(threads))
+        .and_then(|b| b.commit_from_file(path))
+        .map_err(|e| {
+            eprintln!(
+                "ERROR [faces]: Failed to load model {}: {}",
+                path.display(),
+                e
+            );
+            format!(
+                "No se pudo cargar el modelo {}. Verifique que sea un ONNX válido.",
+                path.display()
+            )
+        })
+}
+
+impl Engine {
+    fn load(dir: &Path) -> Result<Self, String> {
+        let status = status(dir);
+        let Some(runtime) = status.runtime.clone().filter(|_| status.ready) else {
+            return Err(status.missing_message());
+        };
+        // `ort` entra en pánico si la biblioteca no se puede cargar (versión o arquitectura
+        // incorrecta): se aísla para devolver un error en lugar de tirar la aplicación
+        std::panic::catch_unwind(|| -> Result<Self, String> {
+            ort::init_from(runtime.clone())
+                .with_name("osint-faces")
+                .commit()
+                .map_err(ort_error)?;
+            let detector = open_session(&dir.join(DETECTOR_FILE))?;
+            let embedder = open_session(&dir.join(EMBEDDER_FILE))?;
+            // Entrada [1, 3, alto, ancho]; si es dinámica se usa la de RFB-320
+            let detector_size = match detector.inputs.first().map(|i| &i.input_type) {
+                Some(ValueType::Tensor { shape, .. }) if shape.len() == 4 && shape[2] > 0 => {
+                    (shape[3] as u32, shape[2] as u32)
+                }
+                _ => (320, 240),
+            };
+            Ok(Self {
+                detector,
+                embedder,
+                detector_size,
+            })
+        })
+        .unwrap_or_else(|_| {
+            eprintln!("ERROR [faces]: Could not load ONNX Runtime from {}", runtime);
+            Err(format!(
+                "No se pudo cargar ONNX Runtime desde {}. Verifique que la versión (1.22 o superior) y la arquitectura coincidan.",
+                runtime
+            ))
+        })
+    }
+
+    fn detect(&mut self, image: &DynamicImage) -> Result<Vec<FaceBox>, String> {
+        let (width, height) = self.detector_size;
+        let resized = image
+            .resize_exact(width, height, FilterType::Triangle)
+            .to_rgb8();
+        let input = Tensor::from_array((
+            [1usize, 3, height as usize, width as usize],
+            to_chw(&resized, 127.0, 128.0),
+        ))
+        .map_err(ort_error)?;
+        let output_count = self.detector.outputs.len();
+        let outputs = self.detector.run(ort::inputs![input]).map_err(ort_error)?;
+
+        // Las salidas se reconocen por la última dimensión: 2 (fondo/rostro) y 4 (recuadro)
+        let mut scores: &[f32] = &[];
+        let mut boxes: &[f32] = &[];
+        for i in 0..output_count {
+            let (shape, data) = outputs[i].try_extract_tensor::<f32>().map_err(ort_error)?;
+            match shape.last() {
+                Some(2) => scores = data,
+                Some(4) => boxes = data,
+                _ => {}
+            }
+        }
+        if scores.is_empty() || scores.len() / 2 != boxes.len() / 4 {
+            return Err("El detector no tiene las salidas esperadas (scores y boxes).".to_string());
+        }
+
+        let candidates = scores
+            .chunks_exact(2)
+            .zip(boxes.chunks_exact(4))
+            .filter(|(s, _)| s[1] >= SCORE_THRESHOLD)
+            .map(|(s, b)| ([b[0], b[1], b[2], b[3]], s[1]))
+            .collect();
+        let (img_w, img_h) = (image.width() as f32, image.height() as f32);
+        Ok(non_max_suppression(candidates)
+            .into_iter()
+            .filter_map(|(b, score)| {
+                let x1 = (b[0].clamp(0.0, 1.0) * img_w) as u32;
+                let y1 = (b[1].clamp(0.0, 1.0) * img_h) as u32;
+                let x2 = (b[2].clamp(0.0, 1.0) * img_w) as u32;
+                let y2 = (b[3].clamp(0.0, 1.0) * img_h) as u32;
+                (x2 > x1 + 8 && y2 > y1 + 8).then_some(FaceBox {
+                    x: x1,
+                    y: y1,
+                    width: x2 - x1,
+                    height: y2 - y1,
+                    score,
+                })
+            })
+            .collect())
+    }
+
+    fn embed(&mut self, image: &DynamicImage, face: &FaceBox) -> Result<Vec<f32>, String> {
+        // Recorte cuadrado centrado en el rostro, con margen y dentro de la imagen
+        let side = (face.width.max(face.height) as f32 * (1.0 + CROP_MARGIN * 2.0)) as u32;
+        let side = side.min(image.width()).min(image.height()).max(1);
+        let center_x = face.x + face.width / 2;
+        let center_y = face.y + face.height / 2;
+        let x = center_x.saturating_sub(side / 2).min(image.width() - side);
+        let y = center_y.saturating_sub(side / 2).min(image.height() - side);
+        let crop = image
+            .crop_imm(x, y, side, side)
+            .resize_exact(EMBED_SIZE, EMBED_SIZE, FilterType::Triangle)
+            .to_rgb8();
+
+        let input = Tensor::from_array((
+            [1usize, 3, EMBED_SIZE as usize, EMBED_SIZE as usize],
+            to_chw(&crop, 127.5, 127.5),
+        ))
+        .map_err(ort_error)?;
+        let outputs = self.embedder.run(ort::inputs![input]).map_err(ort_error)?;
+        let (_, data) = outputs[0].try_extract_tensor::<f32>().map_err(ort_error)?;
+
+        let norm = data.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err("El modelo de embeddings devolvió un vector inválido.".to_string());
+        }
+        Ok(data.iter().map(|v| v / norm).collect())
+    }
+}
+
+/// Detecta y vectoriza los rostros de una imagen ya descargada. Bloqueante: inferencia en CPU.
+pub fn analyze_bytes(source: &str, bytes: &[u8], models: &Path) -> Result<FaceReport, String> {
+    let decoded = image::load_from_memory(bytes).map_err(|e| {
+        eprintln!("ERROR [faces]: Decode failure for {}: {}", source, e);
+        "No se pudo decodificar la imagen.".to_string()
+    })?;
+
+    let mut guard = ENGINE
+        .lock()
+        .map_err(|_| "El motor facial no está disponible.".to_string())?;
+    if guard.is_none() {
+        *guard = Some(Engine::load(models)?);
+    }
+    let engine = guard.as_mut().expect("motor inicializado");
+
+    let mut faces = Vec::new();
+    for bbox in engine.detect(&decoded)? {
+        let embedding = engine.embed(&decoded, &bbox)?;
+        faces.push(DetectedFace {
+            bbox,
+            embedding: encode_embedding(&embedding),
+        });
+    }
+    Ok(FaceReport {
+        source: source.to_string(),
+        sha256: format!("{:x}", Sha256::digest(bytes)),
+        width: decoded.width(),
+        height: decoded.height(),
+        faces,
+    })
+}
+
+/// Analiza una imagen local o remota.
+pub async fn analyze(
+    source: &str,
+    models: &Path,
+    config: &OsintConfig,
+) -> Result<FaceReport, String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err("Debe indicar una imagen (ruta local o URL).".to_string());
+    }
+    let status = status(models);
+    if !status.ready {
+        return Err(status.missing_message());
+    }
+
+    let bytes = crate::imagehash::fetch(source, config).await?;
+    let source_owned = source.to_string();
+    let models = models.to_path_buf();
+    tokio::task::spawn_blocking(move || analyze_bytes(&source_owned, &bytes, &models))
+        .await
+        .map_err(|e| format!("Error interno del análisis facial: {}", e))?
+}
+
+/// Guarda el análisis como evidencia del objetivo File `target_name`: los recuadros en el
+/// detalle "rostros", cada embedding como atributo, y un vínculo "mismo_rostro" con las
+/// imágenes del caso que superan el umbral. Devuelve las coincidencias encontradas.
+pub fn register(
+    case_manager: &CaseManager,
+    case_name: &str,
+    target_name: &str,
+    report: &FaceReport,
+) -> Result<Vec<FaceMatch>, String> {
+    let target_id = case_manager.ensure_target(case_name, target_name, TargetType::File)?;
+
+    // Rostros ya guardados en el caso, agrupados por imagen
+    let mut others: Vec<(String, FaceReport)> = Vec::new();
+    for (other_id, other_name, value) in
+        case_manager.list_attribute_values(case_name, ATTRIBUTE_KEY)?
+    {
+        if other_id == target_id {
+            continue;
+        }
+        let face = DetectedFace {
+            bbox: FaceBox {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+                score: 0.0,
+            },
+            embedding: value,
+        };
+        match others.iter_mut().find(|(id, _)| *id == other_id) {
+            Some((_, other)) => other.faces.push(face),
+            None => others.push((
+                other_id,
+                FaceReport {
+                    source: other_name,
+                    sha256: String::new(),
+                    width: 0,
+                    height: 0,
+                    faces: vec![face],
+                },
+            )),
+        }
+    }
+
+    let mut matches = Vec::new();
+    for (other_id, other) in &others {
+        let Some(found) = best_match(report, other).filter(|m| m.similarity >= LIKELY_THRESHOLD)
+        else {
+            continue;
+        };
+        if found.similarity >= MATCH_THRESHOLD {
+            case_manager.add_link(case_name, &target_id, other_id, "mismo_rostro")?;
+        }
+        matches.push(found);
+    }
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    for face in &report.faces {
+        case_manager.add_attribute(
+            case_name,
+            &target_id,
+            ATTRIBUTE_KEY,
+            &face.embedding,
+            "Biometric",
+        )?;
+    }
+    let details = serde_json::json!({
+        "source": report.source,
+        "sha256": report.sha256,
+        "rostros": report.faces.iter().map(|f| &f.bbox).collect::<Vec<_>>(),
+        "coincidencias": matches,
+        "umbral": MATCH_THRESHOLD,
+    });
+    case_manager.save_tool_details(case_name, target_name, TargetType::File, "rostros", details)?;
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_y_similitud() {
+        let a = vec![0.6, 0.8, 0.0, 0.0];
+        let encoded = encode_embedding(&a);
+        assert_eq!(decode_embedding(&encoded), Some(a.clone()));
+        assert_eq!(decode_embedding("no-es-base64"), None);
+
+        let same = cosine_similarity(&a, &[1.2, 1.6, 0.0, 0.0]).unwrap();
+        assert!((same - 1.0).abs() < 1e-6);
+        let orthogonal = cosine_similarity(&a, &[0.0, 0.0, 1.0, 0.0]).unwrap();
+        assert!(orthogonal.abs() < 1e-6);
+        assert_eq!(cosine_similarity(&a, &[1.0]), None);
+
+        let report = |source: &str, faces: &[&[f32]]| FaceReport {
+            source: source.to_string(),
+            sha256: String::new(),
+            width: 0,
+            height: 0,
+            faces: faces
+                .iter()
+                .map(|e| DetectedFace {
+                    bbox: FaceBox {
+                        x: 0,
+                        y: 0,
+                        width: 10,
+                        height: 10,
+                        score: 1.0,
+                    },
+                    embedding: encode_embedding(e),
+                })
+                .collect(),
+        };
+        let group = report("grupo.jpg", &[&[0.0, 1.0, 0.0, 0.0], &[0.6, 0.8, 0.0, 0.0]]);
+        let best = best_match(&report("perfil.jpg", &[&a]), &group).unwrap();
+        assert_eq!(best.image, "grupo.jpg");
+        assert_eq!(best.other_face, 1);
+        assert_eq!(best.verdict, "misma_persona");
+    }
+
+    #[test]
+    fn test_nms_descarta_solapados() {
+        let kept = non_max_suppression(vec![
+            ([0.10, 0.10, 0.30, 0.30], 0.80),
+            ([0.11, 0.11, 0.31, 0.31], 0.95),
+            ([0.60, 0.60, 0.80, 0.80], 0.75),
+        ]);
+        assert_eq!(kept.len(), 2);
+        assert_eq!(kept[0].1, 0.95);
+        assert_eq!(kept[1].1, 0.75);
+        assert!(iou(&[0.0, 0.0, 1.0, 1.0], &[2.0, 2.0, 3.0, 3.0]) == 0.0);
+        assert!((iou(&[0.0, 0.0, 1.0, 1.0], &[0.0, 0.0, 1.0, 1.0]) - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,524 @@
+// src-tauri/src/fingerprint.rs
+//
+// Huella HTTP de un sitio: qué responde, quién lo sirve y con qué está armado.
+//
+// Flujo:
+//   1. Se pide la URL con el cliente de `get_http_client` (mismo proxy y User-Agent) pero
+//      siguiendo las redirecciones a mano para registrar cada salto.
+//   2. De la respuesta final se extraen cabeceras de seguridad, banners de servidor/framework,
+//      cookies (sólo nombre y flags, nunca el valor) y tecnologías detectadas.
+//   3. Del HTML se sacan el título y los IDs de analytics/publicidad (UA-, G-, GTM-, pub-):
+//      dos dominios con el mismo ID suelen pertenecer al mismo operador.
+
+use crate::models::OsintConfig;
+use reqwest::header::{HeaderMap, LOCATION, SET_COOKIE};
+use reqwest::{redirect, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+const MAX_REDIRECTS: usize = 10;
+/// Tope de HTML descargado para el análisis
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Cabeceras de seguridad que se esperan en un sitio bien configurado.
+const SECURITY_HEADERS: &[&str] = &[
+    "strict-transport-security",
+    "content-security-policy",
+    "x-frame-options",
+    "x-content-type-options",
+    "referrer-policy",
+    "permissions-policy",
+    "cross-origin-opener-policy",
+];
+
+/// Cabeceras que delatan software de servidor o framework.
+const BANNER_HEADERS: &[&str] = &[
+    "server",
+    "x-powered-by",
+    "x-aspnet-version",
+    "x-aspnetmvc-version",
+    "x-generator",
+    "x-runtime",
+    "via",
+];
+
+/// (cabecera, fragmento en su valor o "" si basta con que exista, tecnología)
+const HEADER_SIGNATURES: &[(&str, &str, &str)] = &[
+    ("server", "cloudflare", "Cloudflare"),
+    ("cf-ray", "", "Cloudflare"),
+    ("x-vercel-id", "", "Vercel"),
+    ("x-amz-cf-id", "", "Amazon CloudFront"),
+    ("x-fastly-request-id", "", "Fastly"),
+    ("x-served-by", "cache-", "Fastly"),
+    ("x-shopify-stage", "", "Shopify"),
+    ("x-wix-request-id", "", "Wix"),
+    ("x-drupal-cache", "", "Drupal"),
+    ("x-nextjs-cache", "", "Next.js"),
+    ("x-powered-by", "php", "PHP"),
+    ("x-powered-by", "asp.net", "ASP.NET"),
+    ("x-powered-by", "express", "Express"),
+    ("server", "nginx", "nginx"),
+    ("server", "apache", "Apache"),
+    ("server", "microsoft-iis", "IIS"),
+    ("server", "litespeed", "LiteSpeed"),
+];
+
+/// (fragmento del HTML en minúsculas, tecnología)
+const BODY_SIGNATURES: &[(&str, &str)] = &[
+    ("/wp-content/", "WordPress"),
+    ("/wp-includes/", "WordPress"),
+    ("drupal-settings-json", "Drupal"),
+    ("/sites/default/files/", "Drupal"),
+    ("cdn.shopify.com", "Shopify"),
+    ("static.wixstatic.com", "Wix"),
+    ("/_next/static/", "Next.js"),
+    ("__next_data__", "Next.js"),
+    ("/_nuxt/", "Nuxt"),
+    ("ng-version=", "Angular"),
+    ("data-reactroot", "React"),
+    ("jquery", "jQuery"),
+    ("googletagmanager.com", "Google Tag Manager"),
+    ("google-analytics.com", "Google Analytics"),
+    ("pagead2.googlesyndication.com", "Google AdSense"),
+];
+
+/// (prefijo, tipo) de los identificadores de seguimiento reconocidos.
+const TRACKING_PREFIXES: &[(&str, &str)] = &[
+    ("UA-", "google_analytics"),
+    ("G-", "google_analytics_4"),
+    ("GTM-", "google_tag_manager"),
+    ("pub-", "google_adsense"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+/// Cookie recibida: el valor se descarta a propósito (puede ser una sesión).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CookieInfo {
+    pub name: String,
+    pub secure: bool,
+    pub http_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TrackingId {
+    pub id: String,
+    pub kind: String,
+}
+
+/// Resultado del análisis, listo para `detalles_tecnicos.http`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HttpFingerprint {
+    pub url: String,
+    pub final_url: String,
+    pub status: u16,
+    /// Saltos previos a la respuesta final (vacío si no hubo redirecciones)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub redirects: Vec<RedirectHop>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub security_headers: BTreeMap<String, String>,
+    pub missing_security_headers: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub banners: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub technologies: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub cookies: Vec<CookieInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tracking_ids: Vec<TrackingId>,
+}
+
+impl HttpFingerprint {
+    /// Host pedido, que es el objetivo donde se guarda la huella.
+    pub fn host(&self) -> Option<String> {
+        host_of(&self.url)
+    }
+
+    /// Host de la respuesta final (puede diferir del pedido tras las redirecciones).
+    pub fn final_host(&self) -> Option<String> {
+        host_of(&self.final_url)
+    }
+}
+
+fn host_of(url: &str) -> Option<String> {
+    Url::parse(url).ok().and_then(|u| {
+        u.host_str()
+            .map(|h| h.trim_matches(['[', ']']).to_lowercase())
+    })
+}
+
+/// Normaliza el objetivo a una URL http(s). Sin esquema se asume HTTPS.
+fn normalize_url(target: &str) -> Result<Url, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Debe indicar una URL o dominio.".to_string());
+    }
+    let with_scheme = if target.contains("://") {
+        target.to_string()
+    } else {
+        format!("https://{}", target)
+    };
+    let url = Url::parse(&with_scheme).map_err(|_| format!("URL inválida: {}", target))?;
+    match url.scheme() {
+        "http" | "https" if url.host_str().is_some() => Ok(url),
+        _ => Err(format!("Sólo se admiten URLs http/https: {}", target)),
+    }
+}
+
+/// Obtiene la huella HTTP de una URL o dominio.
+pub async fn fingerprint(target: &str, config: &OsintConfig) -> Result<HttpFingerprint, String> {
+    let start = normalize_url(target)?;
+    let explicit_scheme = target.contains("://");
+
+    let client = crate::tools::http_client_builder(config)
+        .redirect(redirect::Policy::none())
+        .build()
+        .map_err(|e| {
+            eprintln!("ERROR [fingerprint]: Client build failure: {}", e);
+            "No se pudo preparar el cliente HTTP.".to_string()
+        })?;
+
+    let mut current = start.clone();
+    let mut redirects = Vec::new();
+    let mut cookies: Vec<CookieInfo> = Vec::new();
+
+    loop {
+        let response = match client.get(current.clone()).send().await {
+            Ok(r) => r,
+            // Sin esquema explícito, un sitio que no habla HTTPS se reintenta por HTTP
+            Err(_) if redirects.is_empty() && !explicit_scheme && current.scheme() == "https" => {
+                let _ = current.set_scheme("http");
+                client.get(current.clone()).send().await.map_err(|e| {
+                    eprintln!("ERROR [fingerprint]: Request failure {}: {}", current, e);
+                    format!(
+                        "No se pudo conectar con {}",
+                        start.host_str().unwrap_or(target)
+                    )
+                })?
+            }
+            Err(e) => {
+                eprintln!("ERROR [fingerprint]: Request failure {}: {}", current, e);
+                return Err(format!("No se pudo conectar con {}", current));
+            }
+        };
+
+        let status = response.status();
+        for cookie in parse_cookies(response.headers()) {
+            if !cookies.iter().any(|c| c.name == cookie.name) {
+                cookies.push(cookie);
+            }
+        }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let next = location.as_deref().and_then(|l| current.join(l).ok());
+
+        if status.is_redirection() && next.is_some() {
+            redirects.push(RedirectHop {
+                url: current.to_string(),
+                status: status.as_u16(),
+                location,
+            });
+            if redirects.len() > MAX_REDIRECTS {
+                return Err(format!(
+                    "Demasiadas redirecciones (más de {}) desde {}",
+                    MAX_REDIRECTS, start
+                ));
+            }
+            current = next.unwrap_or(current);
+            continue;
+        }
+
+        let headers = response.headers().clone();
+        let html = read_body(response).await;
+        return Ok(analyze(
+            start.to_string(),
+            current.to_string(),
+            status.as_u16(),
+            redirects,
+            &headers,
+            &html,
+            cookies,
+        ));
+    }
+}
+
+/// Lee el cuerpo respetando `MAX_BODY_BYTES`; un error a mitad de lectura deja lo leído.
+async fn read_body(mut response: reqwest::Response) -> String {
+    let mut body = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_BODY_BYTES {
+            body.truncate(MAX_BODY_BYTES);
+            break;
+        }
+    }
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+fn analyze(
+    url: String,
+    final_url: String,
+    status: u16,
+    redirects: Vec<RedirectHop>,
+    headers: &HeaderMap,
+    html: &str,
+    cookies: Vec<CookieInfo>,
+) -> HttpFingerprint {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_string())
+    };
+
+    let mut security_headers = BTreeMap::new();
+    let mut missing_security_headers = Vec::new();
+    for name in SECURITY_HEADERS {
+        match header(name) {
+            Some(value) => {
+                security_headers.insert(name.to_string(), value);
+            }
+            None => missing_security_headers.push(name.to_string()),
+        }
+    }
+
+    let banners: BTreeMap<String, String> = BANNER_HEADERS
+        .iter()
+        .filter_map(|name| header(name).map(|v| (name.to_string(), v)))
+        .collect();
+
+    let mut technologies = BTreeSet::new();
+    for (name, needle, tech) in HEADER_SIGNATURES {
+        if let Some(value) = header(name) {
+            if needle.is_empty() || value.to_lowercase().contains(needle) {
+                technologies.insert(tech.to_string());
+            }
+        }
+    }
+    let lower = html.to_lowercase();
+    for (needle, tech) in BODY_SIGNATURES {
+        if lower.contains(needle) {
+            technologies.insert(tech.to_string());
+        }
+    }
+
+    let document = scraper::Html::parse_document(html);
+    let title = scraper::Selector::parse("title")
+        .ok()
+        .and_then(|sel| {
+            document
+                .select(&sel)
+                .next()
+                .map(|t| t.text().collect::<String>())
+        })
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|t| !t.is_empty());
+    if let Ok(sel) = scraper::Selector::parse("meta[name=generator]") {
+        for meta in document.select(&sel) {
+            if let Some(content) = meta.value().attr("content").map(str::trim) {
+                if !content.is_empty() {
+                    technologies.insert(content.to_string());
+                }
+            }
+        }
+    }
+
+    HttpFingerprint {
+        url,
+        final_url,
+        status,
+        redirects,
+        title,
+        security_headers,
+        missing_security_headers,
+        banners,
+        technologies: technologies.into_iter().collect(),
+        cookies,
+        tracking_ids: extract_tracking_ids(html),
+    }
+}
+
+/// Interpreta las cabeceras `Set-Cookie` quedándose sólo con el nombre y los flags.
+fn parse_cookies(headers: &HeaderMap) -> Vec<CookieInfo> {
+    headers
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .filter_map(parse_set_cookie)
+        .collect()
+}
+
+fn parse_set_cookie(raw: &str) -> Option<CookieInfo> {
+    let mut parts = raw.split(';');
+    let name = parts.next()?.split('=').next()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut cookie = CookieInfo {
+        name: name.to_string(),
+        ..Default::default()
+    };
+    for attr in parts {
+        let (key, value) = match attr.split_once('=') {
+            Some((k, v)) => (k.trim(), Some(v.trim())),
+            None => (attr.trim(), None),
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "secure" => cookie.secure = true,
+            "httponly" => cookie.http_only = true,
+            "samesite" => cookie.same_site = value.map(str::to_string),
+            "domain" => cookie.domain = value.map(str::to_string),
+            _ => {}
+        }
+    }
+    Some(cookie)
+}
+
+/// Busca IDs de Google Analytics (UA-/G-), Tag Manager (GTM-) y AdSense (pub-) en el HTML.
+pub fn extract_tracking_ids(html: &str) -> Vec<TrackingId> {
+    let mut found = BTreeSet::new();
+    for (prefix, kind) in TRACKING_PREFIXES {
+        for (pos, _) in html.match_indices(prefix) {
+            // El prefijo tiene que empezar una palabra: descarta "PNG-", "IMG-", etc.
+            if html[..pos]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric())
+            {
+                continue;
+            }
+            let rest = &html[pos + prefix.len()..];
+            let suffix: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || (*prefix == "UA-" && *c == '-'))
+                .collect();
+            if is_valid_tracking_suffix(prefix, &suffix) {
+                found.insert(TrackingId {
+                    id: format!("{}{}", prefix, suffix),
+                    kind: kind.to_string(),
+                });
+            }
+        }
+    }
+    found.into_iter().collect()
+}
+
+fn is_valid_tracking_suffix(prefix: &str, suffix: &str) -> bool {
+    let upper_alnum = |s: &str| {
+        s.chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+    };
+    let digits = |s: &str, min: usize, max: usize| {
+        (min..=max).contains(&s.len()) && s.chars().all(|c| c.is_ascii_digit())
+    };
+    match prefix {
+        "UA-" => suffix
+            .split_once('-')
+            .is_some_and(|(account, property)| digits(account, 4, 10) && digits(property, 1, 4)),
+        // Los IDs de GA4 siempre llevan algún dígito; sin esto "G-" matchea palabras sueltas
+        "G-" => {
+            (8..=12).contains(&suffix.len())
+                && upper_alnum(suffix)
+                && suffix.chars().any(|c| c.is_ascii_digit())
+        }
+        "GTM-" => (4..=9).contains(&suffix.len()) && upper_alnum(suffix),
+        "pub-" => digits(suffix, 10, 20),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extrae_ids_de_seguimiento() {
+        let html = r#"
+            <script async src="https://www.googletagmanager.com/gtag/js?id=G-AB12CD34EF"></script>
+            <script>ga('create', 'UA-1234567-2', 'auto'); gtag('config', 'UA-1234567-2');</script>
+            <script>(function(w,d,s,l,i){})(window,document,'script','dataLayer','GTM-K9XYZ12');</script>
+            <ins data-ad-client="ca-pub-1234567890123456"></ins>
+            <img src="IMG-20240101.PNG-G-HEADER.png"> <p>G-force y UA-12</p>
+        "#;
+        let ids: Vec<(String, String)> = extract_tracking_ids(html)
+            .into_iter()
+            .map(|t| (t.id, t.kind))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("G-AB12CD34EF".into(), "google_analytics_4".into()),
+                ("GTM-K9XYZ12".into(), "google_tag_manager".into()),
+                ("UA-1234567-2".into(), "google_analytics".into()),
+                ("pub-1234567890123456".into(), "google_adsense".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_analiza_cabeceras_y_cookies() {
+        let mut headers = HeaderMap::new();
+        headers.insert("server", "cloudflare".parse().unwrap());
+        headers.insert("x-powered-by", "PHP/8.1.2".parse().unwrap());
+        headers.insert(
+            "strict-transport-security",
+            "max-age=31536000".parse().unwrap(),
+        );
+        headers.append(
+            SET_COOKIE,
+            "PHPSESSID=secreto; path=/; Secure; HttpOnly; SameSite=Lax"
+                .parse()
+                .unwrap(),
+        );
+        let html = r#"<html><head><title> Tienda
+            Ejemplo </title><meta name="generator" content="WordPress 6.4"></head>
+            <body><link href="/wp-content/themes/x.css"></body></html>"#;
+
+        let report = analyze(
+            "https://ejemplo.com.ar/".into(),
+            "https://ejemplo.com.ar/".into(),
+            200,
+            vec![],
+            &headers,
+            html,
+            parse_cookies(&headers),
+        );
+        assert_eq!(report.title.as_deref(), Some("Tienda Ejemplo"));
+        assert!(report
+            .security_headers
+            .contains_key("strict-transport-security"));
+        assert!(report
+            .missing_security_headers
+            .contains(&"content-security-policy".to_string()));
+        assert_eq!(report.banners.get("x-powered-by").unwrap(), "PHP/8.1.2");
+        for tech in ["Cloudflare", "PHP", "WordPress", "WordPress 6.4"] {
+            assert!(report.technologies.contains(&tech.to_string()), "{}", tech);
+        }
+        assert_eq!(
+            report.cookies,
+            vec![CookieInfo {
+                name: "PHPSESSID".into(),
+                secure: true,
+                http_only: true,
+                same_site: Some("Lax".into()),
+                domain: None,
+            }]
+        );
+    }
+}
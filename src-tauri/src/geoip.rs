@@ -0,0 +1,410 @@
+// src-tauri/src/geoip.rs
+//
+// Geolocalización de IPs sin salir a Internet.
+//
+// Flujo:
+//   1. Si el analista configuró una base MMDB (`geoip_database`: GeoLite2/GeoIP2 City,
+//      Country o ASN, DB-IP Lite, etc.) se consulta primero: según el tipo de base aporta
+//      país, región, ciudad, coordenadas, zona horaria y/o ASN, más el prefijo que contiene
+//      a la IP.
+//   2. Si no hay base propia, falla o no conoce la IP, se usan los archivos `geoip` y
+//      `geoip6` que vienen con el sidecar de Tor (solo país, rangos "desde,hasta,CC").
+//   3. Las tablas se cargan una vez y quedan en memoria; se recargan si cambia la ruta.
+//   4. Las IPs privadas o reservadas se rechazan: no tienen ubicación.
+
+use crate::models::OsintConfig;
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const TOR_SOURCE: &str = "tor-geoip";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct GeoLocation {
+    pub ip: String,
+    /// Base que respondió ("tor-geoip" o el tipo de la MMDB, ej: "GeoLite2-City")
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_org: Option<String>,
+    /// Prefijo de la base que contiene a la IP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+}
+
+/// Resultado de `ip_intel`: ubicación local y, si se habilitó, el enriquecimiento en línea.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IpIntelReport {
+    pub ip: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoLocation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub online: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Rangos "desde,hasta,país" ordenados, como los distribuye Tor.
+#[derive(Debug, Default)]
+struct RangeTable<T> {
+    ranges: Vec<(T, T, [u8; 2])>,
+}
+
+impl<T: Ord + Copy> RangeTable<T> {
+    fn from_ranges(mut ranges: Vec<(T, T, [u8; 2])>) -> Self {
+        ranges.sort_by_key(|r| r.0);
+        Self { ranges }
+    }
+
+    fn country(&self, ip: T) -> Option<String> {
+        let i = self.ranges.partition_point(|r| r.0 <= ip);
+        let (_, high, cc) = self.ranges.get(i.checked_sub(1)?)?;
+        (ip <= *high).then(|| String::from_utf8_lossy(cc).into_owned())
+    }
+}
+
+fn country_code(cc: &str) -> Option<[u8; 2]> {
+    // "??" (sin asignar) y "A1"/"A2" (proxies anónimos, satélite) no son países
+    match cc.trim().as_bytes() {
+        [a, b] if a.is_ascii_uppercase() && b.is_ascii_uppercase() => Some([*a, *b]),
+        _ => None,
+    }
+}
+
+/// `geoip`: "16777216,16777471,AU" (enteros). `geoip6`: "2001:200::,2001:200:ffff:...,JP".
+fn parse_tor_lines<T, F>(text: &str, parse: F) -> Vec<(T, T, [u8; 2])>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split(',');
+            let low = parse(parts.next()?)?;
+            let high = parse(parts.next()?)?;
+            Some((low, high, country_code(parts.next()?)?))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct TorGeoIp {
+    v4: RangeTable<u32>,
+    v6: RangeTable<u128>,
+}
+
+impl TorGeoIp {
+    fn parse(v4: &str, v6: &str) -> Self {
+        Self {
+            v4: RangeTable::from_ranges(parse_tor_lines(v4, |s| s.trim().parse().ok())),
+            v6: RangeTable::from_ranges(parse_tor_lines(v6, |s| {
+                s.trim().parse::<Ipv6Addr>().ok().map(u128::from)
+            })),
+        }
+    }
+
+    fn country(&self, ip: IpAddr) -> Option<String> {
+        match ip {
+            IpAddr::V4(v4) => self.v4.country(u32::from(v4)),
+            IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+                Some(v4) => self.v4.country(u32::from(v4)),
+                None => self.v6.country(u128::from(v6)),
+            },
+        }
+    }
+}
+
+/// Archivos GeoIP del sidecar de Tor (los mismos que recibe con --GeoIPFile/--GeoIPv6File).
+pub fn tor_geoip_files(resource_dir: &Path) -> (PathBuf, PathBuf) {
+    let dir = resource_dir.join("resources").join("tor");
+    (dir.join("geoip"), dir.join("geoip6"))
+}
+
+/// Base cargada junto con la ruta de la que salió
+type Cached<T> = Mutex<Option<(PathBuf, Arc<T>)>>;
+
+static TOR_TABLES: Cached<TorGeoIp> = Mutex::new(None);
+static MMDB: Cached<Reader<Vec<u8>>> = Mutex::new(None);
+
+fn tor_tables(resource_dir: &Path) -> Result<Arc<TorGeoIp>, String> {
+    let mut cached = TOR_TABLES.lock().map_err(|e| e.to_string())?;
+    if let Some((dir, tables)) = cached.as_ref() {
+        if dir == resource_dir {
+            return Ok(tables.clone());
+        }
+    }
+    let (v4_path, v6_path) = tor_geoip_files(resource_dir);
+    let v4 = std::fs::read_to_string(&v4_path).map_err(|e| {
+        eprintln!("ERROR [geoip]: Failed to read {}: {}", v4_path.display(), e);
+        "No se encontró la base GeoIP de Tor.".to_string()
+    })?;
+    // geoip6 es opcional: sin él solo se ubican IPv4
+    let v6 = std::fs::read_to_string(&v6_path).unwrap_or_default();
+    let tables = Arc::new(TorGeoIp::parse(&v4, &v6));
+    *cached = Some((resource_dir.to_path_buf(), tables.clone()));
+    Ok(tables)
+}
+
+fn mmdb_reader(path: &Path) -> Result<Arc<Reader<Vec<u8>>>, String> {
+    let mut cached = MMDB.lock().map_err(|e| e.to_string())?;
+    if let Some((loaded, reader)) = cached.as_ref() {
+        if loaded == path {
+            return Ok(reader.clone());
+        }
+    }
+    let reader = Reader::open_readfile(path).map(Arc::new).map_err(|e| {
+        eprintln!("ERROR [geoip]: Failed to open {}: {}", path.display(), e);
+        format!("No se pudo abrir la base MMDB {}.", path.display())
+    })?;
+    *cached = Some((path.to_path_buf(), reader.clone()));
+    Ok(reader)
+}
+
+/// Nombre en español si la base lo trae; si no, en inglés.
+fn localized(names: &Option<BTreeMap<&str, &str>>) -> Option<String> {
+    let names = names.as_ref()?;
+    names
+        .get("es")
+        .or_else(|| names.get("en"))
+        .map(|n| n.to_string())
+}
+
+fn prefix(ip: IpAddr, len: usize) -> String {
+    let network = match ip {
+        IpAddr::V4(v4) => {
+            let mask = u32::MAX
+                .checked_shl(32u32.saturating_sub(len as u32))
+                .unwrap_or(0);
+            IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask))
+        }
+        IpAddr::V6(v6) => {
+            let mask = u128::MAX
+                .checked_shl(128u32.saturating_sub(len as u32))
+                .unwrap_or(0);
+            IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask))
+        }
+    };
+    format!("{}/{}", network, len)
+}
+
+/// Consulta la MMDB según su tipo. `None` si la IP no está en la base.
+fn mmdb_lookup(reader: &Reader<Vec<u8>>, ip: IpAddr) -> Result<Option<GeoLocation>, String> {
+    let db_type = reader.metadata.database_type.clone();
+    let mut location = GeoLocation {
+        ip: ip.to_string(),
+        source: db_type.clone(),
+        ..Default::default()
+    };
+    let not_found = |e: &MaxMindDBError| matches!(e, MaxMindDBError::AddressNotFoundError(_));
+
+    let lower = db_type.to_lowercase();
+    let prefix_len = if lower.contains("asn") || lower.contains("isp") {
+        match reader.lookup_prefix::<geoip2::Asn>(ip) {
+            Ok((asn, len)) => {
+                location.asn = asn.autonomous_system_number;
+                location.as_org = asn.autonomous_system_organization.map(str::to_string);
+                len
+            }
+            Err(e) if not_found(&e) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        }
+    } else {
+        // City y Country comparten estructura; los campos que la base no tiene quedan vacíos
+        match reader.lookup_prefix::<geoip2::City>(ip) {
+            Ok((city, len)) => {
+                let country = city.country.or(city.registered_country);
+                location.country_code = country
+                    .as_ref()
+                    .and_then(|c| c.iso_code)
+                    .map(str::to_string);
+                location.country = country.as_ref().and_then(|c| localized(&c.names));
+                location.region = city
+                    .subdivisions
+                    .as_ref()
+                    .and_then(|s| s.first())
+                    .and_then(|s| localized(&s.names));
+                location.city = city.city.as_ref().and_then(|c| localized(&c.names));
+                if let Some(loc) = city.location {
+                    location.latitude = loc.latitude;
+                    location.longitude = loc.longitude;
+                    location.time_zone = loc.time_zone.map(str::to_string);
+                }
+                len
+            }
+            Err(e) if not_found(&e) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    location.network = Some(prefix(ip, prefix_len));
+    Ok(Some(location))
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.octets()[0] == 100 && (64..128).contains(&v4.octets()[1])
+                || v4.octets()[0] >= 224)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80
+                    || first == 0x2001 && v6.segments()[1] == 0x0db8)
+            }
+        },
+    }
+}
+
+/// Ubica una IP con las bases locales. Los problemas con la MMDB propia no son fatales:
+/// quedan en `warnings` y se sigue con la base de Tor.
+pub fn locate(
+    ip: &str,
+    resource_dir: &Path,
+    config: &OsintConfig,
+    warnings: &mut Vec<String>,
+) -> Result<GeoLocation, String> {
+    let ip: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| "Se espera una dirección IP (v4 o v6).".to_string())?;
+    if !is_public(ip) {
+        return Err("La IP es privada o reservada: no tiene geolocalización.".to_string());
+    }
+
+    let mmdb_path = config.geoip_database.trim();
+    if !mmdb_path.is_empty() {
+        match mmdb_reader(Path::new(mmdb_path)).and_then(|r| mmdb_lookup(&r, ip)) {
+            // Una base ASN no sabe de países: se completa con la de Tor
+            Ok(Some(mut location)) => {
+                if location.country_code.is_none() {
+                    if let Ok(tor) = tor_tables(resource_dir) {
+                        location.country_code = tor.country(ip);
+                    }
+                }
+                return Ok(location);
+            }
+            Ok(None) => {}
+            Err(e) => warnings.push(e),
+        }
+    }
+
+    let country_code = tor_tables(resource_dir)?.country(ip);
+    if country_code.is_none() {
+        return Err("La IP no figura en las bases GeoIP locales.".to_string());
+    }
+    Ok(GeoLocation {
+        ip: ip.to_string(),
+        source: TOR_SOURCE.to_string(),
+        country_code,
+        ..Default::default()
+    })
+}
+
+/// `locate` fuera del runtime async (la primera carga lee varios MB de disco).
+pub async fn lookup(
+    ip: &str,
+    resource_dir: &Path,
+    config: &OsintConfig,
+) -> (Result<GeoLocation, String>, Vec<String>) {
+    let (ip, dir, config) = (ip.to_string(), resource_dir.to_path_buf(), config.clone());
+    tokio::task::spawn_blocking(move || {
+        let mut warnings = Vec::new();
+        let result = locate(&ip, &dir, &config, &mut warnings);
+        (result, warnings)
+    })
+    .await
+    .unwrap_or_else(|e| (Err(e.to_string()), Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tablas_de_tor_v4_y_v6() {
+        let v4 = "# Last updated based on February 7 2024 Maxmind GeoLite2 Country\n\
+                  16777216,16777471,AU\n\
+                  134744064,134744319,US\n\
+                  3232235520,3232235775,??\n\
+                  basura\n";
+        let v6 = "# comentario\n\
+                  2001:200::,2001:200:ffff:ffff:ffff:ffff:ffff:ffff,JP\n\
+                  2800::,2800:ff:ffff:ffff:ffff:ffff:ffff:ffff,AR\n";
+        let tor = TorGeoIp::parse(v4, v6);
+        assert_eq!(tor.v4.ranges.len(), 2);
+        assert_eq!(tor.country("8.8.8.8".parse().unwrap()), Some("US".into()));
+        assert_eq!(tor.country("1.0.0.1".parse().unwrap()), Some("AU".into()));
+        assert_eq!(tor.country("1.0.1.1".parse().unwrap()), None);
+        assert_eq!(
+            tor.country("2800:40::1".parse().unwrap()),
+            Some("AR".into())
+        );
+        assert_eq!(
+            tor.country("::ffff:8.8.8.8".parse().unwrap()),
+            Some("US".into())
+        );
+        assert_eq!(tor.country("2a00::1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_filtra_privadas_y_arma_prefijos() {
+        for ip in [
+            "10.1.2.3",
+            "192.168.0.1",
+            "127.0.0.1",
+            "100.64.0.1",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{}", ip);
+        }
+        assert!(is_public("8.8.8.8".parse().unwrap()));
+        assert!(is_public("2800:40::1".parse().unwrap()));
+        assert_eq!(prefix("8.8.4.4".parse().unwrap(), 24), "8.8.4.0/24");
+        assert_eq!(prefix("2800:40::1".parse().unwrap(), 32), "2800:40::/32");
+
+        let mut warnings = Vec::new();
+        let config = OsintConfig::default();
+        assert!(locate("192.168.1.1", Path::new("."), &config, &mut warnings).is_err());
+        assert!(locate("no-es-ip", Path::new("."), &config, &mut warnings).is_err());
+
+        // Una MMDB inexistente avisa y cae en la base de Tor (acá tampoco existe)
+        let config = OsintConfig {
+            geoip_database: "/no/existe.mmdb".to_string(),
+            ..Default::default()
+        };
+        let dir = Path::new("/no/existe");
+        assert!(locate("8.8.8.8", dir, &config, &mut warnings).is_err());
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -0,0 +1,299 @@
+// src-tauri/src/hashing.rs
+//
+// Hashes de archivos para pivotear sobre adjuntos sospechosos.
+//
+// Flujo:
+//   1. Se calculan MD5, SHA-1 y SHA-256 (identifican el contenido exacto) y un hash difuso
+//      estilo ssdeep/spamsum (detecta variantes: el mismo documento con cambios menores,
+//      un binario re-empaquetado, etc.).
+//   2. Si hay API key de VirusTotal se consulta el SHA-256 con `virustotal::lookup`:
+//      detecciones, etiqueta de amenaza, nombres con los que circuló y primera vez que se vio.
+//   3. El orquestador registra el archivo (File) y cada hash (Hash) como objetivos vinculados,
+//      y compara el hash difuso con el de los demás archivos del caso.
+
+use crate::models::OsintConfig;
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+// Parámetros de spamsum (los mismos que ssdeep)
+const ROLLING_WINDOW: usize = 7;
+const MIN_BLOCKSIZE: u32 = 3;
+const SPAMSUM_LENGTH: usize = 64;
+const HASH_PRIME: u32 = 0x0100_0193;
+const HASH_INIT: u32 = 0x2802_1967;
+const B64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Similitud mínima (0-100) para considerar dos archivos como variantes
+pub const FUZZY_MATCH_THRESHOLD: u32 = 60;
+
+/// Resultado del cálculo, listo para `detalles_tecnicos.hashes` del objetivo File.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HashReport {
+    pub file: String,
+    pub size_bytes: u64,
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+    /// Hash difuso "tamaño_bloque:firma:firma_doble" (formato ssdeep)
+    pub ssdeep: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virustotal: Option<crate::virustotal::VtReport>,
+    /// Motivo por el que no se pudo consultar VirusTotal (sin API key, cuota agotada...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virustotal_note: Option<String>,
+}
+
+/// Calcula los hashes de un archivo local.
+pub fn hash_file(path: &str) -> Result<HashReport, String> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        eprintln!("ERROR [hashing]: Failed to open file: {} - {}", path, e);
+        "No se pudo abrir el archivo especificado.".to_string()
+    })?;
+    Ok(HashReport {
+        file: Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string()),
+        size_bytes: bytes.len() as u64,
+        md5: format!("{:x}", Md5::digest(&bytes)),
+        sha1: format!("{:x}", Sha1::digest(&bytes)),
+        sha256: format!("{:x}", Sha256::digest(&bytes)),
+        ssdeep: fuzzy_hash(&bytes),
+        virustotal: None,
+        virustotal_note: None,
+    })
+}
+
+/// Hashes del archivo y, si hay API key, su reputación en VirusTotal.
+pub async fn analyze(path: &str, config: &OsintConfig) -> Result<HashReport, String> {
+    let owned = path.to_string();
+    let mut report = tokio::task::spawn_blocking(move || hash_file(&owned))
+        .await
+        .map_err(|e| format!("Error interno al calcular hashes: {}", e))??;
+
+    // Sin API key `lookup` devuelve el motivo, que queda como nota
+    match crate::virustotal::lookup(&report.sha256, config).await {
+        Ok(vt) => report.virustotal = Some(vt),
+        Err(e) => report.virustotal_note = Some(e),
+    }
+    Ok(report)
+}
+
+/// Hash rodante de spamsum sobre una ventana de 7 bytes.
+#[derive(Default)]
+struct RollingHash {
+    window: [u8; ROLLING_WINDOW],
+    h1: u32,
+    h2: u32,
+    h3: u32,
+    n: usize,
+}
+
+impl RollingHash {
+    fn update(&mut self, c: u8) -> u32 {
+        let c32 = c as u32;
+        self.h2 = self.h2.wrapping_sub(self.h1);
+        self.h2 = self
+            .h2
+            .wrapping_add((ROLLING_WINDOW as u32).wrapping_mul(c32));
+        self.h1 = self.h1.wrapping_add(c32);
+        self.h1 = self
+            .h1
+            .wrapping_sub(self.window[self.n % ROLLING_WINDOW] as u32);
+        self.window[self.n % ROLLING_WINDOW] = c;
+        self.n += 1;
+        self.h3 = (self.h3 << 5) ^ c32;
+        self.h1.wrapping_add(self.h2).wrapping_add(self.h3)
+    }
+}
+
+fn sum_hash(c: u8, h: u32) -> u32 {
+    h.wrapping_mul(HASH_PRIME) ^ c as u32
+}
+
+/// Hash difuso estilo ssdeep (algoritmo spamsum): "tamaño_bloque:firma:firma_doble".
+pub fn fuzzy_hash(data: &[u8]) -> String {
+    let mut block_size = MIN_BLOCKSIZE;
+    while (block_size as usize) * SPAMSUM_LENGTH < data.len() {
+        block_size *= 2;
+    }
+
+    loop {
+        let mut roll = RollingHash::default();
+        let (mut h1, mut h2) = (HASH_INIT, HASH_INIT);
+        let (mut sig1, mut sig2) = (String::new(), String::new());
+        let mut rolled = 0;
+
+        for &c in data {
+            h1 = sum_hash(c, h1);
+            h2 = sum_hash(c, h2);
+            rolled = roll.update(c);
+
+            if rolled % block_size == block_size - 1 {
+                if sig1.len() < SPAMSUM_LENGTH - 1 {
+                    sig1.push(B64[(h1 % 64) as usize] as char);
+                    h1 = HASH_INIT;
+                }
+                if rolled % (block_size * 2) == block_size * 2 - 1
+                    && sig2.len() < SPAMSUM_LENGTH / 2 - 1
+                {
+                    sig2.push(B64[(h2 % 64) as usize] as char);
+                    h2 = HASH_INIT;
+                }
+            }
+        }
+        if rolled != 0 {
+            sig1.push(B64[(h1 % 64) as usize] as char);
+            sig2.push(B64[(h2 % 64) as usize] as char);
+        }
+
+        // Firma demasiado corta: se repite con un bloque más chico
+        if block_size > MIN_BLOCKSIZE && sig1.len() < SPAMSUM_LENGTH / 2 {
+            block_size /= 2;
+            continue;
+        }
+        return format!("{}:{}:{}", block_size, sig1, sig2);
+    }
+}
+
+/// Las repeticiones largas de un carácter no aportan información y distorsionan la distancia.
+fn eliminate_sequences(sig: &str) -> Vec<u8> {
+    let mut out: Vec<u8> = Vec::with_capacity(sig.len());
+    for &b in sig.as_bytes() {
+        if out.len() >= 3 && out[out.len() - 3..].iter().all(|&p| p == b) {
+            continue;
+        }
+        out.push(b);
+    }
+    out
+}
+
+/// Distancia de edición con inserción/borrado = 1 y sustitución = 2 (la que usa ssdeep).
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = prev[j] + if ca == cb { 0 } else { 2 };
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+fn score_signatures(a: &[u8], b: &[u8], block_size: u32) -> u32 {
+    if a.len() > SPAMSUM_LENGTH || b.len() > SPAMSUM_LENGTH {
+        return 0;
+    }
+    // Sin al menos una ventana en común las coincidencias son casuales
+    if a.len() < ROLLING_WINDOW
+        || b.len() < ROLLING_WINDOW
+        || !a
+            .windows(ROLLING_WINDOW)
+            .any(|w| b.windows(ROLLING_WINDOW).any(|o| o == w))
+    {
+        return 0;
+    }
+
+    let distance = edit_distance(a, b) * SPAMSUM_LENGTH / (a.len() + b.len());
+    let distance = (100 * distance / SPAMSUM_LENGTH) as u32;
+    if distance >= 100 {
+        return 0;
+    }
+    let score = 100 - distance;
+    // Con bloques chicos las firmas cortas coinciden demasiado fácil: se acota el puntaje
+    let small_block = (99 + ROLLING_WINDOW as u32) / ROLLING_WINDOW as u32 * MIN_BLOCKSIZE;
+    if block_size < small_block {
+        score.min(block_size / MIN_BLOCKSIZE * a.len().min(b.len()) as u32)
+    } else {
+        score
+    }
+}
+
+/// Similitud (0-100) entre dos hashes difusos. Sólo se comparan firmas con tamaños de
+/// bloque iguales o consecutivos, como en ssdeep.
+pub fn fuzzy_compare(a: &str, b: &str) -> u32 {
+    let parse = |h: &str| {
+        let mut parts = h.splitn(3, ':');
+        let block_size: u32 = parts.next()?.parse().ok()?;
+        Some((
+            block_size,
+            parts.next()?.to_string(),
+            parts.next()?.to_string(),
+        ))
+    };
+    let (Some((bs_a, a1, a2)), Some((bs_b, b1, b2))) = (parse(a), parse(b)) else {
+        return 0;
+    };
+    if a == b {
+        return 100;
+    }
+    let (a1, a2, b1, b2) = (
+        eliminate_sequences(&a1),
+        eliminate_sequences(&a2),
+        eliminate_sequences(&b1),
+        eliminate_sequences(&b2),
+    );
+
+    if bs_a == bs_b {
+        score_signatures(&a1, &b1, bs_a).max(score_signatures(&a2, &b2, bs_a * 2))
+    } else if bs_a == bs_b * 2 {
+        score_signatures(&a1, &b2, bs_a)
+    } else if bs_b == bs_a * 2 {
+        score_signatures(&a2, &b1, bs_b)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bytes pseudoaleatorios reproducibles (generador congruencial lineal)
+    fn sample(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_difuso_detecta_variantes() {
+        let original = sample(64 * 1024, 7);
+        let mut variant = original.clone();
+        // Se pisa un tramo del medio, como al editar un párrafo
+        variant[30_000..30_400].copy_from_slice(&sample(400, 99));
+        let unrelated = sample(64 * 1024, 1234);
+
+        let h_original = fuzzy_hash(&original);
+        assert_eq!(h_original, fuzzy_hash(&original));
+        assert!(h_original.starts_with("1536:"), "{}", h_original);
+
+        assert_eq!(fuzzy_compare(&h_original, &h_original), 100);
+        let similar = fuzzy_compare(&h_original, &fuzzy_hash(&variant));
+        assert!(similar >= FUZZY_MATCH_THRESHOLD, "{}", similar);
+        assert_eq!(fuzzy_compare(&h_original, &fuzzy_hash(&unrelated)), 0);
+    }
+
+    #[test]
+    fn test_hashes_md5_sha1_sha256() {
+        let path = std::env::temp_dir().join("sodiic_hash_test.txt");
+        std::fs::write(&path, "abc").unwrap();
+        let report = hash_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(report.md5, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(report.sha1, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            report.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
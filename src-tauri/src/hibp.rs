@@ -0,0 +1,423 @@
+// src-tauri/src/hibp.rs
+//
+// Filtraciones de Have I Been Pwned (API v3).
+//
+// Flujo:
+//   1. Cuentas (email o usuario): `/breachedaccount` con `truncateResponse=false` para
+//      tener fecha, clases de datos y marcas de verificación/sensibilidad de cada brecha.
+//      Para emails además `/pasteaccount` (publicaciones en sitios de pegado).
+//   2. Dominios: `/breacheddomain` devuelve alias → brechas, sólo para dominios verificados
+//      en la cuenta de HIBP. El detalle de cada brecha sale del catálogo público `/breaches`.
+//   3. El registro en el caso guarda un resumen en el objetivo y cada brecha o publicación
+//      como evento fechado (cronología) en el Email y en las personas con ese email.
+
+use crate::cases::{CaseManager, RelatedTarget, TargetType, TimelineEvent};
+use crate::models::OsintConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const API_URL: &str = "https://haveibeenpwned.com/api/v3";
+/// Máximo de cuentas de un dominio que se crean como objetivos
+const MAX_DOMAIN_ACCOUNTS: usize = 100;
+
+/// Brecha tal como la describe HIBP (campos en PascalCase).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Breach {
+    pub name: String,
+    pub title: String,
+    pub domain: String,
+    pub breach_date: String,
+    pub added_date: String,
+    pub pwn_count: u64,
+    pub data_classes: Vec<String>,
+    pub is_verified: bool,
+    pub is_sensitive: bool,
+    pub is_fabricated: bool,
+    pub is_spam_list: bool,
+}
+
+impl Breach {
+    /// "Adobe (2013-10-04): Email addresses, Passwords [no verificada]"
+    fn summary(&self) -> String {
+        let mut line = format!(
+            "{} ({}): {}",
+            self.title,
+            self.breach_date,
+            self.data_classes.join(", ")
+        );
+        let flags: Vec<&str> = [
+            (!self.is_verified, "no verificada"),
+            (self.is_sensitive, "sensible"),
+            (self.is_fabricated, "fabricada"),
+            (self.is_spam_list, "lista de spam"),
+        ]
+        .into_iter()
+        .filter_map(|(on, label)| on.then_some(label))
+        .collect();
+        if !flags.is_empty() {
+            line.push_str(&format!(" [{}]", flags.join(", ")));
+        }
+        line
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct Paste {
+    pub source: String,
+    pub id: String,
+    pub title: Option<String>,
+    pub date: Option<String>,
+    pub email_count: u64,
+}
+
+impl Paste {
+    fn label(&self) -> String {
+        match &self.title {
+            Some(title) if !title.is_empty() => format!("{} {} ({})", self.source, self.id, title),
+            _ => format!("{} {}", self.source, self.id),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AccountReport {
+    pub account: String,
+    pub breaches: Vec<Breach>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pastes: Vec<Paste>,
+    /// Consultas parciales que fallaron (pastes sin acceso, cuota...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DomainReport {
+    pub domain: String,
+    /// Alias de la cuenta (lo que va antes de la @) → nombres de las brechas
+    pub accounts: BTreeMap<String, Vec<String>>,
+    /// Detalle de las brechas que aparecen en `accounts`
+    pub breaches: Vec<Breach>,
+}
+
+impl AccountReport {
+    fn is_email(&self) -> bool {
+        self.account.contains('@')
+    }
+
+    /// Resumen para `detalles_tecnicos.filtraciones`.
+    pub fn case_details(&self) -> serde_json::Value {
+        serde_json::json!({
+            "brechas": self.breaches.iter().map(Breach::summary).collect::<Vec<_>>(),
+            "pastes": self.pastes.len(),
+        })
+    }
+}
+
+impl DomainReport {
+    fn email(&self, alias: &str) -> String {
+        format!("{}@{}", alias, self.domain)
+    }
+
+    /// Las cuentas filtradas del dominio como Emails vinculados al Dominio.
+    pub fn related_targets(&self) -> Vec<RelatedTarget> {
+        self.accounts
+            .keys()
+            .take(MAX_DOMAIN_ACCOUNTS)
+            .map(|alias| RelatedTarget {
+                source: self.domain.clone(),
+                source_type: TargetType::Domain,
+                name: self.email(alias),
+                target_type: TargetType::Email,
+                relation: "cuenta_filtrada".to_string(),
+            })
+            .collect()
+    }
+}
+
+fn breach_event(subject_id: &str, subject_name: &str, breach: &Breach) -> TimelineEvent {
+    TimelineEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        subject_id: subject_id.to_string(),
+        subject_name: subject_name.to_string(),
+        date: Some(breach.breach_date.clone()).filter(|d| !d.is_empty()),
+        kind: "filtracion".to_string(),
+        title: format!("Filtración {}", breach.title),
+        details: breach.summary(),
+        source: "hibp".to_string(),
+    }
+}
+
+fn paste_event(subject_id: &str, subject_name: &str, paste: &Paste) -> TimelineEvent {
+    TimelineEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        subject_id: subject_id.to_string(),
+        subject_name: subject_name.to_string(),
+        date: paste.date.as_ref().map(|d| d.chars().take(10).collect()),
+        kind: "paste".to_string(),
+        title: format!("Publicado en {}", paste.label()),
+        details: format!("{} emails en la publicación", paste.email_count),
+        source: "hibp".to_string(),
+    }
+}
+
+/// Guarda el resumen en el Email/Usuario y cada brecha y publicación como evento fechado,
+/// también en las personas del caso que tengan ese email. Devuelve los eventos nuevos.
+pub fn register(
+    case_manager: &CaseManager,
+    case_name: &str,
+    report: &AccountReport,
+) -> Result<usize, String> {
+    let target_type = if report.is_email() {
+        TargetType::Email
+    } else {
+        TargetType::Username
+    };
+    // La cuenta llega en minúsculas: se respeta el nombre con el que ya estaba en el caso
+    let name = case_manager
+        .find_target_by_name(case_name, &report.account)?
+        .map(|t| t.name)
+        .unwrap_or_else(|| report.account.clone());
+    let target_id = case_manager.save_tool_details(
+        case_name,
+        &name,
+        target_type,
+        "filtraciones",
+        report.case_details(),
+    )?;
+
+    let mut subjects = vec![(target_id, name)];
+    if report.is_email() {
+        for person in case_manager.get_persons(case_name)? {
+            if person
+                .email
+                .as_deref()
+                .is_some_and(|e| e.trim().to_lowercase() == report.account)
+            {
+                let name = format!(
+                    "{} {}",
+                    person.first_name.as_deref().unwrap_or(""),
+                    person.last_name.as_deref().unwrap_or("")
+                )
+                .trim()
+                .to_string();
+                subjects.push((person.id, name));
+            }
+        }
+    }
+
+    let mut added = 0;
+    for (subject_id, subject_name) in &subjects {
+        let events = report
+            .breaches
+            .iter()
+            .map(|b| breach_event(subject_id, subject_name, b))
+            .chain(
+                report
+                    .pastes
+                    .iter()
+                    .map(|p| paste_event(subject_id, subject_name, p)),
+            );
+        for event in events {
+            if case_manager.add_event(case_name, &event)? {
+                added += 1;
+            }
+        }
+    }
+    Ok(added)
+}
+
+/// Guarda el resumen en el Dominio, crea los Emails filtrados y sus eventos.
+pub fn register_domain(
+    case_manager: &CaseManager,
+    case_name: &str,
+    report: &DomainReport,
+) -> Result<usize, String> {
+    case_manager.save_tool_details(
+        case_name,
+        &report.domain,
+        TargetType::Domain,
+        "filtraciones_dominio",
+        serde_json::json!({
+            "cuentas": report.accounts.len(),
+            "brechas": report.breaches.iter().map(Breach::summary).collect::<Vec<_>>(),
+        }),
+    )?;
+    case_manager.link_related(case_name, &report.related_targets())?;
+
+    let mut added = 0;
+    for (alias, names) in report.accounts.iter().take(MAX_DOMAIN_ACCOUNTS) {
+        let email = report.email(alias);
+        let email_id = case_manager.ensure_target(case_name, &email, TargetType::Email)?;
+        for breach in report.breaches.iter().filter(|b| names.contains(&b.name)) {
+            if case_manager.add_event(case_name, &breach_event(&email_id, &email, breach))? {
+                added += 1;
+            }
+        }
+    }
+    Ok(added)
+}
+
+/// GET a la API. `Ok(None)` si HIBP no tiene datos (404).
+async fn fetch(path: &str, config: &OsintConfig) -> Result<Option<serde_json::Value>, String> {
+    let client = crate::tools::get_http_client(config).await;
+    let response = client
+        .get(format!("{}/{}", API_URL, path))
+        .header("hibp-api-key", config.hibp_api_key.trim())
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("ERROR [hibp]: API Request failure: {}", e);
+            "No se pudo completar la búsqueda de filtraciones en la base de datos externa."
+                .to_string()
+        })?;
+
+    match response.status().as_u16() {
+        404 => Ok(None),
+        401 => Err("HIBP rechazó la API key configurada.".to_string()),
+        403 => Err(
+            "HIBP denegó el acceso (dominio no verificado o plan sin esta consulta).".to_string(),
+        ),
+        429 => {
+            let wait = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| format!(" en {} s", s))
+                .unwrap_or_default();
+            Err(format!(
+                "Límite de consultas de HIBP alcanzado; reintentar{}.",
+                wait
+            ))
+        }
+        200 => response.json().await.map(Some).map_err(|e| {
+            eprintln!("ERROR [hibp]: Invalid response: {}", e);
+            "Respuesta de HIBP ilegible.".to_string()
+        }),
+        status => Err(format!("HIBP API Error: {}", status)),
+    }
+}
+
+fn parse_list<T: for<'de> Deserialize<'de>>(body: Option<serde_json::Value>) -> Vec<T> {
+    body.and_then(|b| serde_json::from_value(b).ok())
+        .unwrap_or_default()
+}
+
+fn require_key(config: &OsintConfig) -> Result<(), String> {
+    if config.hibp_api_key.trim().is_empty() {
+        return Err("API Key de HIBP no configurada. Esta herramienta requiere una llave válida para funcionar. No se realizarán simulaciones para garantizar la integridad del informe.".to_string());
+    }
+    Ok(())
+}
+
+/// Brechas (con detalle) y, para emails, publicaciones en sitios de pegado.
+pub async fn lookup_account(account: &str, config: &OsintConfig) -> Result<AccountReport, String> {
+    require_key(config)?;
+    let account = account.trim().to_lowercase();
+    let encoded = urlencoding::encode(&account);
+
+    let mut breaches: Vec<Breach> = parse_list(
+        fetch(
+            &format!("breachedaccount/{}?truncateResponse=false", encoded),
+            config,
+        )
+        .await?,
+    );
+    breaches.sort_by(|a, b| a.breach_date.cmp(&b.breach_date));
+
+    let mut report = AccountReport {
+        account: account.clone(),
+        breaches,
+        ..Default::default()
+    };
+    if report.is_email() {
+        match fetch(&format!("pasteaccount/{}", encoded), config).await {
+            Ok(body) => report.pastes = parse_list(body),
+            Err(e) => report.notes.push(format!("Pastes: {}", e)),
+        }
+    }
+    Ok(report)
+}
+
+/// Cuentas filtradas de un dominio verificado en la cuenta de HIBP.
+pub async fn lookup_domain(domain: &str, config: &OsintConfig) -> Result<DomainReport, String> {
+    require_key(config)?;
+    let domain = domain.trim().trim_start_matches("www.").to_lowercase();
+
+    let accounts: BTreeMap<String, Vec<String>> =
+        fetch(&format!("breacheddomain/{}", domain), config)
+            .await?
+            .and_then(|b| serde_json::from_value(b).ok())
+            .unwrap_or_default();
+
+    let mut breaches = Vec::new();
+    if !accounts.is_empty() {
+        let catalog: Vec<Breach> = parse_list(fetch("breaches", config).await?);
+        breaches = catalog
+            .into_iter()
+            .filter(|b| accounts.values().any(|names| names.contains(&b.name)))
+            .collect();
+        breaches.sort_by(|a, b| a.breach_date.cmp(&b.breach_date));
+    }
+    Ok(DomainReport {
+        domain,
+        accounts,
+        breaches,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_brechas_completas_y_pastes() {
+        let breaches: Vec<Breach> = parse_list(Some(serde_json::json!([{
+            "Name": "Adobe", "Title": "Adobe", "Domain": "adobe.com",
+            "BreachDate": "2013-10-04", "AddedDate": "2013-12-04T00:00:00Z",
+            "PwnCount": 152445165, "DataClasses": ["Email addresses", "Passwords"],
+            "IsVerified": true, "IsSensitive": false, "IsFabricated": false, "IsSpamList": false
+        }, {
+            "Name": "Dudosa", "Title": "Dudosa", "BreachDate": "2020-01-01",
+            "DataClasses": ["Email addresses"], "IsVerified": false, "IsSensitive": true
+        }])));
+        assert_eq!(breaches.len(), 2);
+        assert_eq!(breaches[0].pwn_count, 152445165);
+        assert_eq!(
+            breaches[0].summary(),
+            "Adobe (2013-10-04): Email addresses, Passwords"
+        );
+        assert_eq!(
+            breaches[1].summary(),
+            "Dudosa (2020-01-01): Email addresses [no verificada, sensible]"
+        );
+
+        let pastes: Vec<Paste> = parse_list(Some(serde_json::json!([
+            {"Source": "Pastebin", "Id": "8Q0BvKD8", "Title": "syslog", "Date": "2014-03-04T19:14:54Z", "EmailCount": 139}
+        ])));
+        let event = paste_event("id", "ana@ejemplo.com", &pastes[0]);
+        assert_eq!(event.date.as_deref(), Some("2014-03-04"));
+        assert_eq!(event.title, "Publicado en Pastebin 8Q0BvKD8 (syslog)");
+        assert!(parse_list::<Paste>(None).is_empty());
+    }
+
+    #[test]
+    fn test_dominio_genera_emails_vinculados() {
+        let report = DomainReport {
+            domain: "ejemplo.com".into(),
+            accounts: serde_json::from_value(serde_json::json!({
+                "ana": ["Adobe"],
+                "juan.perez": ["Adobe", "LinkedIn"]
+            }))
+            .unwrap(),
+            breaches: Vec::new(),
+        };
+        let related = report.related_targets();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].name, "ana@ejemplo.com");
+        assert_eq!(related[1].name, "juan.perez@ejemplo.com");
+        assert_eq!(related[1].target_type, TargetType::Email);
+        assert_eq!(related[1].relation, "cuenta_filtrada");
+    }
+}
@@ -0,0 +1,495 @@
+// src-tauri/src/hunter.rs
+//
+// Emails corporativos vía Hunter.io (API v2), por el cliente HTTP de la app (respeta el
+// proxy/Tor) en lugar del `fetch` del navegador.
+//
+// Flujo:
+//   1. Búsqueda por dominio (`/domain-search`): emails públicos con tipo (personal/genérico),
+//      confianza, nombre, cargo, redes y las páginas donde aparecieron. Incluye el patrón de
+//      la organización (ej: `{first}.{last}`). El límite de resultados depende del plan.
+//   2. Buscador (`/email-finder`): el email más probable de nombre + apellido en un dominio.
+//      Si Hunter no lo conoce y se pide, el patrón del dominio aplicado al nombre queda como
+//      candidato; cuesta una búsqueda por dominio más de la cuota.
+//   3. El registro en el caso crea cada Email vinculado al Dominio con sus fuentes, y una
+//      Persona con nombre, email y cargo para los emails personales con titular conocido.
+
+use crate::cases::{CaseManager, RelatedTarget, TargetType};
+use crate::models::{Job, OsintConfig, Person, SocialProfile};
+use serde::{Deserialize, Serialize};
+
+const API_URL: &str = "https://api.hunter.io/v2";
+/// Emails pedidos por búsqueda de dominio si no se indica otro: lo que admite el plan gratuito
+/// (uno mayor responde `pagination_error`)
+const DOMAIN_SEARCH_LIMIT: u32 = 10;
+/// Máximo de la API para los planes pagos
+const MAX_DOMAIN_SEARCH_LIMIT: u32 = 100;
+/// Fuentes guardadas por email en el caso
+const MAX_SOURCES: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HunterSource {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extracted_on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seen_on: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct HunterEmail {
+    pub email: String,
+    /// "personal" o "generic"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub confidence: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linkedin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twitter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<HunterSource>,
+}
+
+impl HunterEmail {
+    fn full_name(&self) -> Option<String> {
+        let name = format!(
+            "{} {}",
+            self.first_name.as_deref().unwrap_or(""),
+            self.last_name.as_deref().unwrap_or("")
+        )
+        .trim()
+        .to_string();
+        Some(name).filter(|n| !n.is_empty())
+    }
+
+    /// Resumen para `detalles_tecnicos.hunter` del Email.
+    fn case_details(&self) -> serde_json::Value {
+        let mut details = serde_json::json!({
+            "confianza": self.confidence,
+            "fuentes": self.sources.iter().take(MAX_SOURCES).map(|s| &s.uri).collect::<Vec<_>>(),
+        });
+        for (key, value) in [
+            ("tipo", &self.kind),
+            ("titular", &self.full_name()),
+            ("cargo", &self.position),
+            ("area", &self.department),
+        ] {
+            if let Some(value) = value {
+                details[key] = serde_json::json!(value);
+            }
+        }
+        details
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DomainEmails {
+    pub domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    /// Patrón de los emails de la organización (ej: "{first}.{last}")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// El servidor acepta cualquier destinatario: la verificación no es concluyente
+    pub accept_all: bool,
+    pub webmail: bool,
+    /// Emails que Hunter conoce del dominio (puede superar los devueltos)
+    pub total: u32,
+    pub emails: Vec<HunterEmail>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct EmailFinderResult {
+    pub domain: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<HunterEmail>,
+    /// Si Hunter no encontró el email: el patrón del dominio aplicado al nombre
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_guess: Option<String>,
+}
+
+/// Minúsculas ASCII sin tildes ni separadores, como se escriben las partes de un email.
+fn email_part(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            'á' | 'à' | 'ä' | 'â' => Some('a'),
+            'é' | 'è' | 'ë' | 'ê' => Some('e'),
+            'í' | 'ì' | 'ï' | 'î' => Some('i'),
+            'ó' | 'ò' | 'ö' | 'ô' => Some('o'),
+            'ú' | 'ù' | 'ü' | 'û' => Some('u'),
+            'ñ' => Some('n'),
+            'ç' => Some('c'),
+            c if c.is_ascii_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Aplica un patrón de Hunter (`{first}`, `{last}`, `{f}`, `{l}`) a un nombre.
+pub fn apply_pattern(pattern: &str, first: &str, last: &str, domain: &str) -> Option<String> {
+    let first = email_part(first);
+    let last = email_part(last);
+    let needs_last = pattern.contains("{last}") || pattern.contains("{l}");
+    if first.is_empty() || (needs_last && last.is_empty()) {
+        return None;
+    }
+    let local = pattern
+        .replace("{first}", &first)
+        .replace("{last}", &last)
+        .replace("{f}", &first[..1])
+        .replace("{l}", last.get(..1).unwrap_or_default());
+    if local.contains('{') {
+        return None;
+    }
+    Some(format!("{}@{}", local, domain.trim().to_lowercase()))
+}
+
+fn text(value: &serde_json::Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn parse_email(item: &serde_json::Value) -> Option<HunterEmail> {
+    Some(HunterEmail {
+        email: text(&item["value"])
+            .or_else(|| text(&item["email"]))?
+            .to_lowercase(),
+        kind: text(&item["type"]),
+        confidence: item["confidence"]
+            .as_u64()
+            .or_else(|| item["score"].as_u64())
+            .unwrap_or(0) as u32,
+        first_name: text(&item["first_name"]),
+        last_name: text(&item["last_name"]),
+        position: text(&item["position"]),
+        department: text(&item["department"]),
+        linkedin: text(&item["linkedin"]).or_else(|| text(&item["linkedin_url"])),
+        twitter: text(&item["twitter"]),
+        phone: text(&item["phone_number"]),
+        sources: item["sources"]
+            .as_array()
+            .map(|sources| {
+                sources
+                    .iter()
+                    .filter_map(|s| {
+                        Some(HunterSource {
+                            uri: text(&s["uri"])?,
+                            extracted_on: text(&s["extracted_on"]),
+                            last_seen_on: text(&s["last_seen_on"]),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+fn parse_domain_search(body: &serde_json::Value) -> DomainEmails {
+    let data = &body["data"];
+    let mut emails: Vec<HunterEmail> = data["emails"]
+        .as_array()
+        .map(|items| items.iter().filter_map(parse_email).collect())
+        .unwrap_or_default();
+    emails.sort_by_key(|e| std::cmp::Reverse(e.confidence));
+    DomainEmails {
+        domain: text(&data["domain"]).unwrap_or_default(),
+        organization: text(&data["organization"]),
+        pattern: text(&data["pattern"]),
+        accept_all: data["accept_all"].as_bool().unwrap_or(false),
+        webmail: data["webmail"].as_bool().unwrap_or(false),
+        total: body["meta"]["results"]
+            .as_u64()
+            .map(|n| n as u32)
+            .unwrap_or(emails.len() as u32),
+        emails,
+    }
+}
+
+/// GET a la API con los errores de Hunter (`{"errors": [{"details": ...}]}`) traducidos.
+async fn fetch(
+    endpoint: &str,
+    query: &[(&str, &str)],
+    config: &OsintConfig,
+) -> Result<serde_json::Value, String> {
+    if config.hunter_io.trim().is_empty() {
+        return Err("API Key de Hunter.io no configurada en ajustes.".to_string());
+    }
+    let client = crate::tools::get_http_client(config).await;
+    let response = client
+        .get(format!("{}/{}", API_URL, endpoint))
+        .query(query)
+        .query(&[("api_key", config.hunter_io.trim())])
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("ERROR [hunter]: API request failed: {}", e);
+            "No se pudo conectar con Hunter.io.".to_string()
+        })?;
+    let status = response.status().as_u16();
+    // Los errores de cuota pueden venir sin cuerpo JSON
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+
+    match status {
+        200 if body.is_null() => Err("Respuesta de Hunter.io ilegible.".to_string()),
+        200 => Ok(body),
+        401 => Err("Hunter.io rechazó la API key configurada.".to_string()),
+        429 => Err("Límite de consultas de Hunter.io alcanzado; reintentar más tarde.".to_string()),
+        _ if body["errors"][0]["id"] == "pagination_error" => Err(
+            "El plan de Hunter.io no admite tantos resultados por búsqueda; indicar un límite menor (el plan gratuito permite 10).".to_string(),
+        ),
+        _ => {
+            let details = body["errors"][0]["details"]
+                .as_str()
+                .unwrap_or("error desconocido");
+            eprintln!("ERROR [hunter]: HTTP {}: {}", status, details);
+            Err(format!("Hunter.io: {}", details))
+        }
+    }
+}
+
+/// Emails públicos de un dominio y el patrón de la organización. `limit` se acota a
+/// 1-`MAX_DOMAIN_SEARCH_LIMIT`; sin él se piden `DOMAIN_SEARCH_LIMIT`.
+pub async fn domain_search(
+    domain: &str,
+    limit: Option<u32>,
+    config: &OsintConfig,
+) -> Result<DomainEmails, String> {
+    let domain = domain.trim().trim_start_matches("www.").to_lowercase();
+    let limit = limit
+        .unwrap_or(DOMAIN_SEARCH_LIMIT)
+        .clamp(1, MAX_DOMAIN_SEARCH_LIMIT)
+        .to_string();
+    let body = fetch(
+        "domain-search",
+        &[("domain", domain.as_str()), ("limit", limit.as_str())],
+        config,
+    )
+    .await?;
+    let mut report = parse_domain_search(&body);
+    if report.domain.is_empty() {
+        report.domain = domain;
+    }
+    Ok(report)
+}
+
+/// Email más probable de una persona en un dominio. Con `with_pattern`, si Hunter no lo
+/// conoce se hace una búsqueda por dominio más para proponer el patrón aplicado al nombre.
+pub async fn find_email(
+    domain: &str,
+    first_name: &str,
+    last_name: &str,
+    with_pattern: bool,
+    config: &OsintConfig,
+) -> Result<EmailFinderResult, String> {
+    let domain = domain.trim().trim_start_matches("www.").to_lowercase();
+    let body = fetch(
+        "email-finder",
+        &[
+            ("domain", domain.as_str()),
+            ("first_name", first_name.trim()),
+            ("last_name", last_name.trim()),
+        ],
+        config,
+    )
+    .await?;
+    let data = &body["data"];
+    let mut result = EmailFinderResult {
+        organization: text(&data["company"]),
+        email: parse_email(data),
+        domain,
+        pattern: None,
+        pattern_guess: None,
+    };
+    if result.email.is_none() && with_pattern {
+        // Una búsqueda mínima alcanza para conocer el patrón de la organización
+        if let Ok(search) = domain_search(&result.domain, Some(1), config).await {
+            result.pattern_guess = search
+                .pattern
+                .as_deref()
+                .and_then(|p| apply_pattern(p, first_name, last_name, &result.domain));
+            result.pattern = search.pattern;
+        }
+    }
+    Ok(result)
+}
+
+/// Crea los Emails vinculados al Dominio con sus fuentes y las Personas de los emails
+/// personales (nombre, email, cargo y redes). Devuelve (emails, personas nuevas).
+pub fn register(
+    case_manager: &CaseManager,
+    case_name: &str,
+    domain: &str,
+    organization: Option<&str>,
+    emails: &[HunterEmail],
+) -> Result<(usize, usize), String> {
+    let related: Vec<RelatedTarget> = emails
+        .iter()
+        .map(|e| RelatedTarget {
+            source: domain.to_string(),
+            source_type: TargetType::Domain,
+            name: e.email.clone(),
+            target_type: TargetType::Email,
+            relation: "email_del_dominio".to_string(),
+        })
+        .collect();
+    case_manager.link_related(case_name, &related)?;
+
+    let mut known_emails: Vec<String> = case_manager
+        .get_persons(case_name)?
+        .into_iter()
+        .filter_map(|p| p.email.map(|e| e.trim().to_lowercase()))
+        .collect();
+    let mut persons = 0;
+    for email in emails {
+        case_manager.save_tool_details(
+            case_name,
+            &email.email,
+            TargetType::Email,
+            "hunter",
+            email.case_details(),
+        )?;
+
+        if email.kind.as_deref() == Some("generic")
+            || email.full_name().is_none()
+            || known_emails.contains(&email.email)
+        {
+            continue;
+        }
+        let mut social_profiles = Vec::new();
+        if let Some(url) = &email.linkedin {
+            social_profiles.push(SocialProfile {
+                id: None,
+                platform: "LinkedIn".to_string(),
+                username: url
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                url: url.clone(),
+            });
+        }
+        if let Some(handle) = &email.twitter {
+            social_profiles.push(SocialProfile {
+                id: None,
+                platform: "Twitter".to_string(),
+                username: handle.trim_start_matches('@').to_string(),
+                url: format!("https://x.com/{}", handle.trim_start_matches('@')),
+            });
+        }
+        case_manager.create_person(
+            case_name,
+            Person {
+                id: uuid::Uuid::new_v4().to_string(),
+                first_name: email.first_name.clone(),
+                last_name: email.last_name.clone(),
+                nicknames: Vec::new(),
+                dni: None,
+                birth_date: None,
+                phone: email.phone.clone(),
+                email: Some(email.email.clone()),
+                addresses: Vec::new(),
+                jobs: email
+                    .position
+                    .iter()
+                    .map(|title| Job {
+                        id: None,
+                        title: title.clone(),
+                        company: organization.unwrap_or(domain).to_string(),
+                        date_start: None,
+                        date_end: None,
+                    })
+                    .collect(),
+                social_profiles,
+                created_at: chrono::Utc::now().to_rfc3339(),
+            },
+        )?;
+        known_emails.push(email.email.clone());
+        persons += 1;
+    }
+    Ok((emails.len(), persons))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_busqueda_por_dominio() {
+        let body = serde_json::json!({
+            "data": {
+                "domain": "ejemplo.com", "organization": "Ejemplo SA", "pattern": "{first}.{last}",
+                "accept_all": false, "webmail": false,
+                "emails": [
+                    {"value": "info@ejemplo.com", "type": "generic", "confidence": 70, "sources": []},
+                    {
+                        "value": "Ana.Perez@ejemplo.com", "type": "personal", "confidence": 94,
+                        "first_name": "Ana", "last_name": "Pérez", "position": "CTO",
+                        "linkedin": "https://www.linkedin.com/in/anaperez", "twitter": null,
+                        "sources": [{"domain": "blog.ejemplo.com", "uri": "http://blog.ejemplo.com/equipo",
+                                     "extracted_on": "2023-01-10", "last_seen_on": "2024-02-01", "still_on_page": true}]
+                    }
+                ]
+            },
+            "meta": {"results": 12, "limit": 100, "offset": 0}
+        });
+        let report = parse_domain_search(&body);
+        assert_eq!(report.pattern.as_deref(), Some("{first}.{last}"));
+        assert_eq!(report.total, 12);
+        assert_eq!(report.emails[0].email, "ana.perez@ejemplo.com");
+        assert_eq!(report.emails[0].position.as_deref(), Some("CTO"));
+        assert_eq!(report.emails[0].twitter, None);
+        assert_eq!(
+            report.emails[0].sources[0].uri,
+            "http://blog.ejemplo.com/equipo"
+        );
+        assert_eq!(report.emails[1].kind.as_deref(), Some("generic"));
+
+        let details = report.emails[0].case_details();
+        assert_eq!(details["titular"], "Ana Pérez");
+        assert_eq!(details["fuentes"][0], "http://blog.ejemplo.com/equipo");
+    }
+
+    #[test]
+    fn test_aplica_patron_de_email() {
+        assert_eq!(
+            apply_pattern("{first}.{last}", "José", "Núñez", "Ejemplo.com").as_deref(),
+            Some("jose.nunez@ejemplo.com")
+        );
+        assert_eq!(
+            apply_pattern("{f}{last}", "Ana", "de la Torre", "ejemplo.com").as_deref(),
+            Some("adelatorre@ejemplo.com")
+        );
+        assert_eq!(
+            apply_pattern("{first}", "Ana", "", "ejemplo.com").as_deref(),
+            Some("ana@ejemplo.com")
+        );
+        assert_eq!(
+            apply_pattern("{first}.{last}", "Ana", "", "ejemplo.com"),
+            None
+        );
+        assert_eq!(
+            apply_pattern("{first}{middle}", "Ana", "Gil", "ejemplo.com"),
+            None
+        );
+    }
+}
@@ -0,0 +1,316 @@
+// src-tauri/src/imagehash.rs
+//
+// Hashes perceptuales de imágenes para encontrar casi-duplicados dentro de un caso.
+//
+// Flujo:
+//   1. La imagen (archivo local o URL) se decodifica y se pasa a escala de grises.
+//   2. Se calculan tres hashes de 64 bits:
+//        - aHash: cada píxel de una miniatura 8x8 contra el promedio.
+//        - dHash: gradiente horizontal en una miniatura 9x8.
+//        - pHash: frecuencias bajas de la DCT de una miniatura 32x32 contra su mediana.
+//      Sobreviven a recompresión, cambio de tamaño y ajustes leves de color, que es lo que
+//      hacen las plataformas con una foto de perfil.
+//   3. Dos imágenes se comparan por distancia de Hamming; el pHash decide si son la misma foto.
+//   4. Se guarda una copia en la carpeta del caso: las fotos de perfil cambian o desaparecen.
+
+use crate::models::OsintConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Distancia máxima de pHash (sobre 64 bits) para considerar dos imágenes la misma foto
+pub const PHASH_THRESHOLD: u32 = 10;
+/// Tamaño máximo de imagen a descargar
+pub const MAX_IMAGE_BYTES: usize = 20 * 1024 * 1024;
+
+const DCT_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+/// Hashes de una imagen en hexadecimal (16 dígitos cada uno).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImageHashes {
+    pub ahash: String,
+    pub dhash: String,
+    pub phash: String,
+}
+
+impl ImageHashes {
+    /// Forma compacta "ahash:dhash:phash" para guardar como atributo.
+    pub fn to_attribute(&self) -> String {
+        format!("{}:{}:{}", self.ahash, self.dhash, self.phash)
+    }
+
+    pub fn from_attribute(value: &str) -> Option<Self> {
+        let mut parts = value.split(':');
+        let hashes = ImageHashes {
+            ahash: parts.next()?.to_string(),
+            dhash: parts.next()?.to_string(),
+            phash: parts.next()?.to_string(),
+        };
+        parts.next().is_none().then_some(hashes)
+    }
+}
+
+/// Resultado del análisis, listo para `detalles_tecnicos.imagen` del objetivo File.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageReport {
+    /// Ruta o URL de origen
+    pub source: String,
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub sha256: String,
+    pub hashes: ImageHashes,
+    /// Copia guardada en la carpeta del caso
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored_copy: Option<String>,
+}
+
+/// Comparación entre dos imágenes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ImageMatch {
+    pub ahash_distance: u32,
+    pub dhash_distance: u32,
+    pub phash_distance: u32,
+    /// Similitud 0-100 promediando los tres hashes
+    pub similarity: u32,
+    pub near_duplicate: bool,
+}
+
+fn bits_to_hex(bits: impl Iterator<Item = bool>) -> String {
+    let value = bits.fold(0u64, |acc, bit| (acc << 1) | bit as u64);
+    format!("{:016x}", value)
+}
+
+fn thumbnail(image: &image::DynamicImage, width: u32, height: u32) -> Vec<f64> {
+    image
+        .resize_exact(width, height, image::imageops::FilterType::Lanczos3)
+        .to_luma8()
+        .pixels()
+        .map(|p| p.0[0] as f64)
+        .collect()
+}
+
+fn average_hash(image: &image::DynamicImage) -> String {
+    let pixels = thumbnail(image, HASH_SIZE as u32, HASH_SIZE as u32);
+    let mean = pixels.iter().sum::<f64>() / pixels.len() as f64;
+    bits_to_hex(pixels.iter().map(|&p| p > mean))
+}
+
+fn difference_hash(image: &image::DynamicImage) -> String {
+    let width = HASH_SIZE + 1;
+    let pixels = thumbnail(image, width as u32, HASH_SIZE as u32);
+    bits_to_hex(
+        (0..HASH_SIZE)
+            .flat_map(|row| (0..HASH_SIZE).map(move |col| (row, col)))
+            .map(|(row, col)| pixels[row * width + col + 1] > pixels[row * width + col]),
+    )
+}
+
+/// DCT-II unidimensional (la imagen es chica: no hace falta la versión rápida).
+fn dct_1d(input: &[f64]) -> Vec<f64> {
+    let n = input.len() as f64;
+    (0..input.len())
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .map(|(i, &x)| x * (std::f64::consts::PI / n * (i as f64 + 0.5) * k as f64).cos())
+                .sum()
+        })
+        .collect()
+}
+
+fn perceptual_hash(image: &image::DynamicImage) -> String {
+    let pixels = thumbnail(image, DCT_SIZE as u32, DCT_SIZE as u32);
+    // DCT 2D separable: primero filas, después columnas
+    let rows: Vec<Vec<f64>> = pixels.chunks(DCT_SIZE).map(dct_1d).collect();
+    let low_freq: Vec<f64> = (0..HASH_SIZE)
+        .map(|col| dct_1d(&rows.iter().map(|r| r[col]).collect::<Vec<_>>()))
+        .flat_map(|column| column.into_iter().take(HASH_SIZE).collect::<Vec<_>>())
+        .collect();
+    // `low_freq` quedó por columnas: se reordena por filas para que el bit i sea (fila, col)
+    let block: Vec<f64> = (0..HASH_SIZE)
+        .flat_map(|row| (0..HASH_SIZE).map(move |col| (row, col)))
+        .map(|(row, col)| low_freq[col * HASH_SIZE + row])
+        .collect();
+
+    let mut sorted = block.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = (sorted[HASH_SIZE * HASH_SIZE / 2 - 1] + sorted[HASH_SIZE * HASH_SIZE / 2]) / 2.0;
+    bits_to_hex(block.iter().map(|&c| c > median))
+}
+
+/// Calcula los tres hashes de una imagen ya decodificada.
+pub fn compute(image: &image::DynamicImage) -> ImageHashes {
+    ImageHashes {
+        ahash: average_hash(image),
+        dhash: difference_hash(image),
+        phash: perceptual_hash(image),
+    }
+}
+
+/// Decodifica la imagen y arma el reporte.
+pub fn analyze_bytes(source: &str, bytes: &[u8]) -> Result<ImageReport, String> {
+    let format = image::guess_format(bytes).map_err(|_| {
+        "El archivo no es una imagen soportada (JPEG, PNG, GIF o WebP).".to_string()
+    })?;
+    let decoded = image::load_from_memory_with_format(bytes, format).map_err(|e| {
+        eprintln!("ERROR [imagehash]: Decode failure for {}: {}", source, e);
+        "No se pudo decodificar la imagen.".to_string()
+    })?;
+    Ok(ImageReport {
+        source: source.to_string(),
+        format: format!("{:?}", format).to_lowercase(),
+        width: decoded.width(),
+        height: decoded.height(),
+        sha256: format!("{:x}", Sha256::digest(bytes)),
+        hashes: compute(&decoded),
+        stored_copy: None,
+    })
+}
+
+/// Descarga (URL) o lee (ruta local) la imagen, respetando el tamaño máximo.
+pub async fn fetch(source: &str, config: &OsintConfig) -> Result<Vec<u8>, String> {
+    if !source.starts_with("http://") && !source.starts_with("https://") {
+        let bytes = tokio::fs::read(source).await.map_err(|e| {
+            eprintln!("ERROR [imagehash]: Failed to open file: {} - {}", source, e);
+            "No se pudo abrir el archivo especificado.".to_string()
+        })?;
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err("La imagen supera el tamaño máximo (20 MB).".to_string());
+        }
+        return Ok(bytes);
+    }
+
+    let client = crate::tools::get_http_client(config).await;
+    let mut response = client.get(source).send().await.map_err(|e| {
+        eprintln!("ERROR [imagehash]: Download failed for {}: {}", source, e);
+        "No se pudo descargar la imagen.".to_string()
+    })?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "La descarga de la imagen devolvió el código {}.",
+            response.status().as_u16()
+        ));
+    }
+    let mut bytes = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err("La imagen supera el tamaño máximo (20 MB).".to_string());
+        }
+    }
+    Ok(bytes)
+}
+
+/// Obtiene la imagen, calcula sus hashes y, con `store_dir`, guarda una copia nombrada por
+/// su SHA-256 (la misma imagen agregada dos veces no se duplica).
+pub async fn analyze(
+    source: &str,
+    store_dir: Option<&Path>,
+    config: &OsintConfig,
+) -> Result<ImageReport, String> {
+    let source = source.trim().to_string();
+    let bytes = fetch(&source, config).await?;
+    let (mut report, bytes) = tokio::task::spawn_blocking(move || {
+        analyze_bytes(&source, &bytes).map(|report| (report, bytes))
+    })
+    .await
+    .map_err(|e| format!("Error interno al procesar la imagen: {}", e))??;
+
+    if let Some(dir) = store_dir {
+        let path = dir.join(format!("{}.{}", report.sha256, report.format));
+        let stored = tokio::fs::create_dir_all(dir).await.is_ok()
+            && (path.exists() || tokio::fs::write(&path, &bytes).await.is_ok());
+        if stored {
+            report.stored_copy = Some(path.to_string_lossy().into_owned());
+        } else {
+            eprintln!(
+                "WARN [imagehash]: No se pudo guardar la copia en {:?}",
+                path
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Distancia de Hamming entre dos hashes hexadecimales; `None` si alguno es inválido.
+pub fn hamming(a: &str, b: &str) -> Option<u32> {
+    let a = u64::from_str_radix(a, 16).ok()?;
+    let b = u64::from_str_radix(b, 16).ok()?;
+    Some((a ^ b).count_ones())
+}
+
+pub fn compare(a: &ImageHashes, b: &ImageHashes) -> Option<ImageMatch> {
+    let ahash_distance = hamming(&a.ahash, &b.ahash)?;
+    let dhash_distance = hamming(&a.dhash, &b.dhash)?;
+    let phash_distance = hamming(&a.phash, &b.phash)?;
+    let total = ahash_distance + dhash_distance + phash_distance;
+    Some(ImageMatch {
+        ahash_distance,
+        dhash_distance,
+        phash_distance,
+        similarity: 100 - total * 100 / (3 * 64),
+        near_duplicate: phash_distance <= PHASH_THRESHOLD,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgb, RgbImage};
+
+    /// Imagen sintética con formas y degradés (algo parecido a una foto)
+    fn sample(width: u32, height: u32, variant: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let (fx, fy) = (x as f64 / width as f64, y as f64 / height as f64);
+            let circle = ((fx - 0.4).powi(2) + (fy - 0.5).powi(2)).sqrt() < 0.25;
+            let value = match variant {
+                0 if circle => 230.0,
+                0 => 40.0 + 150.0 * fx * fy,
+                _ => 255.0 * ((fx * 9.0).sin() * (fy * 7.0).cos()).abs(),
+            };
+            let v = value as u8;
+            Rgb([v, v.saturating_add(10), v / 2])
+        }))
+    }
+
+    #[test]
+    fn test_detecta_casi_duplicados() {
+        let original = sample(400, 300, 0);
+        // La misma foto reducida y recomprimida en JPEG, como la sirve otra plataforma
+        let mut jpeg = Vec::new();
+        original
+            .resize(160, 120, image::imageops::FilterType::Triangle)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpeg),
+                image::ImageFormat::Jpeg,
+            )
+            .unwrap();
+        let copy = analyze_bytes("https://cdn.ejemplo.com/avatar.jpg", &jpeg).unwrap();
+        assert_eq!(copy.format, "jpeg");
+        assert_eq!((copy.width, copy.height), (160, 120));
+
+        let result = compare(&compute(&original), &copy.hashes).unwrap();
+        assert!(result.near_duplicate, "{:?}", result);
+        assert!(result.similarity >= 85, "{:?}", result);
+
+        let other = compare(&compute(&original), &compute(&sample(400, 300, 1))).unwrap();
+        assert!(!other.near_duplicate, "{:?}", other);
+    }
+
+    #[test]
+    fn test_hamming_y_atributo() {
+        assert_eq!(hamming("ffffffffffffffff", "0000000000000000"), Some(64));
+        assert_eq!(hamming("00000000000000f0", "0000000000000010"), Some(3));
+        assert_eq!(hamming("zz", "00"), None);
+
+        let hashes = compute(&sample(64, 64, 0));
+        assert_eq!(hashes.phash.len(), 16);
+        assert_eq!(
+            ImageHashes::from_attribute(&hashes.to_attribute()),
+            Some(hashes)
+        );
+    }
+}
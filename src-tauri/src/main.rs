@@ -4,19 +4,42 @@ use tauri::{Manager, WindowEvent};
 use tokio::sync::Mutex;
 
 mod agent;
+mod asn;
 mod cases;
 mod commands;
+mod darkweb;
+mod dns;
+mod dorks;
+mod email;
+mod faces;
+mod fingerprint;
+mod geoip;
+mod hashing;
+mod hibp;
+mod hunter;
+mod imagehash;
 mod loop_detector;
 mod mac_spoof;
 mod memory;
+mod metadata;
 mod models;
 mod orchestrator;
+mod phone;
+mod ping;
 mod scraper;
 mod secrets;
+mod shodan;
 mod skills;
+mod subdomains;
 mod telegram;
+mod tls;
 mod tools;
 mod tor_manager;
+mod usernames;
+mod virustotal;
+mod wayback;
+mod websearch;
+mod whois;
 mod worker;
 
 pub struct AgentAbort(pub Arc<std::sync::atomic::AtomicBool>);
@@ -59,8 +82,17 @@ fn main() {
             commands::run_osint_lookup,
             commands::ask_agent,
             commands::extract_metadata,
+            commands::verify_email_cmd,
             commands::web_scrape_search,
-            commands::download_face_models,
+            commands::run_dorks,
+            commands::promote_dork_hit,
+            commands::wayback_history,
+            commands::wayback_snapshot,
+            commands::ip_infrastructure,
+            commands::hunter_domain_search,
+            commands::hunter_email_finder,
+            commands::face_models_status,
+            commands::compare_faces,
             commands::read_file_base64,
             commands::update_osint_config,
             commands::set_tor_active,
@@ -92,6 +124,7 @@ fn main() {
             commands::create_target_cmd,
             commands::delete_target_cmd,
             commands::get_activity_log_cmd,
+            commands::get_case_timeline,
             // Secrets
             commands::save_secure_secret,
             commands::get_secure_secret,
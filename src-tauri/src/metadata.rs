@@ -0,0 +1,984 @@
+// src-tauri/src/metadata.rs
+//
+// Extracción de metadatos de archivos locales: documentos (PDF, Office) e imágenes (EXIF).
+//
+// Flujo:
+//   1. Se detecta el formato por la firma del archivo, no por la extensión.
+//   2. PDF: diccionario Info (autor, herramienta, fechas, claves propias como "Company"),
+//      paquete XMP (creadores, historial de revisiones) y cantidad de actualizaciones
+//      incrementales (cada guardado incremental agrega un `%%EOF`).
+//   3. OOXML (docx/xlsx/pptx): `docProps/core.xml`, `docProps/app.xml` y las relaciones
+//      externas (plantillas y vínculos a rutas locales o de red).
+//   4. Imágenes: campos EXIF y XMP embebido. La posición GPS se pasa a grados decimales y
+//      la cámara (marca, modelo, número de serie) queda como huella del dispositivo.
+//   5. De todo lo anterior se sacan rutas/hosts embebidos y los nombres de autor, que se
+//      ofrecen como candidatos a Persona.
+//   6. El SHA-256 del archivo acompaña al reporte como procedencia de cada hallazgo.
+
+use exif::{In, Tag, Value};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+const NS_XMP: &str = "http://ns.adobe.com/xap/1.0/";
+const NS_XMP_MM: &str = "http://ns.adobe.com/xap/1.0/mm/";
+const NS_ST_EVT: &str = "http://ns.adobe.com/xap/1.0/sType/ResourceEvent#";
+const NS_ST_REF: &str = "http://ns.adobe.com/xap/1.0/sType/ResourceRef#";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+const NS_PDF: &str = "http://ns.adobe.com/pdf/1.3/";
+const NS_RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const NS_CP: &str = "http://schemas.openxmlformats.org/package/2006/metadata/core-properties";
+const NS_DCTERMS: &str = "http://purl.org/dc/terms/";
+const NS_APP: &str = "http://schemas.openxmlformats.org/officeDocument/2006/extended-properties";
+
+/// Tope de lectura de cada parte XML de un OOXML (propiedades y relaciones pesan pocos KB)
+const MAX_ZIP_ENTRY_BYTES: u64 = 1024 * 1024;
+
+/// Valores de autor que ponen las aplicaciones por defecto y no identifican a nadie.
+const GENERIC_AUTHORS: &[&str] = &[
+    "admin",
+    "administrador",
+    "administrator",
+    "autor",
+    "author",
+    "microsoft office user",
+    "owner",
+    "propietario",
+    "unknown",
+    "user",
+    "usuario",
+    "windows user",
+    "usuario de windows",
+];
+
+/// Hosts de los espacios de nombres XML: están en todo paquete XMP u OOXML y no dicen
+/// nada de quién hizo el documento.
+const SCHEMA_HOSTS: &[&str] = &[
+    "ns.adobe.com",
+    "purl.org",
+    "schemas.microsoft.com",
+    "schemas.openxmlformats.org",
+    "www.w3.org",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PdfInfo {
+    pub version: String,
+    pub encrypted: bool,
+    pub pages: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    /// Aplicación que generó el original (ej: "Microsoft® Word para Microsoft 365")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// Biblioteca que escribió el PDF
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// Guardados incrementales posteriores a la versión original
+    pub incremental_updates: usize,
+    /// Claves no estándar del diccionario Info (ej: "Company", "SourceModified")
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub extra: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct XmpEvent {
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub software_agent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct XmpInfo {
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub creators: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator_tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derived_from: Option<String>,
+    /// Historial de revisiones (xmpMM:History)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub history: Vec<XmpEvent>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OfficeProps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_printed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manager: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Minutos de edición acumulados
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_time: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExifField {
+    pub tag: String,
+    pub ifd: String,
+    pub value: String,
+}
+
+/// Posición GPS de la foto en grados decimales (WGS84).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GpsInfo {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Metros sobre el nivel del mar (negativo si está por debajo)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
+    /// Fecha y hora UTC del receptor GPS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+}
+
+impl GpsInfo {
+    /// Coordenadas "lat,lon", usadas como nombre del objetivo Location.
+    pub fn coordinates(&self) -> String {
+        crate::cases::TargetType::location_name(self.latitude, self.longitude)
+    }
+
+    pub fn maps_url(&self) -> String {
+        format!(
+            "https://www.openstreetmap.org/?mlat={:.6}&mlon={:.6}#map=17/{:.6}/{:.6}",
+            self.latitude, self.longitude, self.latitude, self.longitude
+        )
+    }
+}
+
+/// Dispositivo que tomó la foto.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CameraInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub make: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lens: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub software: Option<String>,
+}
+
+impl CameraInfo {
+    /// Identificador del dispositivo para agrupar fotos: "Marca Modelo" y, si está, "#serie".
+    /// Sin serie sólo agrupa por modelo, que es una pista más débil.
+    pub fn device_id(&self) -> Option<String> {
+        // Muchos fabricantes repiten la marca en el modelo ("Canon" + "Canon EOS 80D")
+        let name = match (self.make.as_deref(), self.model.as_deref()) {
+            (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => {
+                model.to_string()
+            }
+            (Some(make), Some(model)) => format!("{} {}", make, model),
+            (make, model) => make.or(model).unwrap_or_default().to_string(),
+        };
+        if name.is_empty() {
+            return None;
+        }
+        Some(match &self.serial {
+            Some(serial) => format!("{} #{}", name, serial),
+            None => name,
+        })
+    }
+}
+
+/// Resultado de la extracción, listo para `detalles_tecnicos.metadatos` del objetivo File.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MetadataReport {
+    pub file: String,
+    /// pdf, docx, xlsx, pptx, ooxml o image
+    pub format: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdf: Option<PdfInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub office: Option<OfficeProps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xmp: Option<XmpInfo>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub exif: Vec<ExifField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gps: Option<GpsInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraInfo>,
+    /// Rutas locales, UNC o `file://` embebidas (delatan usuarios, equipos y servidores)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub embedded_paths: Vec<String>,
+    /// Servidores de rutas UNC y hosts de URLs embebidas (plantillas, vínculos, XMP)
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub embedded_hosts: Vec<String>,
+    /// Nombres de autor/editor que pueden darse de alta como Persona
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub person_candidates: Vec<String>,
+}
+
+/// Extrae los metadatos del archivo según su formato.
+pub fn extract(path: &str) -> Result<MetadataReport, String> {
+    let bytes = std::fs::read(path).map_err(|e| {
+        eprintln!("ERROR [metadata]: Failed to open file: {} - {}", path, e);
+        "No se pudo abrir el archivo especificado.".to_string()
+    })?;
+
+    let mut report = MetadataReport {
+        file: Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string()),
+        size_bytes: bytes.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&bytes)),
+        ..Default::default()
+    };
+
+    let mut texts: Vec<String> = Vec::new();
+    if bytes.starts_with(b"%PDF-") {
+        report.format = "pdf".to_string();
+        let pdf = parse_pdf(&bytes)?;
+        texts.extend(pdf.title.iter().cloned());
+        texts.extend(pdf.extra.values().cloned());
+        report.pdf = Some(pdf);
+        report.xmp = find_xmp_packet(&bytes).and_then(|packet| {
+            texts.push(packet.clone());
+            parse_xmp(&packet)
+        });
+    } else if bytes.starts_with(b"PK\x03\x04") {
+        let (format, office, external) = parse_ooxml(&bytes)?;
+        report.format = format;
+        texts.extend(external);
+        texts.extend(office.template.iter().cloned());
+        report.office = Some(office);
+    } else {
+        report.format = "image".to_string();
+        let exif = read_exif(&bytes)?;
+        report.exif = exif_fields(&exif);
+        report.gps = exif_gps(&exif);
+        report.camera = exif_camera(&exif);
+        report.xmp = find_xmp_packet(&bytes).and_then(|packet| {
+            texts.push(packet.clone());
+            parse_xmp(&packet)
+        });
+    }
+
+    report.embedded_paths = texts.iter().flat_map(|t| extract_paths(t)).collect();
+    report.embedded_paths.sort();
+    report.embedded_paths.dedup();
+    report.embedded_hosts = texts.iter().flat_map(|t| extract_hosts(t)).collect();
+    report.embedded_hosts.sort();
+    report.embedded_hosts.dedup();
+    report.person_candidates = person_candidates(&report);
+    Ok(report)
+}
+
+fn read_exif(bytes: &[u8]) -> Result<exif::Exif, String> {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(bytes))
+        .map_err(|e| {
+            eprintln!("ERROR [metadata]: EXIF read failure: {}", e);
+            "No se encontraron metadatos EXIF o el formato no es soportado.".to_string()
+        })
+}
+
+fn exif_fields(exif: &exif::Exif) -> Vec<ExifField> {
+    exif.fields()
+        .map(|f| ExifField {
+            tag: f.tag.to_string(),
+            ifd: f.ifd_num.to_string(),
+            value: f.display_value().with_unit(exif).to_string(),
+        })
+        .collect()
+}
+
+fn exif_text(exif: &exif::Exif, tag: Tag) -> Option<String> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Ascii(parts) => {
+            let text = parts
+                .iter()
+                .map(|p| String::from_utf8_lossy(p).trim().to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Some(text.trim_matches(char::from(0)).trim().to_string()).filter(|t| !t.is_empty())
+        }
+        _ => None,
+    }
+}
+
+fn exif_rationals(exif: &exif::Exif, tag: Tag) -> Option<Vec<f64>> {
+    match &exif.get_field(tag, In::PRIMARY)?.value {
+        Value::Rational(values) => Some(values.iter().map(|r| r.to_f64()).collect()),
+        _ => None,
+    }
+}
+
+/// Grados, minutos y segundos a grados decimales; el hemisferio S/W lo vuelve negativo.
+fn dms_to_decimal(dms: &[f64], reference: Option<&str>) -> Option<f64> {
+    let degrees = *dms.first()?;
+    let minutes = dms.get(1).copied().unwrap_or(0.0);
+    let seconds = dms.get(2).copied().unwrap_or(0.0);
+    let value = degrees + minutes / 60.0 + seconds / 3600.0;
+    if !value.is_finite() {
+        return None;
+    }
+    Some(match reference.map(str::trim) {
+        Some("S") | Some("W") => -value,
+        _ => value,
+    })
+}
+
+fn exif_gps(exif: &exif::Exif) -> Option<GpsInfo> {
+    let latitude = dms_to_decimal(
+        &exif_rationals(exif, Tag::GPSLatitude)?,
+        exif_text(exif, Tag::GPSLatitudeRef).as_deref(),
+    )?;
+    let longitude = dms_to_decimal(
+        &exif_rationals(exif, Tag::GPSLongitude)?,
+        exif_text(exif, Tag::GPSLongitudeRef).as_deref(),
+    )?;
+    // Cámaras sin señal graban 0/0: no es una posición real
+    if !(-90.0..=90.0).contains(&latitude)
+        || !(-180.0..=180.0).contains(&longitude)
+        || (latitude == 0.0 && longitude == 0.0)
+    {
+        return None;
+    }
+
+    let below_sea_level = exif
+        .get_field(Tag::GPSAltitudeRef, In::PRIMARY)
+        .and_then(|f| f.value.get_uint(0))
+        == Some(1);
+    let altitude = exif_rationals(exif, Tag::GPSAltitude)
+        .and_then(|v| v.first().copied())
+        .filter(|a| a.is_finite())
+        .map(|a| if below_sea_level { -a } else { a });
+
+    // GPSDateStamp "2023:05:14" + GPSTimeStamp [h, m, s] en UTC
+    let timestamp = exif_text(exif, Tag::GPSDateStamp).map(|date| {
+        let date = date.replace(':', "-");
+        match exif_rationals(exif, Tag::GPSTimeStamp).filter(|t| t.len() == 3) {
+            Some(t) => format!(
+                "{}T{:02}:{:02}:{:02}Z",
+                date, t[0] as u32, t[1] as u32, t[2] as u32
+            ),
+            None => date,
+        }
+    });
+
+    Some(GpsInfo {
+        latitude,
+        longitude,
+        altitude,
+        timestamp,
+    })
+}
+
+fn exif_camera(exif: &exif::Exif) -> Option<CameraInfo> {
+    let camera = CameraInfo {
+        make: exif_text(exif, Tag::Make),
+        model: exif_text(exif, Tag::Model),
+        serial: exif_text(exif, Tag::BodySerialNumber),
+        lens: exif_text(exif, Tag::LensModel),
+        owner: exif_text(exif, Tag::CameraOwnerName),
+        software: exif_text(exif, Tag::Software),
+    };
+    (camera != CameraInfo::default()).then_some(camera)
+}
+
+/// Texto de un string PDF: UTF-16BE con BOM o PDFDocEncoding (≈ Latin-1).
+fn pdf_text(bytes: &[u8]) -> String {
+    let text = match bytes.strip_prefix(&[0xFE, 0xFF]) {
+        Some(utf16) => {
+            let units: Vec<u16> = utf16
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        None => bytes.iter().map(|&b| b as char).collect(),
+    };
+    text.trim_matches(char::from(0)).trim().to_string()
+}
+
+/// Convierte una fecha PDF (`D:20230115103000-03'00'`) a ISO 8601; si no encaja se deja igual.
+fn pdf_date(raw: &str) -> String {
+    let s = raw.trim().trim_start_matches("D:");
+    let digits: String = s.chars().take_while(char::is_ascii_digit).collect();
+    if digits.len() < 4 {
+        return raw.trim().to_string();
+    }
+    let part = |from: usize, len: usize, default: &str| {
+        digits.get(from..from + len).unwrap_or(default).to_string()
+    };
+    let mut iso = format!(
+        "{}-{}-{}T{}:{}:{}",
+        part(0, 4, "0000"),
+        part(4, 2, "01"),
+        part(6, 2, "01"),
+        part(8, 2, "00"),
+        part(10, 2, "00"),
+        part(12, 2, "00")
+    );
+    let tz = &s[digits.len()..];
+    match tz.chars().next() {
+        Some('Z') => iso.push('Z'),
+        Some(sign @ ('+' | '-')) => {
+            let tz_digits: String = tz.chars().filter(char::is_ascii_digit).collect();
+            if tz_digits.len() >= 2 {
+                let minutes = tz_digits.get(2..4).unwrap_or("00");
+                iso.push_str(&format!("{}{}:{}", sign, &tz_digits[..2], minutes));
+            }
+        }
+        _ => {}
+    }
+    iso
+}
+
+fn parse_pdf(bytes: &[u8]) -> Result<PdfInfo, String> {
+    let doc = lopdf::Document::load_mem(bytes).map_err(|e| {
+        eprintln!("ERROR [metadata]: PDF parse failure: {}", e);
+        "El PDF está dañado o no se pudo interpretar.".to_string()
+    })?;
+
+    let eof_markers = bytes.windows(5).filter(|w| *w == b"%%EOF").count();
+    let mut info = PdfInfo {
+        version: doc.version.clone(),
+        encrypted: doc.is_encrypted(),
+        pages: doc.get_pages().len(),
+        incremental_updates: eof_markers.saturating_sub(1),
+        ..Default::default()
+    };
+
+    let dict = doc
+        .trailer
+        .get(b"Info")
+        .ok()
+        .and_then(|obj| doc.dereference(obj).ok())
+        .and_then(|(_, obj)| obj.as_dict().ok());
+    let Some(dict) = dict else {
+        return Ok(info);
+    };
+
+    for (key, value) in dict.iter() {
+        let value = match doc.dereference(value) {
+            Ok((_, lopdf::Object::String(raw, _))) => pdf_text(raw),
+            Ok((_, lopdf::Object::Name(raw))) => String::from_utf8_lossy(raw).into_owned(),
+            _ => continue,
+        };
+        if value.is_empty() {
+            continue;
+        }
+        match key.as_slice() {
+            b"Title" => info.title = Some(value),
+            b"Author" => info.author = Some(value),
+            b"Subject" => info.subject = Some(value),
+            b"Keywords" => info.keywords = Some(value),
+            b"Creator" => info.creator = Some(value),
+            b"Producer" => info.producer = Some(value),
+            b"CreationDate" => info.created = Some(pdf_date(&value)),
+            b"ModDate" => info.modified = Some(pdf_date(&value)),
+            b"Trapped" => {}
+            other => {
+                info.extra
+                    .insert(String::from_utf8_lossy(other).into_owned(), value);
+            }
+        }
+    }
+    Ok(info)
+}
+
+/// Busca un paquete XMP sin comprimir (lo habitual en PDF, JPEG y TIFF).
+fn find_xmp_packet(bytes: &[u8]) -> Option<String> {
+    let find = |needle: &[u8], from: usize| {
+        bytes
+            .get(from..)?
+            .windows(needle.len())
+            .position(|w| w == needle)
+            .map(|p| p + from)
+    };
+    let start = find(b"<x:xmpmeta", 0)?;
+    let end = find(b"</x:xmpmeta>", start)? + b"</x:xmpmeta>".len();
+    Some(String::from_utf8_lossy(&bytes[start..end]).into_owned())
+}
+
+/// Valor de una propiedad XMP, escrita como atributo o como elemento (con rdf:li o sin ellos).
+fn xmp_values(doc: &roxmltree::Document, ns: &str, name: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    for node in doc.descendants().filter(|n| n.is_element()) {
+        if let Some(attr) = node.attribute((ns, name)) {
+            values.push(attr.trim().to_string());
+        }
+        if node.tag_name().namespace() == Some(ns) && node.tag_name().name() == name {
+            let items: Vec<String> = node
+                .descendants()
+                .filter(|n| n.tag_name().namespace() == Some(NS_RDF) && n.tag_name().name() == "li")
+                .filter_map(|li| li.text().map(|t| t.trim().to_string()))
+                .collect();
+            if items.is_empty() {
+                if let Some(text) = node.text() {
+                    values.push(text.trim().to_string());
+                }
+            } else {
+                values.extend(items);
+            }
+        }
+    }
+    values.retain(|v| !v.is_empty());
+    values
+}
+
+fn parse_xmp(packet: &str) -> Option<XmpInfo> {
+    let doc = roxmltree::Document::parse(packet).ok()?;
+    let first = |ns: &str, name: &str| xmp_values(&doc, ns, name).into_iter().next();
+
+    // Cada evento del historial puede traer sus campos como atributos o como hijos
+    let field = |li: roxmltree::Node, name: &str| {
+        li.attribute((NS_ST_EVT, name))
+            .map(str::to_string)
+            .or_else(|| {
+                li.descendants()
+                    .find(|n| {
+                        n.tag_name().namespace() == Some(NS_ST_EVT) && n.tag_name().name() == name
+                    })
+                    .and_then(|n| n.text().map(str::to_string))
+            })
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let history = doc
+        .descendants()
+        .filter(|n| n.tag_name().namespace() == Some(NS_XMP_MM) && n.tag_name().name() == "History")
+        .flat_map(|h| h.descendants())
+        .filter(|n| n.tag_name().namespace() == Some(NS_RDF) && n.tag_name().name() == "li")
+        .filter_map(|li| {
+            Some(XmpEvent {
+                action: field(li, "action")?,
+                when: field(li, "when"),
+                software_agent: field(li, "softwareAgent"),
+            })
+        })
+        .collect();
+
+    let info = XmpInfo {
+        creators: xmp_values(&doc, NS_DC, "creator"),
+        creator_tool: first(NS_XMP, "CreatorTool"),
+        producer: first(NS_PDF, "Producer"),
+        created: first(NS_XMP, "CreateDate"),
+        modified: first(NS_XMP, "ModifyDate"),
+        document_id: first(NS_XMP_MM, "DocumentID"),
+        derived_from: first(NS_ST_REF, "filePath"),
+        history,
+    };
+    Some(info)
+}
+
+/// Lee una parte XML del paquete. Las que declaran más de `MAX_ZIP_ENTRY_BYTES` se ignoran y
+/// la lectura se corta ahí igual: el tamaño declarado puede mentir (bomba de compresión).
+fn zip_entry(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Option<String> {
+    let entry = archive.by_name(name).ok()?;
+    if entry.size() > MAX_ZIP_ENTRY_BYTES {
+        eprintln!(
+            "ERROR [metadata]: ZIP entry too large: {} ({} bytes)",
+            name,
+            entry.size()
+        );
+        return None;
+    }
+    let mut content = String::new();
+    entry
+        .take(MAX_ZIP_ENTRY_BYTES)
+        .read_to_string(&mut content)
+        .ok()?;
+    Some(content)
+}
+
+/// Lee las propiedades de un documento OOXML. Devuelve el formato, las propiedades y los
+/// destinos externos de las relaciones (plantillas, vínculos a archivos).
+fn parse_ooxml(bytes: &[u8]) -> Result<(String, OfficeProps, Vec<String>), String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| {
+        eprintln!("ERROR [metadata]: ZIP parse failure: {}", e);
+        "El archivo comprimido está dañado.".to_string()
+    })?;
+
+    let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    if !names.iter().any(|n| n == "[Content_Types].xml") {
+        return Err("El archivo ZIP no es un documento de Office (OOXML).".to_string());
+    }
+    let format = if names.iter().any(|n| n.starts_with("word/")) {
+        "docx"
+    } else if names.iter().any(|n| n.starts_with("xl/")) {
+        "xlsx"
+    } else if names.iter().any(|n| n.starts_with("ppt/")) {
+        "pptx"
+    } else {
+        "ooxml"
+    };
+
+    let mut props = OfficeProps::default();
+    if let Some(core) = zip_entry(&mut archive, "docProps/core.xml") {
+        if let Ok(doc) = roxmltree::Document::parse(&core) {
+            let get = |ns: &str, name: &str| xmp_values(&doc, ns, name).into_iter().next();
+            props.title = get(NS_DC, "title");
+            props.subject = get(NS_DC, "subject");
+            props.creator = get(NS_DC, "creator");
+            props.description = get(NS_DC, "description");
+            props.keywords = get(NS_CP, "keywords");
+            props.last_modified_by = get(NS_CP, "lastModifiedBy");
+            props.revision = get(NS_CP, "revision");
+            props.last_printed = get(NS_CP, "lastPrinted");
+            props.created = get(NS_DCTERMS, "created");
+            props.modified = get(NS_DCTERMS, "modified");
+        }
+    }
+    if let Some(app) = zip_entry(&mut archive, "docProps/app.xml") {
+        if let Ok(doc) = roxmltree::Document::parse(&app) {
+            let get = |name: &str| xmp_values(&doc, NS_APP, name).into_iter().next();
+            props.application = get("Application");
+            props.app_version = get("AppVersion");
+            props.company = get("Company");
+            props.manager = get("Manager");
+            props.template = get("Template");
+            props.total_time = get("TotalTime");
+        }
+    }
+
+    let mut external = Vec::new();
+    for name in names.iter().filter(|n| n.ends_with(".rels")) {
+        let Some(rels) = zip_entry(&mut archive, name) else {
+            continue;
+        };
+        let Ok(doc) = roxmltree::Document::parse(&rels) else {
+            continue;
+        };
+        external.extend(
+            doc.descendants()
+                .filter(|n| n.attribute("TargetMode") == Some("External"))
+                .filter_map(|n| n.attribute("Target"))
+                .map(str::to_string),
+        );
+    }
+
+    Ok((format.to_string(), props, external))
+}
+
+/// Rutas de archivo embebidas: UNC (`\\servidor\recurso`), unidades de Windows, `file://`
+/// y directorios personales de Unix/macOS.
+fn extract_paths(text: &str) -> Vec<String> {
+    let stop = |c: char| {
+        matches!(
+            c,
+            '"' | '\'' | '<' | '>' | '|' | '\n' | '\r' | '\t' | '\0' | ')'
+        )
+    };
+    let take_path = |start: usize| {
+        let rest = &text[start..];
+        let end = rest.find(stop).unwrap_or(rest.len());
+        rest[..end]
+            .trim_end_matches(['.', ',', ';', ' '])
+            .to_string()
+    };
+
+    let mut paths = Vec::new();
+    let bytes = text.as_bytes();
+    for (i, _) in text.char_indices() {
+        let rest = &bytes[i..];
+        let prev_alnum = i > 0 && bytes[i - 1].is_ascii_alphanumeric();
+        let is_unc = rest.starts_with(b"\\\\")
+            && rest.get(2).is_some_and(|b| b.is_ascii_alphanumeric())
+            && (i == 0 || bytes[i - 1] != b'\\');
+        let is_drive = !prev_alnum
+            && rest.len() > 3
+            && rest[0].is_ascii_alphabetic()
+            && rest[1] == b':'
+            && rest[2] == b'\\';
+        let is_file_uri = rest.starts_with(b"file:/");
+        let is_home = !prev_alnum && (rest.starts_with(b"/home/") || rest.starts_with(b"/Users/"));
+
+        if is_unc || is_drive || is_file_uri || is_home {
+            let path = take_path(i);
+            if path.len() > 4 {
+                paths.push(path);
+            }
+        }
+    }
+    // Un `file:///C:\...` también matchea como unidad: se queda la forma más larga
+    let all = paths.clone();
+    paths.retain(|p| {
+        !all.iter()
+            .any(|other| other != p && other.ends_with(p.as_str()))
+    });
+    paths
+}
+
+/// Servidores de las rutas UNC (`\\\\servidor\\recurso`) y hosts de las URLs http(s) del
+/// texto, en minúsculas. Se descartan los hosts de espacios de nombres XML.
+fn extract_hosts(text: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = extract_paths(text)
+        .iter()
+        .filter_map(|path| {
+            let unc = &path[path.find("\\\\")? + 2..];
+            unc.split('\\').next().map(str::to_string)
+        })
+        .collect();
+
+    for scheme in ["http://", "https://"] {
+        for (i, _) in text.match_indices(scheme) {
+            let rest = &text[i + scheme.len()..];
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '-'))
+                .unwrap_or(rest.len());
+            hosts.push(rest[..end].trim_end_matches('.').to_string());
+        }
+    }
+
+    let mut hosts: Vec<String> = hosts
+        .into_iter()
+        .map(|h| h.to_lowercase())
+        .filter(|h| !h.is_empty() && !SCHEMA_HOSTS.contains(&h.as_str()))
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+/// Autores y editores encontrados en cualquier formato, sin los valores genéricos.
+fn person_candidates(report: &MetadataReport) -> Vec<String> {
+    let mut raw: Vec<String> = Vec::new();
+    if let Some(pdf) = &report.pdf {
+        raw.extend(pdf.author.iter().cloned());
+    }
+    if let Some(xmp) = &report.xmp {
+        raw.extend(xmp.creators.iter().cloned());
+    }
+    if let Some(office) = &report.office {
+        raw.extend(office.creator.iter().cloned());
+        raw.extend(office.last_modified_by.iter().cloned());
+    }
+    if let Some(camera) = &report.camera {
+        raw.extend(camera.owner.iter().cloned());
+    }
+
+    let mut candidates: Vec<String> = Vec::new();
+    for name in raw.iter().flat_map(|r| r.split(';')) {
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        if name.chars().count() < 3
+            || !name.chars().any(char::is_alphabetic)
+            || GENERIC_AUTHORS.contains(&name.to_lowercase().as_str())
+        {
+            continue;
+        }
+        if !candidates.iter().any(|c| c.eq_ignore_ascii_case(&name)) {
+            candidates.push(name);
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_metadatos_ooxml() {
+        let mut buffer = Vec::new();
+        {
+            let mut zip = zip::ZipWriter::new(Cursor::new(&mut buffer));
+            let opts = zip::write::SimpleFileOptions::default();
+            let mut add = |name: &str, content: &str| {
+                zip.start_file(name, opts).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            };
+            add("[Content_Types].xml", "<Types/>");
+            add("word/document.xml", "<w:document/>");
+            // Parte inflada: se descarta sin descomprimirla entera
+            add(
+                "word/_rels/inflada.xml.rels",
+                &format!(
+                    r#"<Relationships>{}<Relationship Target="http://inflada.ejemplo.com/" TargetMode="External"/></Relationships>"#,
+                    " ".repeat(2 * 1024 * 1024)
+                ),
+            );
+            add(
+                "docProps/core.xml",
+                r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+                    xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
+                    <dc:creator>Juan Pérez</dc:creator><cp:lastModifiedBy>Usuario</cp:lastModifiedBy>
+                    <cp:revision>7</cp:revision><dcterms:created>2024-03-01T10:00:00Z</dcterms:created>
+                </cp:coreProperties>"#,
+            );
+            add(
+                "docProps/app.xml",
+                r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
+                    <Application>Microsoft Office Word</Application><Company>Ejemplo SA</Company>
+                </Properties>"#,
+            );
+            add(
+                "word/_rels/settings.xml.rels",
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                    <Relationship Id="rId1" Target="file:///\\srv-archivos\plantillas\Informe.dotm" TargetMode="External"/>
+                </Relationships>"#,
+            );
+            zip.finish().unwrap();
+        }
+
+        let (format, props, external) = parse_ooxml(&buffer).unwrap();
+        assert_eq!(format, "docx");
+        assert_eq!(props.creator.as_deref(), Some("Juan Pérez"));
+        assert_eq!(props.revision.as_deref(), Some("7"));
+        assert_eq!(props.company.as_deref(), Some("Ejemplo SA"));
+        assert_eq!(
+            extract_paths(&external[0]),
+            vec!["file:///\\\\srv-archivos\\plantillas\\Informe.dotm"]
+        );
+        assert_eq!(external.len(), 1);
+        assert_eq!(extract_hosts(&external[0]), vec!["srv-archivos"]);
+        assert_eq!(
+            extract_hosts(
+                r#"<Relationship Target="https://Intranet.Ejemplo.com/plantillas/a.dotx"/>"#
+            ),
+            vec!["intranet.ejemplo.com"]
+        );
+
+        // "Usuario" es el valor por defecto de Office: no es un candidato
+        let report = MetadataReport {
+            office: Some(props),
+            ..Default::default()
+        };
+        assert_eq!(person_candidates(&report), vec!["Juan Pérez"]);
+    }
+
+    #[test]
+    fn test_gps_y_camara_exif() {
+        use exif::{Field, Rational};
+        let rationals = |v: &[(u32, u32)]| {
+            Value::Rational(
+                v.iter()
+                    .map(|&(num, denom)| Rational { num, denom })
+                    .collect(),
+            )
+        };
+        let ascii = |s: &str| Value::Ascii(vec![s.as_bytes().to_vec()]);
+        let field = |tag, value| Field {
+            tag,
+            ifd_num: In::PRIMARY,
+            value,
+        };
+        // Obelisco de Buenos Aires: 34° 36' 13.8" S, 58° 22' 53.9" W
+        let fields = [
+            field(Tag::Make, ascii("Canon")),
+            field(Tag::Model, ascii("Canon EOS 80D")),
+            field(Tag::BodySerialNumber, ascii("123456789")),
+            field(Tag::GPSLatitudeRef, ascii("S")),
+            field(Tag::GPSLatitude, rationals(&[(34, 1), (36, 1), (138, 10)])),
+            field(Tag::GPSLongitudeRef, ascii("W")),
+            field(Tag::GPSLongitude, rationals(&[(58, 1), (22, 1), (539, 10)])),
+            field(Tag::GPSAltitudeRef, Value::Byte(vec![0])),
+            field(Tag::GPSAltitude, rationals(&[(25, 1)])),
+            field(Tag::GPSDateStamp, ascii("2023:05:14")),
+            field(Tag::GPSTimeStamp, rationals(&[(17, 1), (5, 1), (9, 1)])),
+        ];
+        let mut writer = exif::experimental::Writer::new();
+        for f in &fields {
+            writer.push_field(f);
+        }
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let exif = exif::Reader::new().read_raw(tiff.into_inner()).unwrap();
+
+        let gps = exif_gps(&exif).unwrap();
+        assert_eq!(gps.coordinates(), "-34.603833,-58.381639");
+        assert_eq!(gps.altitude, Some(25.0));
+        assert_eq!(gps.timestamp.as_deref(), Some("2023-05-14T17:05:09Z"));
+
+        let camera = exif_camera(&exif).unwrap();
+        assert_eq!(
+            camera.device_id().as_deref(),
+            Some("Canon EOS 80D #123456789")
+        );
+    }
+
+    #[test]
+    fn test_xmp_fechas_y_rutas_pdf() {
+        assert_eq!(
+            pdf_date("D:20230115103000-03'00'"),
+            "2023-01-15T10:30:00-03:00"
+        );
+        assert_eq!(pdf_date("D:20230115"), "2023-01-15T00:00:00");
+
+        let packet = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+            <rdf:Description xmlns:xmp="http://ns.adobe.com/xap/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/"
+                xmlns:xmpMM="http://ns.adobe.com/xap/1.0/mm/" xmlns:stEvt="http://ns.adobe.com/xap/1.0/sType/ResourceEvent#"
+                xmp:CreatorTool="Microsoft® Word 2019">
+                <dc:creator><rdf:Seq><rdf:li>María Gómez</rdf:li></rdf:Seq></dc:creator>
+                <xmpMM:History><rdf:Seq>
+                    <rdf:li stEvt:action="created" stEvt:when="2023-01-15T10:30:00-03:00"/>
+                    <rdf:li><stEvt:action>saved</stEvt:action><stEvt:softwareAgent>Adobe Acrobat</stEvt:softwareAgent></rdf:li>
+                </rdf:Seq></xmpMM:History>
+            </rdf:Description></rdf:RDF></x:xmpmeta>"#;
+        let bytes = format!("%PDF-1.7 basura {} %%EOF", packet);
+        let xmp = parse_xmp(&find_xmp_packet(bytes.as_bytes()).unwrap()).unwrap();
+        assert_eq!(xmp.creators, vec!["María Gómez"]);
+        // Los espacios de nombres no son hosts del documento
+        assert!(extract_hosts(packet).is_empty());
+        assert_eq!(xmp.creator_tool.as_deref(), Some("Microsoft® Word 2019"));
+        assert_eq!(xmp.history.len(), 2);
+        assert_eq!(
+            xmp.history[1].software_agent.as_deref(),
+            Some("Adobe Acrobat")
+        );
+
+        assert_eq!(
+            extract_paths("Microsoft Word - C:\\Users\\jperez\\Desktop\\informe final.docx"),
+            vec!["C:\\Users\\jperez\\Desktop\\informe final.docx"]
+        );
+    }
+}
@@ -31,6 +31,23 @@ pub struct OsintConfig {
     pub telegram_active: bool,
     pub ollama_url: String,
     pub ollama_model: String,
+    // Resolver DNS propio (vacío = el del sistema) y transporte TCP en lugar de UDP
+    #[serde(default)]
+    pub dns_server: String,
+    #[serde(default)]
+    pub dns_over_tcp: bool,
+    // Instancia propia de SearXNG (ej: http://localhost:8888) con formato JSON habilitado
+    #[serde(default)]
+    pub searxng_url: String,
+    // Archivo web compatible con Wayback/CDX (vacío = web.archive.org)
+    #[serde(default)]
+    pub wayback_url: String,
+    // Base MMDB propia (GeoLite2, DB-IP...); sin ella se usa la GeoIP de Tor
+    #[serde(default)]
+    pub geoip_database: String,
+    // Permite completar la geolocalización local con servicios en línea (ip-api.com)
+    #[serde(default)]
+    pub online_enrichment: bool,
 }
 
 impl Default for OsintConfig {
@@ -56,6 +73,12 @@ impl Default for OsintConfig {
             telegram_active: false,
             ollama_url: "http://localhost:11434".to_string(),
             ollama_model: "llama3.2".to_string(),
+            dns_server: String::new(),
+            dns_over_tcp: false,
+            searxng_url: String::new(),
+            wayback_url: String::new(),
+            geoip_database: String::new(),
+            online_enrichment: false,
         }
     }
 }
@@ -1,11 +1,16 @@
 use crate::agent::{Agent, ToolCall};
 use crate::cases::{CaseManager, Target, TargetType};
 use crate::loop_detector::{LoopDetector, LoopLevel, LoopResult};
+use crate::models::{OsintConfig, OsintResult};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
+/// Hechos de la cronología que entran al contexto del agente; el resto queda en el panel
+const TIMELINE_CONTEXT_LIMIT: usize = 15;
+
 pub struct Orchestrator {
     app: AppHandle,
     case_manager: Arc<CaseManager>,
@@ -126,6 +131,38 @@ impl Orchestrator {
             markdown_ctx.push('\n');
         }
 
+        // 3. Cronología (hechos fechados: filtraciones, publicaciones...). Sólo los más
+        // recientes: un dominio con cientos de cuentas filtradas desbordaría el contexto
+        if let Ok(events) = self.case_manager.get_timeline(case_name) {
+            let dated: Vec<_> = events.iter().filter(|e| e.date.is_some()).collect();
+            let undated = events.len() - dated.len();
+            if !dated.is_empty() {
+                let recent = &dated[dated.len().saturating_sub(TIMELINE_CONTEXT_LIMIT)..];
+                markdown_ctx.push_str("- **CRONOLOGÍA (más recientes):**\n");
+                for e in recent {
+                    markdown_ctx.push_str(&format!(
+                        "  * {} · {} · {}\n",
+                        e.date.as_deref().unwrap_or_default(),
+                        e.subject_name,
+                        e.title
+                    ));
+                }
+                let older = dated.len() - recent.len();
+                if older > 0 {
+                    markdown_ctx.push_str(&format!("  * ({} hechos anteriores omitidos)\n", older));
+                }
+            }
+            if undated > 0 {
+                markdown_ctx.push_str(&format!(
+                    "- **CRONOLOGÍA:** {} hechos sin fecha (ver la pestaña Cronología)\n",
+                    undated
+                ));
+            }
+            if !events.is_empty() {
+                markdown_ctx.push('\n');
+            }
+        }
+
         format!(
             "{}\n--------------------------------------------\n",
             markdown_ctx
@@ -221,6 +258,1207 @@ impl Orchestrator {
                         ));
                     }
                 }
+                "hacer_ping" | "ping" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let options = crate::ping::PingOptions {
+                        count: call
+                            .arguments
+                            .get("count")
+                            .and_then(|c| c.trim().parse().ok()),
+                        ports: call
+                            .arguments
+                            .get("ports")
+                            .map(|p| {
+                                p.split(',')
+                                    .filter_map(|port| port.trim().parse().ok())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        tcp_only: call
+                            .arguments
+                            .get("tcp_only")
+                            .is_some_and(|v| v.trim().eq_ignore_ascii_case("true")),
+                    };
+                    let res = crate::tools::perform_ping(&target, &options).await;
+
+                    let target_type = if target.trim().parse::<std::net::IpAddr>().is_ok() {
+                        TargetType::IP
+                    } else {
+                        TargetType::Domain
+                    };
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        target.trim(),
+                        target_type,
+                        "ping",
+                        res,
+                    ));
+                }
+                "escanear_puertos" | "port_scan" | "nmap" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let options = crate::tools::PortScanOptions {
+                        ports: call.arguments.get("ports").cloned(),
+                        concurrency: call
+                            .arguments
+                            .get("concurrency")
+                            .and_then(|c| c.trim().parse().ok()),
+                        timeout_ms: call
+                            .arguments
+                            .get("timeout_ms")
+                            .and_then(|t| t.trim().parse().ok()),
+                        grab_banners: call
+                            .arguments
+                            .get("banners")
+                            .is_some_and(|v| v.trim().eq_ignore_ascii_case("true")),
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::port_scan(&target, &options, &config).await;
+
+                    // Se guarda sobre la IP escaneada; si se pidió un dominio, queda vinculado a ella
+                    let report =
+                        serde_json::from_str::<crate::tools::PortScanReport>(&res.data).ok();
+                    let (name, target_type) = match &report {
+                        Some(r) if r.ip.parse::<std::net::IpAddr>().is_ok() => {
+                            (r.ip.clone(), TargetType::IP)
+                        }
+                        _ => (target.trim().to_string(), TargetType::Domain),
+                    };
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type,
+                        "puertos",
+                        res,
+                    ));
+
+                    if let Some(r) = report.filter(|r| r.ip != r.target) {
+                        let related = [crate::cases::RelatedTarget {
+                            source: r.target.clone(),
+                            source_type: TargetType::Domain,
+                            name: r.ip.clone(),
+                            target_type: TargetType::IP,
+                            relation: "resuelve_a".to_string(),
+                        }];
+                        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                            eprintln!("ERROR [orchestrator]: No se pudo vincular {}: {}", r.ip, e);
+                        }
+                    }
+                }
+                "inspeccionar_tls" | "tls" | "certificado" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let port = call
+                        .arguments
+                        .get("port")
+                        .and_then(|p| p.trim().parse().ok());
+                    let add_targets = call
+                        .arguments
+                        .get("crear_objetivos")
+                        .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"));
+                    let config = self.current_config().await;
+                    let res = crate::tools::inspect_tls(&target, port, &config).await;
+
+                    let report = serde_json::from_str::<crate::tls::TlsReport>(&res.data).ok();
+                    let name = report
+                        .as_ref()
+                        .map(|r| r.host.clone())
+                        .unwrap_or_else(|| target.trim().to_string());
+                    let target_type = if name.parse::<std::net::IpAddr>().is_ok() {
+                        TargetType::IP
+                    } else {
+                        TargetType::Domain
+                    };
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type.clone(),
+                        "tls",
+                        res,
+                    ));
+
+                    // Los SANs sólo se convierten en objetivos si el agente lo pide explícitamente
+                    if let Some(report) = report.filter(|_| add_targets) {
+                        let related: Vec<crate::cases::RelatedTarget> = report
+                            .san_domains
+                            .iter()
+                            .map(|domain| crate::cases::RelatedTarget {
+                                source: report.host.clone(),
+                                source_type: target_type.clone(),
+                                name: domain.clone(),
+                                target_type: TargetType::Domain,
+                                relation: "san_certificado".to_string(),
+                            })
+                            .collect();
+                        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                            eprintln!("ERROR [orchestrator]: No se pudieron crear los SANs: {}", e);
+                        }
+                    }
+                }
+                "consultar_whois" | "whois" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::perform_whois(&target, &config).await;
+
+                    // Nombre y tipo salen del registro normalizado por el cliente WHOIS, así
+                    // "WWW.Ejemplo.com" y "ejemplo.com" quedan en el mismo objetivo
+                    let record = serde_json::from_str::<crate::whois::WhoisRecord>(&res.data).ok();
+                    let name = record
+                        .as_ref()
+                        .map(|r| r.query.clone())
+                        .filter(|q| !q.is_empty())
+                        .unwrap_or_else(|| target.trim().to_string());
+                    let target_type = match record.map(|r| r.kind) {
+                        Some(crate::whois::WhoisQueryKind::Ip) => TargetType::IP,
+                        Some(crate::whois::WhoisQueryKind::Domain) => TargetType::Domain,
+                        _ => TargetType::Other,
+                    };
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type,
+                        "whois",
+                        res,
+                    ));
+                }
+                "huella_http" | "http_fingerprint" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::http_fingerprint(&target, &config).await;
+
+                    let report =
+                        serde_json::from_str::<crate::fingerprint::HttpFingerprint>(&res.data).ok();
+                    let name = report
+                        .as_ref()
+                        .and_then(|r| r.host())
+                        .unwrap_or_else(|| target.trim().to_string());
+                    let target_type = if name.parse::<std::net::IpAddr>().is_ok() {
+                        TargetType::IP
+                    } else {
+                        TargetType::Domain
+                    };
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type.clone(),
+                        "http",
+                        res,
+                    ));
+
+                    if let Some(report) = report {
+                        if let Some(final_host) = report.final_host().filter(|h| *h != name) {
+                            let final_type = if final_host.parse::<std::net::IpAddr>().is_ok() {
+                                TargetType::IP
+                            } else {
+                                TargetType::Domain
+                            };
+                            let related = [crate::cases::RelatedTarget {
+                                source: name.clone(),
+                                source_type: target_type.clone(),
+                                name: final_host,
+                                target_type: final_type,
+                                relation: "redirige_a".to_string(),
+                            }];
+                            if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                                eprintln!(
+                                    "ERROR [orchestrator]: No se pudo vincular la redirección: {}",
+                                    e
+                                );
+                            }
+                        }
+
+                        let ids: Vec<String> =
+                            report.tracking_ids.iter().map(|t| t.id.clone()).collect();
+                        let shared = self.correlate_attribute(
+                            case_name,
+                            &name,
+                            target_type,
+                            "tracking_id",
+                            &ids,
+                            "comparte_tracking_id",
+                        );
+                        if !shared.is_empty() {
+                            tool_results.push(
+                                serde_json::json!({
+                                    "status": "OK",
+                                    "message": format!(
+                                        "IDs de seguimiento compartidos con otros objetivos del caso: {}",
+                                        shared.join(", ")
+                                    )
+                                })
+                                .to_string(),
+                            );
+                        }
+                    }
+                }
+                "geolocalizar_ip" | "ip_intel" | "geoip" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("ip"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let resource_dir = self.app.path().resource_dir().unwrap_or_default();
+                    let res = crate::tools::ip_intel(target.clone(), &resource_dir, &config).await;
+                    let coordinates =
+                        serde_json::from_str::<crate::geoip::IpIntelReport>(&res.data)
+                            .ok()
+                            .and_then(|r| r.location)
+                            .and_then(|l| {
+                                Some(TargetType::location_name(l.latitude?, l.longitude?))
+                            });
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        target.trim(),
+                        TargetType::IP,
+                        "geolocalizacion",
+                        res,
+                    ));
+                    // Solo con una base de ciudades: la de Tor no trae coordenadas
+                    if let Some(coordinates) = coordinates {
+                        let related = [crate::cases::RelatedTarget {
+                            source: target.trim().to_string(),
+                            source_type: TargetType::IP,
+                            name: coordinates,
+                            target_type: TargetType::Location,
+                            relation: "geolocalizada_en".to_string(),
+                        }];
+                        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                            eprintln!(
+                                "ERROR [orchestrator]: No se pudo vincular la ubicación: {}",
+                                e
+                            );
+                        }
+                    }
+                }
+                "buscar_filtraciones" | "leaks" | "hibp" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let by_domain = call
+                        .arguments
+                        .get("alcance")
+                        .is_some_and(|a| a.eq_ignore_ascii_case("dominio"));
+                    let config = self.current_config().await;
+                    let registered = if by_domain {
+                        crate::hibp::lookup_domain(&target, &config)
+                            .await
+                            .and_then(|report| {
+                                let events = crate::hibp::register_domain(
+                                    &self.case_manager,
+                                    case_name,
+                                    &report,
+                                )?;
+                                Ok(serde_json::json!({
+                                    "status": "OK",
+                                    "message": format!(
+                                        "{} cuentas de {} aparecen en {} brechas ({} eventos nuevos en la cronología).",
+                                        report.accounts.len(),
+                                        report.domain,
+                                        report.breaches.len(),
+                                        events
+                                    )
+                                }))
+                            })
+                    } else {
+                        crate::hibp::lookup_account(&target, &config)
+                            .await
+                            .and_then(|report| {
+                                let events =
+                                    crate::hibp::register(&self.case_manager, case_name, &report)?;
+                                let mut detail = report.case_details();
+                                detail["cuenta"] = serde_json::json!(report.account);
+                                detail["eventos_nuevos"] = serde_json::json!(events);
+                                if !report.notes.is_empty() {
+                                    detail["notas"] = serde_json::json!(report.notes);
+                                }
+                                Ok(detail)
+                            })
+                    };
+                    tool_results.push(
+                        registered
+                            .unwrap_or_else(
+                                |e| serde_json::json!({ "status": "ERROR", "message": e }),
+                            )
+                            .to_string(),
+                    );
+                }
+                "consultar_virustotal" | "virustotal" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::virus_total_scan(target, &config).await;
+                    let Some(report) =
+                        serde_json::from_str::<crate::virustotal::VtReport>(&res.data).ok()
+                    else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                        continue;
+                    };
+                    // Las URLs se guardan en su dominio; el resto en el propio objetivo
+                    let (name, target_type) = match report.kind {
+                        crate::virustotal::VtKind::Url => (
+                            crate::wayback::target_host(&report.target),
+                            TargetType::Domain,
+                        ),
+                        crate::virustotal::VtKind::Ip => (report.target.clone(), TargetType::IP),
+                        crate::virustotal::VtKind::File => {
+                            (report.target.clone(), TargetType::Hash)
+                        }
+                        crate::virustotal::VtKind::Domain => {
+                            (report.target.clone(), TargetType::Domain)
+                        }
+                    };
+                    tool_results.push(
+                        serde_json::json!({ "status": "OK", "message": report.verdict() })
+                            .to_string(),
+                    );
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type,
+                        "virustotal",
+                        res,
+                    ));
+                    if report.kind != crate::virustotal::VtKind::Url {
+                        if let Err(e) = self
+                            .case_manager
+                            .link_related(case_name, &report.related_targets())
+                        {
+                            eprintln!(
+                                "ERROR [orchestrator]: No se pudieron vincular los pivotes de VirusTotal: {}",
+                                e
+                            );
+                        }
+                    }
+                }
+                "consultar_shodan" | "shodan" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("ip"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::shodan_intel(target, &config).await;
+                    let Some(host) =
+                        serde_json::from_str::<crate::shodan::ShodanHost>(&res.data).ok()
+                    else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                        continue;
+                    };
+                    if let Err(e) = crate::shodan::register(&self.case_manager, case_name, &host) {
+                        eprintln!("ERROR [orchestrator]: No se pudo guardar shodan: {}", e);
+                    }
+                    let mut summary = host.case_details();
+                    summary["ip"] = serde_json::json!(host.ip);
+                    tool_results.push(summary.to_string());
+                }
+                "infraestructura_ip" | "ip_infra" | "asn" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("ip"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::ip_infrastructure(&target, &config).await;
+                    let Some(report) =
+                        serde_json::from_str::<crate::asn::IpInfraReport>(&res.data).ok()
+                    else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                        continue;
+                    };
+                    match crate::asn::register(&self.case_manager, case_name, &report) {
+                        Ok(same_asn) if !same_asn.is_empty() => tool_results.push(
+                            serde_json::json!({
+                                "status": "OK",
+                                "message": format!("Comparten ASN en el caso: {}", same_asn.join(", "))
+                            })
+                            .to_string(),
+                        ),
+                        Ok(_) => {}
+                        Err(e) => eprintln!(
+                            "ERROR [orchestrator]: No se pudo guardar la infraestructura: {}",
+                            e
+                        ),
+                    }
+                    tool_results.push(crate::tools::clean_technical_noise(
+                        &res.data,
+                        "infraestructura",
+                    ));
+                }
+                "enumerar_dns" | "dns" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::perform_dns_lookup(&target, &config).await;
+
+                    let report = serde_json::from_str::<crate::dns::DnsReport>(&res.data).ok();
+                    let target_type = if target.trim().parse::<std::net::IpAddr>().is_ok() {
+                        TargetType::IP
+                    } else {
+                        TargetType::Domain
+                    };
+                    let name = report
+                        .as_ref()
+                        .map(|r| r.query.clone())
+                        .unwrap_or_else(|| target.clone());
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        target_type,
+                        "dns",
+                        res,
+                    ));
+
+                    if let Some(report) = report {
+                        if let Err(e) = self
+                            .case_manager
+                            .link_related(case_name, &report.related_targets())
+                        {
+                            eprintln!(
+                                "ERROR [orchestrator]: No se pudieron vincular objetivos DNS: {}",
+                                e
+                            );
+                        }
+                    }
+                }
+                "descubrir_subdominios" | "subdomains" => {
+                    let target = call.arguments.get("target").cloned().unwrap_or_default();
+                    let options = crate::subdomains::SubdomainOptions {
+                        wordlist: call.arguments.get("wordlist").cloned(),
+                        concurrency: call
+                            .arguments
+                            .get("concurrency")
+                            .and_then(|c| c.trim().parse().ok()),
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::discover_subdomains(&target, &options, &config).await;
+
+                    let report =
+                        serde_json::from_str::<crate::subdomains::SubdomainReport>(&res.data).ok();
+                    let name = report
+                        .as_ref()
+                        .map(|r| r.domain.clone())
+                        .unwrap_or_else(|| target.clone());
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        TargetType::Domain,
+                        "subdominios",
+                        res,
+                    ));
+
+                    if let Some(report) = report {
+                        self.save_subdomains(case_name, &report);
+                    }
+                }
+                "buscar_usuario" | "username_search" | "search_username" => {
+                    let username = call
+                        .arguments
+                        .get("username")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let options = crate::usernames::UsernameOptions {
+                        manifest: call.arguments.get("manifest").cloned(),
+                        categories: call
+                            .arguments
+                            .get("categorias")
+                            .map(|c| {
+                                c.split(',')
+                                    .map(|s| s.trim().to_string())
+                                    .filter(|s| !s.is_empty())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        concurrency: call
+                            .arguments
+                            .get("concurrency")
+                            .and_then(|c| c.trim().parse().ok()),
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::search_username(&username, &options, &config).await;
+
+                    let report =
+                        serde_json::from_str::<crate::usernames::UsernameReport>(&res.data).ok();
+                    let name = report
+                        .as_ref()
+                        .map(|r| r.username.clone())
+                        .unwrap_or_else(|| username.trim().to_string());
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        TargetType::Username,
+                        "usuarios",
+                        res,
+                    ));
+
+                    // Con una persona indicada, las cuentas encontradas pasan a sus redes sociales
+                    let person_id = call.arguments.get("person_id").map(|p| p.trim());
+                    if let (Some(report), Some(person_id)) =
+                        (report, person_id.filter(|p| !p.is_empty()))
+                    {
+                        let added = self.save_social_profiles(
+                            case_name,
+                            person_id,
+                            report.social_profiles(),
+                        );
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "OK",
+                                "message": format!(
+                                    "{} perfil(es) nuevo(s) agregado(s) a la persona {}.",
+                                    added, person_id
+                                )
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                "buscar_emails_dominio" | "hunter" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("dominio"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let limit = call
+                        .arguments
+                        .get("limite")
+                        .and_then(|l| l.trim().parse().ok());
+                    let config = self.current_config().await;
+                    let result = crate::hunter::domain_search(&target, limit, &config)
+                        .await
+                        .and_then(|report| {
+                            let (emails, persons) = crate::hunter::register(
+                                &self.case_manager,
+                                case_name,
+                                &report.domain,
+                                report.organization.as_deref(),
+                                &report.emails,
+                            )?;
+                            Ok(serde_json::json!({
+                                "dominio": report.domain,
+                                "organizacion": report.organization,
+                                "patron": report.pattern,
+                                "acepta_todo": report.accept_all,
+                                "total_conocidos": report.total,
+                                "emails": report.emails.iter().map(|e| format!(
+                                    "{} ({}%{})",
+                                    e.email,
+                                    e.confidence,
+                                    e.position.as_ref().map(|p| format!(", {}", p)).unwrap_or_default()
+                                )).collect::<Vec<_>>(),
+                                "guardados": emails,
+                                "personas_nuevas": persons,
+                            }))
+                        });
+                    tool_results.push(
+                        result
+                            .unwrap_or_else(
+                                |e| serde_json::json!({ "status": "ERROR", "message": e }),
+                            )
+                            .to_string(),
+                    );
+                }
+                "encontrar_email" | "email_finder" => {
+                    let arg = |key: &str| call.arguments.get(key).cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let with_pattern = arg("patron").trim().eq_ignore_ascii_case("true");
+                    let result = crate::hunter::find_email(
+                        &arg("dominio"),
+                        &arg("nombre"),
+                        &arg("apellido"),
+                        with_pattern,
+                        &config,
+                    )
+                    .await
+                    .and_then(|result| {
+                        if let Some(email) = &result.email {
+                            crate::hunter::register(
+                                &self.case_manager,
+                                case_name,
+                                &result.domain,
+                                result.organization.as_deref(),
+                                std::slice::from_ref(email),
+                            )?;
+                        }
+                        serde_json::to_value(&result).map_err(|e| e.to_string())
+                    });
+                    tool_results.push(
+                        result
+                            .unwrap_or_else(
+                                |e| serde_json::json!({ "status": "ERROR", "message": e }),
+                            )
+                            .to_string(),
+                    );
+                }
+                "verificar_email" | "email_verify" => {
+                    let email = call
+                        .arguments
+                        .get("email")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::verify_email(&email, &config).await;
+
+                    let report = serde_json::from_str::<crate::email::EmailReport>(&res.data).ok();
+                    match report.filter(|r| r.syntax_valid) {
+                        Some(report) => tool_results.push(self.persist_tool_result(
+                            case_name,
+                            &report.email,
+                            TargetType::Email,
+                            "verificacion",
+                            res,
+                        )),
+                        // Una dirección mal formada no merece un objetivo: sólo se informa
+                        None => tool_results.push(crate::tools::clean_technical_noise(
+                            &res.data,
+                            "verificacion",
+                        )),
+                    }
+                }
+                "analizar_telefono" | "phone" | "telefono" => {
+                    let number = call
+                        .arguments
+                        .get("number")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let region = call.arguments.get("region").map(|r| r.as_str());
+                    let res = crate::tools::analyze_phone(&number, region);
+
+                    let report = serde_json::from_str::<crate::phone::PhoneReport>(&res.data).ok();
+                    match report.filter(|r| r.valid) {
+                        Some(report) => tool_results.push(self.persist_tool_result(
+                            case_name,
+                            &report.e164,
+                            TargetType::Phone,
+                            "telefono",
+                            res,
+                        )),
+                        None if res.success => tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": format!("'{}' no es un número válido para la región indicada.", number.trim())
+                            })
+                            .to_string(),
+                        ),
+                        None => tool_results.push(self.persist_tool_result(
+                            case_name,
+                            &number,
+                            TargetType::Phone,
+                            "telefono",
+                            res,
+                        )),
+                    }
+                }
+                "extraer_metadatos" | "metadata" | "metadatos" => {
+                    let path = call
+                        .arguments
+                        .get("path")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let res = crate::tools::extract_metadata(path.clone()).await;
+
+                    let report =
+                        serde_json::from_str::<crate::metadata::MetadataReport>(&res.data).ok();
+                    let name = TargetType::file_name(
+                        &path,
+                        report
+                            .as_ref()
+                            .map(|r| r.sha256.as_str())
+                            .unwrap_or_default(),
+                    );
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        TargetType::File,
+                        "metadatos",
+                        res,
+                    ));
+
+                    let Some(report) = report else {
+                        continue;
+                    };
+                    if let Some(device) = report.camera.as_ref().and_then(|c| c.device_id()) {
+                        let shared = self.correlate_attribute(
+                            case_name,
+                            &name,
+                            TargetType::File,
+                            "dispositivo",
+                            &[device],
+                            "mismo_dispositivo",
+                        );
+                        if !shared.is_empty() {
+                            tool_results.push(
+                                serde_json::json!({
+                                    "status": "OK",
+                                    "message": format!(
+                                        "Archivos del caso tomados con el mismo dispositivo: {}",
+                                        shared.join(", ")
+                                    )
+                                })
+                                .to_string(),
+                            );
+                        }
+                    }
+                    if let Some(gps) = &report.gps {
+                        tool_results.push(self.save_location(
+                            case_name,
+                            &name,
+                            &report.sha256,
+                            gps,
+                        ));
+                    }
+
+                    // Servidores y hosts embebidos: dicen dónde se armó el documento
+                    let hosts: Vec<crate::cases::RelatedTarget> = report
+                        .embedded_hosts
+                        .iter()
+                        .map(|host| crate::cases::RelatedTarget {
+                            source: name.clone(),
+                            source_type: TargetType::File,
+                            name: host.clone(),
+                            target_type: if host.parse::<std::net::IpAddr>().is_ok() {
+                                TargetType::IP
+                            } else {
+                                TargetType::Domain
+                            },
+                            relation: "host_embebido".to_string(),
+                        })
+                        .collect();
+                    if let Err(e) = self.case_manager.link_related(case_name, &hosts) {
+                        eprintln!(
+                            "ERROR [orchestrator]: No se pudieron vincular los hosts embebidos: {}",
+                            e
+                        );
+                    }
+
+                    // Los autores no se dan de alta solos: el nombre puede ser un alias o
+                    // un usuario de equipo; se ofrecen para que se confirmen
+                    if !report.person_candidates.is_empty() {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "OK",
+                                "message": format!(
+                                    "Posibles personas (autores/editores del documento): {}. Usa guardar_hallazgo con target_type 'Person' para confirmarlas.",
+                                    report.person_candidates.join(", ")
+                                )
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                "calcular_hashes" | "hash_file" | "hashes" => {
+                    let path = call
+                        .arguments
+                        .get("path")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::hash_file(&path, &config).await;
+
+                    let report = serde_json::from_str::<crate::hashing::HashReport>(&res.data).ok();
+                    let name = TargetType::file_name(
+                        &path,
+                        report
+                            .as_ref()
+                            .map(|r| r.sha256.as_str())
+                            .unwrap_or_default(),
+                    );
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        TargetType::File,
+                        "hashes",
+                        res,
+                    ));
+                    if let Some(report) = report {
+                        tool_results.extend(self.register_file_hashes(case_name, &name, &report));
+                    }
+                }
+                "agregar_imagen" | "image_hash" | "imagen" => {
+                    let source = call
+                        .arguments
+                        .get("source")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let store_dir = self.case_manager.get_case_path(case_name).join("imagenes");
+                    let res = crate::tools::analyze_image(&source, Some(&store_dir), &config).await;
+
+                    let report =
+                        serde_json::from_str::<crate::imagehash::ImageReport>(&res.data).ok();
+                    let name = TargetType::file_name(
+                        &source,
+                        report
+                            .as_ref()
+                            .map(|r| r.sha256.as_str())
+                            .unwrap_or_default(),
+                    );
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        &name,
+                        TargetType::File,
+                        "imagen",
+                        res,
+                    ));
+                    let Some(report) = report else {
+                        continue;
+                    };
+
+                    if let Some(owner) =
+                        call.arguments.get("owner").filter(|o| !o.trim().is_empty())
+                    {
+                        let owner_type = call
+                            .arguments
+                            .get("owner_type")
+                            .map(|t| TargetType::from_name(t))
+                            .unwrap_or(TargetType::Username);
+                        let related = [crate::cases::RelatedTarget {
+                            source: name.clone(),
+                            source_type: TargetType::File,
+                            name: owner.trim().to_string(),
+                            target_type: owner_type,
+                            relation: "imagen_de".to_string(),
+                        }];
+                        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                            eprintln!("ERROR [orchestrator]: No se pudo vincular la imagen: {}", e);
+                        }
+                    }
+                    if let Some(message) = self.cluster_images(case_name, &name, &report.hashes) {
+                        tool_results.push(message);
+                    }
+                }
+                "comparar_rostros" | "face_compare" | "rostros" => {
+                    let source = call
+                        .arguments
+                        .get("source")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let config = self.current_config().await;
+                    let models_dir = crate::faces::models_dir(
+                        &self.app.path().app_data_dir().unwrap_or_default(),
+                    );
+                    let res = crate::tools::analyze_faces(&source, &models_dir, &config).await;
+                    if !res.success {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                        continue;
+                    }
+                    let Some(report) =
+                        serde_json::from_str::<crate::faces::FaceReport>(&res.data).ok()
+                    else {
+                        continue;
+                    };
+
+                    let name = TargetType::file_name(&source, &report.sha256);
+                    let matches =
+                        crate::faces::register(&self.case_manager, case_name, &name, &report)
+                            .unwrap_or_else(|e| {
+                                eprintln!(
+                                    "ERROR [orchestrator]: No se pudo guardar rostros: {}",
+                                    e
+                                );
+                                Vec::new()
+                            });
+                    tool_results.push(
+                        serde_json::json!({
+                            "status": "OK",
+                            "message": format!(
+                                "{} rostro(s) detectado(s) en {}. Umbral de coincidencia: {:.2}.",
+                                report.faces.len(),
+                                name,
+                                crate::faces::MATCH_THRESHOLD
+                            ),
+                            "rostros": report.faces.iter().map(|f| &f.bbox).collect::<Vec<_>>(),
+                            "coincidencias": matches,
+                        })
+                        .to_string(),
+                    );
+                }
+                "buscar_web" | "web_search" | "search" => {
+                    let query = call
+                        .arguments
+                        .get("query")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let options = crate::websearch::SearchOptions {
+                        engines: call
+                            .arguments
+                            .get("motores")
+                            .map(|m| {
+                                m.split(',')
+                                    .filter_map(crate::websearch::SearchEngine::from_name)
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        pages: call
+                            .arguments
+                            .get("paginas")
+                            .and_then(|p| p.trim().parse().ok()),
+                        language: call.arguments.get("idioma").cloned(),
+                        region: call.arguments.get("region").cloned(),
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::web_scrape_search(&query, &options, &config).await;
+                    // Los resultados no se guardan solos: el agente decide qué vale como hallazgo
+                    if res.success {
+                        tool_results.push(res.data);
+                    } else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                "historial_web" | "wayback" | "web_history" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("url"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let options = crate::wayback::WaybackOptions {
+                        match_type: call
+                            .arguments
+                            .get("alcance")
+                            .and_then(|m| crate::wayback::MatchType::from_name(m)),
+                        from: call.arguments.get("desde").cloned(),
+                        to: call.arguments.get("hasta").cloned(),
+                        limit: None,
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::wayback_history(&target, &options, &config).await;
+                    // Al caso va el resumen; las capturas completas solo en esta respuesta
+                    match serde_json::from_str::<crate::wayback::WaybackReport>(&res.data) {
+                        Ok(report) => {
+                            if let Err(e) = self.case_manager.save_tool_details(
+                                case_name,
+                                &crate::wayback::target_host(&target),
+                                TargetType::Domain,
+                                "wayback",
+                                report.case_details(),
+                            ) {
+                                eprintln!(
+                                    "ERROR [orchestrator]: No se pudo guardar wayback: {}",
+                                    e
+                                );
+                            }
+                            tool_results
+                                .push(crate::tools::clean_technical_noise(&res.data, "wayback"));
+                        }
+                        Err(_) => tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        ),
+                    }
+                }
+                "leer_captura" | "wayback_snapshot" => {
+                    let url = call
+                        .arguments
+                        .get("url")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let timestamp = call.arguments.get("timestamp").cloned().unwrap_or_default();
+                    let config = self.current_config().await;
+                    let res = crate::tools::wayback_snapshot(&url, &timestamp, &config).await;
+                    if res.success {
+                        tool_results.push(res.data);
+                    } else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                "ejecutar_dorks" | "run_dorks" | "dorks" => {
+                    let target = call
+                        .arguments
+                        .get("target")
+                        .or_else(|| call.arguments.get("query"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let options = crate::dorks::DorkOptions {
+                        kind: call
+                            .arguments
+                            .get("tipo")
+                            .and_then(|t| crate::dorks::DorkKind::from_name(t)),
+                        categories: call
+                            .arguments
+                            .get("categorias")
+                            .map(|c| {
+                                c.split(',')
+                                    .map(|s| s.trim().to_string())
+                                    .filter(|s| !s.is_empty())
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        ..Default::default()
+                    };
+                    let config = self.current_config().await;
+                    let res = crate::tools::run_dorks(&target, &options, &config).await;
+                    if res.success {
+                        tool_results.push(res.data);
+                    } else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                    }
+                }
+                "promover_dork" | "promote_dork" => {
+                    let arg = |key: &str| call.arguments.get(key).cloned().unwrap_or_default();
+                    let target = arg("target");
+                    let kind = crate::dorks::DorkKind::from_name(&arg("tipo"))
+                        .unwrap_or_else(|| crate::dorks::DorkKind::detect(&target));
+                    let category = arg("categoria");
+                    let hit = crate::dorks::DorkHit {
+                        title: arg("title"),
+                        url: arg("url").trim().to_string(),
+                        snippet: arg("snippet"),
+                        engines: Vec::new(),
+                        categories: if category.is_empty() {
+                            Vec::new()
+                        } else {
+                            vec![category]
+                        },
+                        dorks: Vec::new(),
+                    };
+                    let message = if hit.url.is_empty() || target.trim().is_empty() {
+                        serde_json::json!({"status": "ERROR", "message": "Faltan target o url."})
+                    } else {
+                        match crate::dorks::promote(
+                            &self.case_manager,
+                            case_name,
+                            &target,
+                            kind,
+                            &hit,
+                        ) {
+                            Ok(()) => serde_json::json!({
+                                "status": "OK",
+                                "message": format!("Resultado guardado como evidencia de {}.", target.trim())
+                            }),
+                            Err(e) => serde_json::json!({"status": "ERROR", "message": e}),
+                        }
+                    };
+                    tool_results.push(message.to_string());
+                }
+                "buscar_darkweb" | "dark_search" | "darkweb" => {
+                    let query = call
+                        .arguments
+                        .get("query")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let res = crate::tools::dark_search(&query).await;
+                    tool_results.push(self.persist_tool_result(
+                        case_name,
+                        query.trim(),
+                        TargetType::Other,
+                        "darkweb",
+                        res,
+                    ));
+                }
+                "leer_onion" | "fetch_onion" | "onion" => {
+                    let url = call
+                        .arguments
+                        .get("url")
+                        .or_else(|| call.arguments.get("target"))
+                        .cloned()
+                        .unwrap_or_default();
+                    let res = crate::tools::fetch_onion(&url).await;
+                    let page = serde_json::from_str::<crate::darkweb::OnionPage>(&res.data).ok();
+                    // Un servicio .onion es un dominio más del caso, identificado por su host
+                    let host = |u: &str| {
+                        reqwest::Url::parse(u)
+                            .ok()
+                            .and_then(|u| u.host_str().map(str::to_string))
+                    };
+                    let name = page
+                        .as_ref()
+                        .and_then(|p| host(&p.url))
+                        .unwrap_or_else(|| url.trim().to_string());
+                    let Some(page) = page else {
+                        tool_results.push(
+                            serde_json::json!({
+                                "status": "ERROR",
+                                "message": res.error.unwrap_or_default()
+                            })
+                            .to_string(),
+                        );
+                        continue;
+                    };
+                    if let Err(e) = self.case_manager.save_tool_details(
+                        case_name,
+                        &name,
+                        TargetType::Domain,
+                        "onion",
+                        page.case_details(),
+                    ) {
+                        eprintln!("ERROR [orchestrator]: No se pudo guardar onion: {}", e);
+                    }
+                    tool_results.push(crate::tools::clean_technical_noise(&res.data, "onion"));
+
+                    let mut related: Vec<crate::cases::RelatedTarget> = Vec::new();
+                    for link_host in page.onion_links.iter().filter_map(|l| host(l)) {
+                        if related.iter().any(|r| r.name == link_host) {
+                            continue;
+                        }
+                        related.push(crate::cases::RelatedTarget {
+                            source: name.clone(),
+                            source_type: TargetType::Domain,
+                            name: link_host,
+                            target_type: TargetType::Domain,
+                            relation: "enlaza_a".to_string(),
+                        });
+                    }
+                    if let Err(e) = self.case_manager.link_related(case_name, &related) {
+                        eprintln!(
+                            "ERROR [orchestrator]: No se pudieron vincular los .onion: {}",
+                            e
+                        );
+                    }
+                }
                 "guardar_hallazgo" | "upsert_intelligence" => {
                     let name = call.arguments.get("name").cloned().unwrap_or_default();
                     let t_type_str = call
@@ -234,17 +1472,28 @@ impl Orchestrator {
                         .cloned()
                         .unwrap_or_else(|| "{}".to_string());
 
-                    let t_type = match t_type_str.as_str() {
-                        "Domain" => TargetType::Domain,
-                        "IP" => TargetType::IP,
-                        "Email" => TargetType::Email,
-                        "Person" => TargetType::Person,
-                        _ => TargetType::Other,
-                    };
+                    let t_type = TargetType::from_name(&t_type_str);
 
-                    let data = serde_json::from_str(&attr_json).unwrap_or_default();
+                    let mut data: HashMap<String, serde_json::Value> =
+                        serde_json::from_str(&attr_json).unwrap_or_default();
+                    let mut id = Uuid::new_v4().to_string();
+                    let mut name = name;
+                    // Teléfonos en E.164: el mismo número en otro formato actualiza el existente
+                    if t_type == TargetType::Phone {
+                        if let Some(e164) = crate::phone::normalize(&name) {
+                            name = e164;
+                        }
+                        if let Ok(Some(existing)) =
+                            self.case_manager.find_target_by_name(case_name, &name)
+                        {
+                            id = existing.id;
+                            let mut merged = existing.data;
+                            merged.extend(data);
+                            data = merged;
+                        }
+                    }
                     let target = Target {
-                        id: Uuid::new_v4().to_string(),
+                        id,
                         name,
                         target_type: t_type,
                         category: "Technical".to_string(),
@@ -273,6 +1522,502 @@ impl Orchestrator {
         tool_results
     }
 
+    /// Copia de la configuración OSINT vigente (proxy, API keys).
+    async fn current_config(&self) -> OsintConfig {
+        let config_state = self.app.state::<Arc<Mutex<OsintConfig>>>();
+        let config = config_state.lock().await;
+        config.clone()
+    }
+
+    /// Persiste el resultado JSON de una herramienta nativa en el objetivo correspondiente
+    /// y devuelve el texto que se le reenvía al agente.
+    fn persist_tool_result(
+        &self,
+        case_name: &str,
+        target_name: &str,
+        target_type: TargetType,
+        tool: &str,
+        res: OsintResult,
+    ) -> String {
+        if !res.success {
+            return serde_json::json!({
+                "status": "ERROR",
+                "message": res.error.unwrap_or_default()
+            })
+            .to_string();
+        }
+
+        if let Ok(details) = serde_json::from_str::<serde_json::Value>(&res.data) {
+            if let Err(e) = self.case_manager.save_tool_details(
+                case_name,
+                target_name,
+                target_type,
+                tool,
+                details,
+            ) {
+                eprintln!("ERROR [orchestrator]: No se pudo guardar {}: {}", tool, e);
+            }
+        }
+
+        crate::tools::clean_technical_noise(&res.data, tool)
+    }
+
+    /// Guarda los valores como atributos `key` del objetivo y lo vincula (`relation`) con los
+    /// objetivos del caso que ya tenían el mismo valor. Devuelve las coincidencias como
+    /// "valor → objetivo". Sirve para IDs de seguimiento, dispositivos, etc.
+    fn correlate_attribute(
+        &self,
+        case_name: &str,
+        target_name: &str,
+        target_type: TargetType,
+        key: &str,
+        values: &[String],
+        relation: &str,
+    ) -> Vec<String> {
+        let mut shared = Vec::new();
+        if values.is_empty() {
+            return shared;
+        }
+        let target_id = match self
+            .case_manager
+            .ensure_target(case_name, target_name, target_type)
+        {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!(
+                    "ERROR [orchestrator]: No se pudo obtener el objetivo: {}",
+                    e
+                );
+                return shared;
+            }
+        };
+
+        for value in values {
+            match self
+                .case_manager
+                .find_targets_with_attribute(case_name, key, value)
+            {
+                Ok(others) => {
+                    for (other_id, other_name) in others {
+                        if other_id == target_id {
+                            continue;
+                        }
+                        if let Err(e) = self
+                            .case_manager
+                            .add_link(case_name, &target_id, &other_id, relation)
+                        {
+                            eprintln!(
+                                "ERROR [orchestrator]: No se pudo vincular {}: {}",
+                                other_name, e
+                            );
+                        }
+                        shared.push(format!("{} → {}", value, other_name));
+                    }
+                }
+                Err(e) => eprintln!("ERROR [orchestrator]: Búsqueda de atributos fallida: {}", e),
+            }
+            if let Err(e) =
+                self.case_manager
+                    .add_attribute(case_name, &target_id, key, value, "Technical")
+            {
+                eprintln!("ERROR [orchestrator]: {}", e);
+            }
+        }
+        shared
+    }
+
+    /// Vincula el archivo con sus hashes (objetivos Hash), guarda la reputación de VirusTotal
+    /// en el SHA-256 y lo correlaciona con el resto de los archivos del caso: mismo contenido
+    /// con otro nombre o variantes por similitud del hash difuso. Devuelve los mensajes extra.
+    fn register_file_hashes(
+        &self,
+        case_name: &str,
+        file_name: &str,
+        report: &crate::hashing::HashReport,
+    ) -> Vec<String> {
+        let mut messages = Vec::new();
+        let related: Vec<crate::cases::RelatedTarget> = [
+            (&report.md5, "hash_md5"),
+            (&report.sha1, "hash_sha1"),
+            (&report.sha256, "hash_sha256"),
+        ]
+        .into_iter()
+        .map(|(hash, relation)| crate::cases::RelatedTarget {
+            source: file_name.to_string(),
+            source_type: TargetType::File,
+            name: hash.clone(),
+            target_type: TargetType::Hash,
+            relation: relation.to_string(),
+        })
+        .collect();
+        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+            eprintln!(
+                "ERROR [orchestrator]: No se pudieron vincular los hashes: {}",
+                e
+            );
+        }
+
+        if let Some(vt) = &report.virustotal {
+            let details = serde_json::to_value(vt).unwrap_or_default();
+            if let Err(e) = self.case_manager.save_tool_details(
+                case_name,
+                &report.sha256,
+                TargetType::Hash,
+                "virustotal",
+                details,
+            ) {
+                eprintln!("ERROR [orchestrator]: No se pudo guardar virustotal: {}", e);
+            }
+            messages
+                .push(serde_json::json!({ "status": "OK", "message": vt.verdict() }).to_string());
+        }
+
+        let duplicates = self.correlate_attribute(
+            case_name,
+            file_name,
+            TargetType::File,
+            "sha256",
+            std::slice::from_ref(&report.sha256),
+            "mismo_contenido",
+        );
+        if !duplicates.is_empty() {
+            messages.push(
+                serde_json::json!({
+                    "status": "OK",
+                    "message": format!("Mismo contenido que: {}", duplicates.join(", "))
+                })
+                .to_string(),
+            );
+        }
+
+        let file_id = match self.case_manager.find_target_by_name(case_name, file_name) {
+            Ok(Some(target)) => target.id,
+            _ => return messages,
+        };
+        let mut similar = Vec::new();
+        match self.case_manager.list_attribute_values(case_name, "ssdeep") {
+            Ok(others) => {
+                for (other_id, other_name, other_hash) in others {
+                    if other_id == file_id {
+                        continue;
+                    }
+                    let score = crate::hashing::fuzzy_compare(&report.ssdeep, &other_hash);
+                    if score < crate::hashing::FUZZY_MATCH_THRESHOLD {
+                        continue;
+                    }
+                    if let Err(e) =
+                        self.case_manager
+                            .add_link(case_name, &file_id, &other_id, "similar_a")
+                    {
+                        eprintln!(
+                            "ERROR [orchestrator]: No se pudo vincular {}: {}",
+                            other_name, e
+                        );
+                    }
+                    similar.push(format!("{} ({}%)", other_name, score));
+                }
+            }
+            Err(e) => eprintln!("ERROR [orchestrator]: Búsqueda de atributos fallida: {}", e),
+        }
+        if let Err(e) = self.case_manager.add_attribute(
+            case_name,
+            &file_id,
+            "ssdeep",
+            &report.ssdeep,
+            "Technical",
+        ) {
+            eprintln!("ERROR [orchestrator]: {}", e);
+        }
+        if !similar.is_empty() {
+            messages.push(
+                serde_json::json!({
+                    "status": "OK",
+                    "message": format!("Variantes por hash difuso: {}", similar.join(", "))
+                })
+                .to_string(),
+            );
+        }
+        messages
+    }
+
+    /// Compara la imagen con todas las del caso (atributo `hash_perceptual`). Cada casi-duplicado
+    /// se vincula con la imagen y, además, se vinculan entre sí sus dueños (`imagen_de`):
+    /// la misma foto de perfil en dos cuentas es una pista fuerte de que son la misma persona.
+    fn cluster_images(
+        &self,
+        case_name: &str,
+        image_name: &str,
+        hashes: &crate::imagehash::ImageHashes,
+    ) -> Option<String> {
+        let image_id = match self.case_manager.find_target_by_name(case_name, image_name) {
+            Ok(Some(target)) => target.id,
+            _ => return None,
+        };
+        let owners = |id: &str| {
+            self.case_manager
+                .find_linked(case_name, id, "imagen_de")
+                .unwrap_or_default()
+        };
+
+        let mut matches = Vec::new();
+        let others = self
+            .case_manager
+            .list_attribute_values(case_name, "hash_perceptual")
+            .unwrap_or_else(|e| {
+                eprintln!("ERROR [orchestrator]: Búsqueda de atributos fallida: {}", e);
+                Vec::new()
+            });
+        for (other_id, other_name, value) in others {
+            if other_id == image_id {
+                continue;
+            }
+            let Some(result) = crate::imagehash::ImageHashes::from_attribute(&value)
+                .and_then(|other| crate::imagehash::compare(hashes, &other))
+                .filter(|r| r.near_duplicate)
+            else {
+                continue;
+            };
+            if let Err(e) =
+                self.case_manager
+                    .add_link(case_name, &image_id, &other_id, "imagen_similar")
+            {
+                eprintln!(
+                    "ERROR [orchestrator]: No se pudo vincular {}: {}",
+                    other_name, e
+                );
+            }
+
+            let mut linked_owners = Vec::new();
+            for (owner_id, owner_name) in owners(&image_id) {
+                for (other_owner_id, other_owner_name) in owners(&other_id) {
+                    if owner_id == other_owner_id {
+                        continue;
+                    }
+                    if let Err(e) = self.case_manager.add_link(
+                        case_name,
+                        &owner_id,
+                        &other_owner_id,
+                        "misma_imagen",
+                    ) {
+                        eprintln!("ERROR [orchestrator]: {}", e);
+                    }
+                    linked_owners.push(format!("{} ↔ {}", owner_name, other_owner_name));
+                }
+            }
+            matches.push(serde_json::json!({
+                "imagen": other_name,
+                "similitud": result.similarity,
+                "distancias": {
+                    "ahash": result.ahash_distance,
+                    "dhash": result.dhash_distance,
+                    "phash": result.phash_distance,
+                },
+                "duenos_vinculados": linked_owners,
+            }));
+        }
+
+        if let Err(e) = self.case_manager.add_attribute(
+            case_name,
+            &image_id,
+            "hash_perceptual",
+            &hashes.to_attribute(),
+            "Technical",
+        ) {
+            eprintln!("ERROR [orchestrator]: {}", e);
+        }
+
+        (!matches.is_empty()).then(|| {
+            serde_json::json!({
+                "status": "OK",
+                "message": format!("{} imagen(es) casi idéntica(s) en el caso.", matches.len()),
+                "coincidencias": matches,
+            })
+            .to_string()
+        })
+    }
+
+    /// Registra la posición GPS de un archivo como objetivo Location vinculado al archivo.
+    /// El SHA-256 del archivo queda como atributo de la ubicación para saber de dónde salió.
+    /// Varias fotos pueden caer en las mismas coordenadas: cada una guarda su altitud y hora
+    /// en `archivos`, indexada por SHA-256, sin pisar a las anteriores.
+    fn save_location(
+        &self,
+        case_name: &str,
+        file_name: &str,
+        sha256: &str,
+        gps: &crate::metadata::GpsInfo,
+    ) -> String {
+        let coordinates = gps.coordinates();
+        let mut files = self
+            .case_manager
+            .find_target_by_name(case_name, &coordinates)
+            .ok()
+            .flatten()
+            .and_then(|t| {
+                t.data
+                    .get("detalles_tecnicos")?
+                    .get("ubicacion")?
+                    .get("archivos")?
+                    .as_object()
+                    .cloned()
+            })
+            .unwrap_or_default();
+        let mut file = serde_json::json!({ "file": file_name });
+        if let Some(altitude) = gps.altitude {
+            file["altitude"] = serde_json::json!(altitude);
+        }
+        if let Some(timestamp) = &gps.timestamp {
+            file["timestamp"] = serde_json::json!(timestamp);
+        }
+        files.insert(sha256.to_string(), file);
+        let details = serde_json::json!({
+            "latitude": gps.latitude,
+            "longitude": gps.longitude,
+            "maps_url": gps.maps_url(),
+            "archivos": files,
+        });
+
+        let location_id = match self.case_manager.save_tool_details(
+            case_name,
+            &coordinates,
+            TargetType::Location,
+            "ubicacion",
+            details,
+        ) {
+            Ok(id) => id,
+            Err(e) => {
+                eprintln!(
+                    "ERROR [orchestrator]: No se pudo guardar la ubicación: {}",
+                    e
+                );
+                return serde_json::json!({ "status": "ERROR", "message": e }).to_string();
+            }
+        };
+        if let Err(e) = self.case_manager.add_attribute(
+            case_name,
+            &location_id,
+            "sha256_origen",
+            sha256,
+            "Technical",
+        ) {
+            eprintln!("ERROR [orchestrator]: {}", e);
+        }
+        let related = [crate::cases::RelatedTarget {
+            source: file_name.to_string(),
+            source_type: TargetType::File,
+            name: coordinates.clone(),
+            target_type: TargetType::Location,
+            relation: "tomada_en".to_string(),
+        }];
+        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+            eprintln!(
+                "ERROR [orchestrator]: No se pudo vincular la ubicación: {}",
+                e
+            );
+        }
+
+        serde_json::json!({
+            "status": "OK",
+            "message": format!(
+                "Ubicación GPS {} ({}) guardada desde {} (SHA-256 {}).",
+                coordinates,
+                gps.maps_url(),
+                file_name,
+                sha256
+            )
+        })
+        .to_string()
+    }
+
+    /// Agrega los perfiles a la persona, salteando las URLs que ya tiene. Devuelve cuántos agregó.
+    fn save_social_profiles(
+        &self,
+        case_name: &str,
+        person_id: &str,
+        profiles: Vec<crate::models::SocialProfile>,
+    ) -> usize {
+        let existing: Vec<String> = match self.case_manager.get_persons(case_name) {
+            Ok(persons) => match persons.into_iter().find(|p| p.id == person_id) {
+                Some(person) => person
+                    .social_profiles
+                    .into_iter()
+                    .map(|s| s.url.to_lowercase())
+                    .collect(),
+                None => {
+                    eprintln!("WARN [orchestrator]: Persona {} inexistente", person_id);
+                    return 0;
+                }
+            },
+            Err(e) => {
+                eprintln!(
+                    "ERROR [orchestrator]: No se pudieron leer las personas: {}",
+                    e
+                );
+                return 0;
+            }
+        };
+
+        let mut added = 0;
+        for profile in profiles {
+            if existing.contains(&profile.url.to_lowercase()) {
+                continue;
+            }
+            match self.case_manager.add_social(case_name, person_id, profile) {
+                Ok(_) => added += 1,
+                Err(e) => eprintln!("ERROR [orchestrator]: {}", e),
+            }
+        }
+        added
+    }
+
+    /// Guarda cada subdominio descubierto como objetivo Domain vinculado a su dominio padre.
+    fn save_subdomains(&self, case_name: &str, report: &crate::subdomains::SubdomainReport) {
+        let related: Vec<crate::cases::RelatedTarget> = report
+            .subdomains
+            .iter()
+            .map(|sub| crate::cases::RelatedTarget {
+                source: report.domain.clone(),
+                source_type: TargetType::Domain,
+                name: sub.name.clone(),
+                target_type: TargetType::Domain,
+                relation: "subdominio".to_string(),
+            })
+            .collect();
+        if let Err(e) = self.case_manager.link_related(case_name, &related) {
+            eprintln!(
+                "ERROR [orchestrator]: No se pudieron vincular los subdominios: {}",
+                e
+            );
+            return;
+        }
+
+        // IPs y fuentes de cada subdominio como datos del objetivo ya creado
+        for sub in &report.subdomains {
+            let Ok(Some(mut target)) = self.case_manager.find_target_by_name(case_name, &sub.name)
+            else {
+                continue;
+            };
+            if !sub.ips.is_empty() {
+                target
+                    .data
+                    .insert("ips".to_string(), serde_json::json!(sub.ips));
+            }
+            target
+                .data
+                .insert("fuentes".to_string(), serde_json::json!(sub.sources));
+            let category = target.category.clone();
+            if let Err(e) = self
+                .case_manager
+                .upsert_target_with_cat(case_name, target, &category)
+            {
+                eprintln!(
+                    "ERROR [orchestrator]: No se pudo guardar el subdominio {}: {}",
+                    sub.name, e
+                );
+            }
+        }
+    }
+
     async fn auto_save_findings(
         &self,
         case_name: &str,
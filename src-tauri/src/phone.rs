@@ -0,0 +1,219 @@
+// src-tauri/src/phone.rs
+//
+// Análisis y normalización de números telefónicos.
+//
+// Flujo:
+//   1. Se parsea el número en cualquier formato ("011 15-4123-4567", "+54 9 11 4123 4567",
+//      "(0351) 423-4567"...) con la metadata de libphonenumber. Sin prefijo internacional
+//      se asume Argentina salvo que se indique otra región.
+//   2. Se normaliza a E.164, que es la forma en que se guarda en objetivos y personas:
+//      el mismo número escrito de dos maneras distintas termina siendo uno solo.
+//   3. Se informa país, tipo (móvil/fijo/...) y, para Argentina, característica y localidad
+//      a partir de la tabla interna. La operadora sólo se informa si viene en el propio número
+//      (códigos de selección de operadora); con la portabilidad no hay tabla offline confiable.
+
+use phonenumber::country::Id;
+use phonenumber::{Mode, PhoneNumber, Type};
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_REGION: Id = Id::AR;
+
+/// Características (códigos de área) argentinas más relevantes y su localidad.
+const AR_AREA_CODES: &[(&str, &str)] = &[
+    ("11", "Ciudad de Buenos Aires y AMBA"),
+    ("220", "Merlo (Buenos Aires)"),
+    ("221", "La Plata (Buenos Aires)"),
+    ("223", "Mar del Plata (Buenos Aires)"),
+    ("230", "Pilar (Buenos Aires)"),
+    ("236", "Junín (Buenos Aires)"),
+    ("237", "Moreno (Buenos Aires)"),
+    ("249", "Tandil (Buenos Aires)"),
+    ("260", "San Rafael (Mendoza)"),
+    ("261", "Mendoza"),
+    ("263", "San Martín (Mendoza)"),
+    ("264", "San Juan"),
+    ("266", "San Luis"),
+    ("280", "Trelew y Rawson (Chubut)"),
+    ("291", "Bahía Blanca (Buenos Aires)"),
+    ("294", "San Carlos de Bariloche (Río Negro)"),
+    ("297", "Comodoro Rivadavia (Chubut)"),
+    ("298", "General Roca (Río Negro)"),
+    ("299", "Neuquén"),
+    ("336", "San Nicolás (Buenos Aires)"),
+    ("341", "Rosario (Santa Fe)"),
+    ("342", "Santa Fe"),
+    ("343", "Paraná (Entre Ríos)"),
+    ("345", "Concordia (Entre Ríos)"),
+    ("348", "Escobar (Buenos Aires)"),
+    ("351", "Córdoba"),
+    ("353", "Villa María (Córdoba)"),
+    ("358", "Río Cuarto (Córdoba)"),
+    ("362", "Resistencia (Chaco)"),
+    ("364", "Presidencia Roque Sáenz Peña (Chaco)"),
+    ("370", "Formosa"),
+    ("376", "Posadas (Misiones)"),
+    ("379", "Corrientes"),
+    ("380", "La Rioja"),
+    ("381", "San Miguel de Tucumán"),
+    ("383", "San Fernando del Valle de Catamarca"),
+    ("385", "Santiago del Estero"),
+    ("387", "Salta"),
+    ("388", "San Salvador de Jujuy"),
+    ("2901", "Ushuaia (Tierra del Fuego)"),
+    ("2902", "El Calafate (Santa Cruz)"),
+    ("2920", "Viedma (Río Negro)"),
+    ("2954", "Santa Rosa (La Pampa)"),
+    ("2964", "Río Grande (Tierra del Fuego)"),
+    ("2966", "Río Gallegos (Santa Cruz)"),
+    ("3564", "San Francisco (Córdoba)"),
+    ("3756", "Puerto Iguazú (Misiones)"),
+];
+
+/// Resultado del análisis, listo para `detalles_tecnicos.telefono` del objetivo Phone.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PhoneReport {
+    pub input: String,
+    pub e164: String,
+    pub international: String,
+    pub national: String,
+    pub valid: bool,
+    pub country_code: u16,
+    /// Código ISO de la región (ej: "AR")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    pub number_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrier: Option<String>,
+}
+
+fn parse_region(region: Option<&str>) -> Id {
+    region
+        .map(|r| r.trim().to_uppercase())
+        .and_then(|r| r.parse().ok())
+        .unwrap_or(DEFAULT_REGION)
+}
+
+fn parse_number(input: &str, region: Option<&str>) -> Result<PhoneNumber, String> {
+    let input = input.trim();
+    let input = input.strip_prefix("tel:").unwrap_or(input);
+    if input.chars().filter(char::is_ascii_digit).count() < 6 {
+        return Err(format!("'{}' no parece un número telefónico.", input));
+    }
+    phonenumber::parse(Some(parse_region(region)), input)
+        .map_err(|_| format!("No se pudo interpretar el número '{}'.", input))
+}
+
+fn type_name(kind: Type) -> &'static str {
+    match kind {
+        Type::Mobile => "mobile",
+        Type::FixedLine => "fixed_line",
+        Type::FixedLineOrMobile => "fixed_line_or_mobile",
+        Type::TollFree => "toll_free",
+        Type::PremiumRate => "premium_rate",
+        Type::SharedCost => "shared_cost",
+        Type::Voip => "voip",
+        Type::PersonalNumber => "personal_number",
+        Type::Pager => "pager",
+        Type::Uan => "uan",
+        Type::Voicemail => "voicemail",
+        _ => "unknown",
+    }
+}
+
+/// Característica y localidad argentina a partir del número nacional significativo.
+/// Los móviles llevan un 9 adelante en E.164 (+54 9 11 ...) que no forma parte del área.
+fn ar_area(national: &str) -> Option<(&'static str, &'static str)> {
+    let digits = national.strip_prefix('9').unwrap_or(national);
+    // Prefijo más largo primero: "2901" antes que un hipotético "290"
+    (2..=4).rev().find_map(|len| {
+        let prefix = digits.get(..len)?;
+        AR_AREA_CODES
+            .iter()
+            .find(|(code, _)| *code == prefix)
+            .copied()
+    })
+}
+
+/// Analiza un número en cualquier formato. `region` es el país a asumir si el número
+/// no trae prefijo internacional (por defecto Argentina).
+pub fn analyze(input: &str, region: Option<&str>) -> Result<PhoneReport, String> {
+    let number = parse_number(input, region)?;
+    let valid = phonenumber::is_valid(&number);
+    let region_id = number.country().id();
+    let national = number.national().to_string();
+
+    let (area_code, locality) = match region_id {
+        Some(Id::AR) => ar_area(&national)
+            .map(|(code, place)| (Some(code.to_string()), Some(place.to_string())))
+            .unwrap_or_default(),
+        _ => (None, None),
+    };
+
+    Ok(PhoneReport {
+        input: input.trim().to_string(),
+        e164: number.format().mode(Mode::E164).to_string(),
+        international: number.format().mode(Mode::International).to_string(),
+        national: number.format().mode(Mode::National).to_string(),
+        valid,
+        country_code: number.code().value(),
+        region: region_id.map(|id| id.as_ref().to_string()),
+        number_type: type_name(number.number_type(&phonenumber::metadata::DATABASE)).to_string(),
+        area_code,
+        locality,
+        carrier: number.carrier().map(|c| c.to_string()),
+    })
+}
+
+/// Forma canónica (E.164) para guardar el número; `None` si no es un número válido.
+pub fn normalize(input: &str) -> Option<String> {
+    let number = parse_number(input, None).ok()?;
+    phonenumber::is_valid(&number).then(|| number.format().mode(Mode::E164).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normaliza_formatos_argentinos() {
+        // El mismo celular de Buenos Aires escrito de distintas formas
+        for input in [
+            "+54 9 11 4123-4567",
+            "011 15-4123-4567",
+            "(11) 15 4123 4567",
+            "5491141234567",
+        ] {
+            assert_eq!(
+                normalize(input).as_deref(),
+                Some("+5491141234567"),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            normalize("(0351) 423-4567").as_deref(),
+            Some("+543514234567")
+        );
+        assert_eq!(normalize("123"), None);
+    }
+
+    #[test]
+    fn test_metadatos_del_numero() {
+        let report = analyze("0351 15 412 3456", None).unwrap();
+        assert!(report.valid);
+        assert_eq!(report.e164, "+5493514123456");
+        assert_eq!(report.region.as_deref(), Some("AR"));
+        assert_eq!(report.number_type, "mobile");
+        assert_eq!(report.area_code.as_deref(), Some("351"));
+        assert_eq!(report.locality.as_deref(), Some("Córdoba"));
+
+        let report = analyze("+34 612 34 56 78", None).unwrap();
+        assert_eq!(report.region.as_deref(), Some("ES"));
+        assert_eq!(report.country_code, 34);
+        assert_eq!(report.locality, None);
+    }
+}
@@ -0,0 +1,426 @@
+// src-tauri/src/ping.rs
+//
+// Ping multiplataforma sin depender del comando `ping` ni de su salida localizada.
+//
+// Flujo:
+//   1. Se resuelve el objetivo a una IP.
+//   2. Se intenta ICMP nativo: socket DGRAM sin privilegios (Linux/macOS) y, si el sistema
+//      no lo permite, socket RAW (root / administrador en Windows).
+//   3. Si ICMP no está permitido o el host no responde eco, se hace un "ping" por TCP connect
+//      sobre los puertos elegidos: un SYN-ACK o un RST también prueban que el host está vivo.
+//   4. Se devuelven estadísticas tipadas (RTT, pérdida, TTL) en un `PingReport`.
+
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Protocol, Socket, Type};
+use std::io::ErrorKind;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+const DEFAULT_COUNT: u16 = 4;
+const MAX_COUNT: u16 = 20;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+/// Puertos del fallback TCP cuando no se indican otros
+const DEFAULT_TCP_PORTS: &[u16] = &[443, 80, 22];
+const ICMP_PAYLOAD: &[u8] = b"sodiic-osint-ping-0123456789abcd";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PingMethod {
+    Icmp,
+    Tcp,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingSample {
+    pub seq: u16,
+    /// `None` = sin respuesta dentro del tiempo de espera
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+}
+
+/// Resultado estructurado, listo para `detalles_tecnicos.ping`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingReport {
+    pub target: String,
+    pub ip: String,
+    pub method: PingMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    pub alive: bool,
+    pub transmitted: u16,
+    pub received: u16,
+    pub loss_percent: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_min_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_avg_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_max_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtt_mdev_ms: Option<f64>,
+    /// TTL de la última respuesta ICMP (sólo disponible con sockets RAW)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+    pub samples: Vec<PingSample>,
+    /// Por qué se usó el fallback TCP, si corresponde
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Parámetros opcionales del ping.
+#[derive(Debug, Clone, Default)]
+pub struct PingOptions {
+    pub count: Option<u16>,
+    /// Puertos para el fallback TCP (vacío = `DEFAULT_TCP_PORTS`)
+    pub ports: Vec<u16>,
+    /// Saltar ICMP e ir directo a TCP
+    pub tcp_only: bool,
+}
+
+fn round_ms(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+impl PingReport {
+    /// Calcula pérdida y estadísticas de RTT a partir de las muestras.
+    fn from_samples(
+        target: &str,
+        ip: IpAddr,
+        method: PingMethod,
+        port: Option<u16>,
+        samples: Vec<PingSample>,
+    ) -> Self {
+        let rtts: Vec<f64> = samples.iter().filter_map(|s| s.rtt_ms).collect();
+        let transmitted = samples.len() as u16;
+        let received = rtts.len() as u16;
+        let loss_percent = if transmitted == 0 {
+            100.0
+        } else {
+            round_ms(100.0 * f64::from(transmitted - received) / f64::from(transmitted))
+        };
+
+        let (min, avg, max, mdev) = if rtts.is_empty() {
+            (None, None, None, None)
+        } else {
+            let min = rtts.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = rtts.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let avg = rtts.iter().sum::<f64>() / rtts.len() as f64;
+            let var = rtts.iter().map(|r| (r - avg).powi(2)).sum::<f64>() / rtts.len() as f64;
+            (
+                Some(round_ms(min)),
+                Some(round_ms(avg)),
+                Some(round_ms(max)),
+                Some(round_ms(var.sqrt())),
+            )
+        };
+
+        PingReport {
+            target: target.to_string(),
+            ip: ip.to_string(),
+            method,
+            port,
+            alive: received > 0,
+            transmitted,
+            received,
+            loss_percent,
+            rtt_min_ms: min,
+            rtt_avg_ms: avg,
+            rtt_max_ms: max,
+            rtt_mdev_ms: mdev,
+            ttl: samples.iter().rev().find_map(|s| s.ttl),
+            samples,
+            note: None,
+        }
+    }
+}
+
+/// Checksum de Internet (RFC 1071) para el encabezado ICMPv4.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = 0;
+    for chunk in data.chunks(2) {
+        let word = if chunk.len() == 2 {
+            u16::from_be_bytes([chunk[0], chunk[1]])
+        } else {
+            u16::from_be_bytes([chunk[0], 0])
+        };
+        sum = sum.wrapping_add(u32::from(word));
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// Arma un Echo Request. En ICMPv6 el checksum lo completa el kernel.
+fn build_echo_request(v6: bool, ident: u16, seq: u16) -> Vec<u8> {
+    let mut packet = vec![if v6 { 128 } else { 8 }, 0, 0, 0];
+    packet.extend_from_slice(&ident.to_be_bytes());
+    packet.extend_from_slice(&seq.to_be_bytes());
+    packet.extend_from_slice(ICMP_PAYLOAD);
+    if !v6 {
+        let sum = checksum(&packet);
+        packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+    packet
+}
+
+/// Interpreta una respuesta recibida y devuelve `(seq, ttl)` si es un Echo Reply.
+/// Los sockets RAW de IPv4 (y los DGRAM de macOS) incluyen el encabezado IP: de ahí sale el TTL.
+fn parse_echo_reply(buf: &[u8], v6: bool) -> Option<(u16, Option<u8>)> {
+    let (icmp, ttl) = if !v6 && buf.first().map(|b| b >> 4) == Some(4) {
+        let ihl = usize::from(buf[0] & 0x0f) * 4;
+        if buf.len() < ihl + 8 {
+            return None;
+        }
+        (&buf[ihl..], Some(buf[8]))
+    } else {
+        (buf, None)
+    };
+
+    if icmp.len() < 8 {
+        return None;
+    }
+    let echo_reply = if v6 { 129 } else { 0 };
+    if icmp[0] != echo_reply {
+        return None;
+    }
+    Some((u16::from_be_bytes([icmp[6], icmp[7]]), ttl))
+}
+
+/// Abre un socket ICMP: primero DGRAM (sin privilegios), después RAW.
+fn open_icmp_socket(ip: IpAddr) -> Result<UdpSocket, String> {
+    let (domain, protocol) = match ip {
+        IpAddr::V4(_) => (Domain::IPV4, Protocol::ICMPV4),
+        IpAddr::V6(_) => (Domain::IPV6, Protocol::ICMPV6),
+    };
+    let socket = Socket::new(domain, Type::DGRAM, Some(protocol))
+        .or_else(|_| Socket::new(domain, Type::RAW, Some(protocol)))
+        .map_err(|e| format!("ICMP no permitido en este sistema ({})", e))?;
+    Ok(socket.into())
+}
+
+/// Sesión ICMP bloqueante (se ejecuta en `spawn_blocking`).
+fn icmp_session(ip: IpAddr, count: u16) -> Result<Vec<PingSample>, String> {
+    let socket = open_icmp_socket(ip)?;
+    let v6 = ip.is_ipv6();
+    let dest = SocketAddr::new(ip, 0);
+    let ident = std::process::id() as u16;
+    let mut buf = [0u8; 1500];
+    let mut samples = Vec::new();
+
+    for seq in 0..count {
+        if seq > 0 {
+            std::thread::sleep(PROBE_INTERVAL);
+        }
+        let packet = build_echo_request(v6, ident, seq);
+        let sent_at = Instant::now();
+        if let Err(e) = socket.send_to(&packet, dest) {
+            return Err(format!("No se pudo enviar el eco ICMP: {}", e));
+        }
+
+        let mut sample = PingSample {
+            seq,
+            rtt_ms: None,
+            ttl: None,
+        };
+        // Un socket ICMP recibe también respuestas ajenas: se descartan hasta el timeout
+        loop {
+            let elapsed = sent_at.elapsed();
+            if elapsed >= PROBE_TIMEOUT {
+                break;
+            }
+            let _ = socket.set_read_timeout(Some(PROBE_TIMEOUT - elapsed));
+            match socket.recv_from(&mut buf) {
+                Ok((len, from)) => {
+                    if from.ip() != ip {
+                        continue;
+                    }
+                    if let Some((reply_seq, ttl)) = parse_echo_reply(&buf[..len], v6) {
+                        if reply_seq == seq {
+                            sample.rtt_ms =
+                                Some(round_ms(sent_at.elapsed().as_secs_f64() * 1000.0));
+                            sample.ttl = ttl;
+                            break;
+                        }
+                    }
+                }
+                Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+                Err(e) => return Err(format!("Error leyendo la respuesta ICMP: {}", e)),
+            }
+        }
+        samples.push(sample);
+    }
+
+    Ok(samples)
+}
+
+/// Un intento de conexión TCP: SYN-ACK (conecta) o RST (rechazada) cuentan como respuesta.
+async fn tcp_probe(ip: IpAddr, port: u16) -> Option<f64> {
+    let started = Instant::now();
+    match tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect((ip, port))).await {
+        Ok(Ok(_)) => Some(round_ms(started.elapsed().as_secs_f64() * 1000.0)),
+        Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => {
+            Some(round_ms(started.elapsed().as_secs_f64() * 1000.0))
+        }
+        _ => None,
+    }
+}
+
+/// Ping por TCP connect: elige el primer puerto que responde y mide sobre él.
+async fn tcp_session(ip: IpAddr, count: u16, ports: &[u16]) -> (u16, Vec<PingSample>) {
+    let mut port = ports[0];
+    let mut first = None;
+    for &candidate in ports {
+        if let Some(rtt) = tcp_probe(ip, candidate).await {
+            port = candidate;
+            first = Some(rtt);
+            break;
+        }
+    }
+
+    let mut samples = vec![PingSample {
+        seq: 0,
+        rtt_ms: first,
+        ttl: None,
+    }];
+    for seq in 1..count {
+        tokio::time::sleep(PROBE_INTERVAL).await;
+        samples.push(PingSample {
+            seq,
+            rtt_ms: tcp_probe(ip, port).await,
+            ttl: None,
+        });
+    }
+    (port, samples)
+}
+
+/// Resuelve el objetivo (IP literal o nombre) a la primera dirección disponible.
+async fn resolve_target(target: &str) -> Result<IpAddr, String> {
+    if let Ok(ip) = target.parse::<IpAddr>() {
+        return Ok(ip);
+    }
+    tokio::net::lookup_host((target, 0))
+        .await
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .map(|addr| addr.ip())
+        .ok_or_else(|| format!("No se pudo resolver '{}'.", target))
+}
+
+/// Ping con ICMP nativo y fallback TCP.
+pub async fn ping(target: &str, options: &PingOptions) -> Result<PingReport, String> {
+    let target = target.trim();
+    let ip = resolve_target(target).await?;
+    let count = options.count.unwrap_or(DEFAULT_COUNT).clamp(1, MAX_COUNT);
+    let ports: Vec<u16> = if options.ports.is_empty() {
+        DEFAULT_TCP_PORTS.to_vec()
+    } else {
+        options.ports.clone()
+    };
+
+    let mut note = None;
+    let mut icmp_report = None;
+    if !options.tcp_only {
+        match tokio::task::spawn_blocking(move || icmp_session(ip, count)).await {
+            Ok(Ok(samples)) => {
+                let report = PingReport::from_samples(target, ip, PingMethod::Icmp, None, samples);
+                if report.alive {
+                    return Ok(report);
+                }
+                note = Some("Sin respuesta ICMP (posible filtrado), se probó TCP".to_string());
+                icmp_report = Some(report);
+            }
+            Ok(Err(e)) => {
+                eprintln!("WARN [ping]: {} - usando fallback TCP", e);
+                note = Some(e);
+            }
+            Err(e) => {
+                eprintln!("ERROR [ping]: ICMP task failed: {}", e);
+                note = Some("Falló la sesión ICMP".to_string());
+            }
+        }
+    }
+
+    let (port, samples) = tcp_session(ip, count, &ports).await;
+    let mut report = PingReport::from_samples(target, ip, PingMethod::Tcp, Some(port), samples);
+
+    // Si tampoco responde por TCP, el resultado ICMP (100% de pérdida) es el más representativo
+    if !report.alive {
+        if let Some(icmp) = icmp_report {
+            report = icmp;
+        }
+    }
+    report.note = note;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estadisticas_de_muestras() {
+        let samples = vec![
+            PingSample {
+                seq: 0,
+                rtt_ms: Some(10.0),
+                ttl: Some(57),
+            },
+            PingSample {
+                seq: 1,
+                rtt_ms: None,
+                ttl: None,
+            },
+            PingSample {
+                seq: 2,
+                rtt_ms: Some(20.0),
+                ttl: Some(58),
+            },
+            PingSample {
+                seq: 3,
+                rtt_ms: Some(30.0),
+                ttl: None,
+            },
+        ];
+        let report = PingReport::from_samples(
+            "host",
+            "10.0.0.1".parse().unwrap(),
+            PingMethod::Icmp,
+            None,
+            samples,
+        );
+
+        assert!(report.alive);
+        assert_eq!(report.transmitted, 4);
+        assert_eq!(report.received, 3);
+        assert_eq!(report.loss_percent, 25.0);
+        assert_eq!(report.rtt_min_ms, Some(10.0));
+        assert_eq!(report.rtt_avg_ms, Some(20.0));
+        assert_eq!(report.rtt_max_ms, Some(30.0));
+        assert_eq!(report.ttl, Some(58));
+    }
+
+    #[test]
+    fn test_eco_icmp_con_encabezado_ip() {
+        let request = build_echo_request(false, 0x1234, 7);
+        assert_eq!(checksum(&request), 0);
+
+        // Respuesta RAW: encabezado IPv4 de 20 bytes (TTL 64) + Echo Reply
+        let mut reply = vec![
+            0x45, 0, 0, 0, 0, 0, 0, 0, 64, 1, 0, 0, 8, 8, 8, 8, 10, 0, 0, 1,
+        ];
+        reply.extend_from_slice(&[0, 0, 0, 0, 0x12, 0x34, 0, 7]);
+        assert_eq!(parse_echo_reply(&reply, false), Some((7, Some(64))));
+
+        // Respuesta DGRAM (Linux): sólo el mensaje ICMP, sin TTL
+        assert_eq!(
+            parse_echo_reply(&[0, 0, 0, 0, 0, 1, 0, 3], false),
+            Some((3, None))
+        );
+        assert_eq!(parse_echo_reply(&[8, 0, 0, 0, 0, 1, 0, 3], false), None);
+    }
+}
@@ -0,0 +1,406 @@
+// src-tauri/src/shodan.rs
+//
+// Consulta de hosts en Shodan con la respuesta tipada.
+//
+// Flujo:
+//   1. `/shodan/host/<ip>` con la API key de la configuración. Los errores de Shodan (key
+//      inválida, IP sin datos, plan sin acceso, límite de consultas) se traducen a mensajes
+//      claros en lugar de devolver el JSON de error.
+//   2. Cada elemento de `data` es un servicio: puerto, transporte, producto, versión, CPE,
+//      un extracto del banner y las CVE que Shodan le asocia.
+//   3. Hostnames y dominios se vuelven objetivos vinculados a la IP.
+//   4. En el caso se guarda un resumen compacto (una línea por servicio, sin banners) para
+//      que el contexto del agente muestre los servicios sin arrastrar el JSON completo.
+
+use crate::cases::{CaseManager, RelatedTarget, TargetType};
+use crate::models::OsintConfig;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+const HOST_URL: &str = "https://api.shodan.io/shodan/host";
+/// Largo máximo del extracto de banner por servicio
+const BANNER_EXCERPT_CHARS: usize = 300;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ShodanService {
+    pub port: u16,
+    pub transport: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Módulo de Shodan que lo identificó (http, ssh, https-simple-new...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpe: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub banner: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vulns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+}
+
+impl ShodanService {
+    /// "443/tcp nginx 1.18.0 (CVE-2021-23017)"
+    fn summary(&self) -> String {
+        let mut line = format!("{}/{}", self.port, self.transport);
+        for part in [&self.product, &self.version, &self.module]
+            .into_iter()
+            .flatten()
+            .take(2)
+        {
+            line.push(' ');
+            line.push_str(part);
+        }
+        if !self.vulns.is_empty() {
+            line.push_str(&format!(" ({})", self.vulns.join(", ")));
+        }
+        line
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ShodanHost {
+    pub ip: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asn: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub domains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<u16>,
+    /// Todas las CVE del host (las de cada servicio más las globales)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vulns: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<ShodanService>,
+}
+
+impl ShodanHost {
+    /// Hostnames y dominios como objetivos vinculados a la IP.
+    pub fn related_targets(&self) -> Vec<RelatedTarget> {
+        let from_ip = |name: &str, relation: &str| RelatedTarget {
+            source: self.ip.clone(),
+            source_type: TargetType::IP,
+            name: name.to_string(),
+            target_type: TargetType::Domain,
+            relation: relation.to_string(),
+        };
+        let mut related: Vec<RelatedTarget> = self
+            .hostnames
+            .iter()
+            .map(|h| from_ip(h, "hostname"))
+            .collect();
+        related.extend(
+            self.domains
+                .iter()
+                .filter(|d| !self.hostnames.contains(d))
+                .map(|d| from_ip(d, "dominio_asociado")),
+        );
+        related
+    }
+
+    /// Resumen para `detalles_tecnicos.shodan`: servicios en una línea, sin banners.
+    pub fn case_details(&self) -> serde_json::Value {
+        let mut details = serde_json::Map::new();
+        let mut put = |key: &str, value: serde_json::Value| {
+            let empty = value.is_null()
+                || value.as_array().is_some_and(|a| a.is_empty())
+                || value.as_str().is_some_and(str::is_empty);
+            if !empty {
+                details.insert(key.to_string(), value);
+            }
+        };
+        put("organizacion", serde_json::json!(self.org));
+        put("isp", serde_json::json!(self.isp));
+        put("asn", serde_json::json!(self.asn));
+        put("sistema_operativo", serde_json::json!(self.os));
+        put("puertos", serde_json::json!(self.ports));
+        put(
+            "servicios",
+            serde_json::json!(self
+                .services
+                .iter()
+                .map(ShodanService::summary)
+                .collect::<Vec<_>>()),
+        );
+        put("vulnerabilidades", serde_json::json!(self.vulns));
+        put("etiquetas", serde_json::json!(self.tags));
+        put("hostnames", serde_json::json!(self.hostnames));
+        put("ultima_actualizacion", serde_json::json!(self.last_update));
+        serde_json::Value::Object(details)
+    }
+}
+
+/// Guarda el resumen en el objetivo IP, las CVE como atributos y vincula hostnames y dominios.
+pub fn register(
+    case_manager: &CaseManager,
+    case_name: &str,
+    host: &ShodanHost,
+) -> Result<(), String> {
+    let ip_id = case_manager.save_tool_details(
+        case_name,
+        &host.ip,
+        TargetType::IP,
+        "shodan",
+        host.case_details(),
+    )?;
+    for cve in &host.vulns {
+        case_manager.add_attribute(case_name, &ip_id, "cve", cve, "Technical")?;
+    }
+    case_manager.link_related(case_name, &host.related_targets())?;
+    Ok(())
+}
+
+fn string_list(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn opt_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// CVE como lista (`"vulns": ["CVE-..."]`) o como mapa (`"vulns": {"CVE-...": {...}}`).
+fn vuln_ids(value: &serde_json::Value) -> Vec<String> {
+    let mut ids = match value {
+        serde_json::Value::Object(map) => map.keys().cloned().collect(),
+        _ => string_list(value),
+    };
+    ids.sort();
+    ids
+}
+
+fn parse_service(item: &serde_json::Value) -> Option<ShodanService> {
+    let port = u16::try_from(item["port"].as_u64()?).ok()?;
+    let banner: String = item["data"]
+        .as_str()
+        .unwrap_or_default()
+        .trim()
+        .chars()
+        .take(BANNER_EXCERPT_CHARS)
+        .collect();
+    let mut cpe = string_list(&item["cpe23"]);
+    if cpe.is_empty() {
+        cpe = string_list(&item["cpe"]);
+    }
+    Some(ShodanService {
+        port,
+        transport: opt_string(&item["transport"]).unwrap_or_else(|| "tcp".to_string()),
+        product: opt_string(&item["product"]),
+        version: opt_string(&item["version"]),
+        module: opt_string(&item["_shodan"]["module"]),
+        cpe,
+        banner,
+        vulns: vuln_ids(&item["vulns"]),
+        timestamp: opt_string(&item["timestamp"]),
+    })
+}
+
+/// Convierte la respuesta de `/shodan/host/<ip>` en un `ShodanHost`.
+fn parse_host(body: &str) -> Result<ShodanHost, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(body).map_err(|_| "Respuesta de Shodan inválida".to_string())?;
+    if let Some(error) = json["error"].as_str() {
+        return Err(error.to_string());
+    }
+
+    let mut services: Vec<ShodanService> = json["data"]
+        .as_array()
+        .map(|items| items.iter().filter_map(parse_service).collect())
+        .unwrap_or_default();
+    services.sort_by(|a, b| a.port.cmp(&b.port).then(a.transport.cmp(&b.transport)));
+
+    let mut vulns = vuln_ids(&json["vulns"]);
+    for service in &services {
+        for id in &service.vulns {
+            if !vulns.contains(id) {
+                vulns.push(id.clone());
+            }
+        }
+    }
+    vulns.sort();
+
+    let mut ports: Vec<u16> = json["ports"]
+        .as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|p| u16::try_from(p.as_u64()?).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    ports.extend(services.iter().map(|s| s.port));
+    ports.sort_unstable();
+    ports.dedup();
+
+    Ok(ShodanHost {
+        ip: opt_string(&json["ip_str"]).unwrap_or_default(),
+        org: opt_string(&json["org"]),
+        isp: opt_string(&json["isp"]),
+        asn: opt_string(&json["asn"]),
+        os: opt_string(&json["os"]),
+        country_code: opt_string(&json["country_code"]),
+        city: opt_string(&json["city"]),
+        last_update: opt_string(&json["last_update"]),
+        hostnames: string_list(&json["hostnames"]),
+        domains: string_list(&json["domains"]),
+        tags: string_list(&json["tags"]),
+        ports,
+        vulns,
+        services,
+    })
+}
+
+/// Consulta un host en Shodan.
+pub async fn lookup(ip: &str, config: &OsintConfig) -> Result<ShodanHost, String> {
+    if config.shodan.trim().is_empty() {
+        return Err("API Key de Shodan no configurada.".to_string());
+    }
+    let ip: IpAddr = ip
+        .trim()
+        .parse()
+        .map_err(|_| "Shodan requiere una dirección IP.".to_string())?;
+
+    let client = crate::tools::get_http_client(config).await;
+    let response = client
+        .get(format!("{}/{}", HOST_URL, ip))
+        .query(&[("key", config.shodan.trim())])
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("ERROR [shodan]: API request failed: {}", e);
+            "Hubo un problema al consultar la base de datos de dispositivos conectados.".to_string()
+        })?;
+    let status = response.status().as_u16();
+    let body = response
+        .text()
+        .await
+        .map_err(|_| "Error leyendo Shodan Intel".to_string())?;
+
+    if body.contains("Requires membership") || status == 403 {
+        return Err("Error de Shodan: Tu API Key es válida pero esta consulta requiere una membresía paga (Membership/Pro).".to_string());
+    }
+    match status {
+        401 => return Err("Error de Shodan: la API Key es inválida.".to_string()),
+        404 => return Err(format!("Shodan no tiene información sobre {}.", ip)),
+        429 => {
+            return Err(
+                "Error de Shodan: se alcanzó el límite de consultas. Intente más tarde."
+                    .to_string(),
+            )
+        }
+        _ => {}
+    }
+
+    let mut host = parse_host(&body).map_err(|e| {
+        eprintln!("ERROR [shodan]: {}", e);
+        format!("Error de Shodan: {}", e)
+    })?;
+    if host.ip.is_empty() {
+        host.ip = ip.to_string();
+    }
+    Ok(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = r#"{
+        "ip_str": "93.184.216.34",
+        "org": "Edgecast",
+        "isp": "Edgecast Inc.",
+        "asn": "AS15133",
+        "os": null,
+        "country_code": "US",
+        "last_update": "2024-05-01T10:00:00.000000",
+        "hostnames": ["www.ejemplo.com"],
+        "domains": ["ejemplo.com", "www.ejemplo.com"],
+        "tags": ["cdn"],
+        "ports": [443, 80],
+        "vulns": ["CVE-2023-44487"],
+        "data": [
+            {
+                "port": 443, "transport": "tcp", "product": "nginx", "version": "1.18.0",
+                "cpe23": ["cpe:2.3:a:f5:nginx:1.18.0"],
+                "data": "HTTP/1.1 200 OK\r\nServer: nginx/1.18.0\r\n",
+                "_shodan": {"module": "https"},
+                "vulns": {"CVE-2021-23017": {"cvss": 7.7, "verified": false}}
+            },
+            {"port": 80, "transport": "tcp", "data": "HTTP/1.1 301", "_shodan": {"module": "http"}},
+            {"transport": "udp", "data": "sin puerto"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parsea_servicios_y_vulnerabilidades() {
+        let host = parse_host(HOST).unwrap();
+        assert_eq!(host.ip, "93.184.216.34");
+        assert_eq!(host.os, None);
+        assert_eq!(host.ports, vec![80, 443]);
+        assert_eq!(host.services.len(), 2);
+        assert_eq!(host.services[0].port, 80);
+        assert_eq!(host.services[0].module.as_deref(), Some("http"));
+
+        let https = &host.services[1];
+        assert_eq!(https.product.as_deref(), Some("nginx"));
+        assert_eq!(https.cpe, vec!["cpe:2.3:a:f5:nginx:1.18.0".to_string()]);
+        assert_eq!(https.vulns, vec!["CVE-2021-23017".to_string()]);
+        assert!(https.banner.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(
+            host.vulns,
+            vec!["CVE-2021-23017".to_string(), "CVE-2023-44487".to_string()]
+        );
+
+        assert_eq!(
+            parse_host(r#"{"error": "No information available for that IP."}"#).unwrap_err(),
+            "No information available for that IP."
+        );
+    }
+
+    #[test]
+    fn test_resumen_compacto_y_objetivos_vinculados() {
+        let host = parse_host(HOST).unwrap();
+        let details = host.case_details();
+        assert_eq!(
+            details["servicios"],
+            serde_json::json!(["80/tcp http", "443/tcp nginx 1.18.0 (CVE-2021-23017)"])
+        );
+        assert!(details.get("sistema_operativo").is_none());
+        assert!(!details.to_string().contains("Server: nginx"));
+
+        let related = host.related_targets();
+        assert_eq!(related.len(), 2);
+        assert_eq!(related[0].name, "www.ejemplo.com");
+        assert_eq!(related[0].relation, "hostname");
+        assert_eq!(related[1].name, "ejemplo.com");
+        assert_eq!(related[1].relation, "dominio_asociado");
+    }
+}
@@ -0,0 +1,334 @@
+// src-tauri/src/subdomains.rs
+//
+// Descubrimiento de subdominios sin depender de herramientas de Kali.
+//
+// Flujo:
+//   1. Certificate Transparency: se consulta crt.sh (`%.dominio`) y se extraen los nombres
+//      de los certificados emitidos.
+//   2. Fuerza bruta DNS con la wordlist indicada (o la interna) y concurrencia acotada.
+//   3. Antes de la fuerza bruta se detecta el comodín (`*.dominio`) resolviendo etiquetas
+//      aleatorias: los candidatos que sólo devuelven las IPs del comodín se descartan.
+//   4. Ambas fuentes se fusionan en un `SubdomainReport` con las IPs de cada nombre.
+
+use crate::models::OsintConfig;
+use hickory_resolver::TokioAsyncResolver;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+const CT_SOURCE: &str = "crt.sh";
+const WORDLIST_SOURCE: &str = "wordlist";
+const DEFAULT_CONCURRENCY: usize = 20;
+const MAX_CONCURRENCY: usize = 100;
+/// Tope de entradas leídas de una wordlist externa
+const MAX_WORDLIST_ENTRIES: usize = 20_000;
+/// Etiquetas aleatorias usadas para detectar el comodín
+const WILDCARD_PROBES: usize = 2;
+
+/// Wordlist interna: los prefijos más frecuentes en infraestructura pública.
+const DEFAULT_WORDLIST: &str = "\
+    www mail webmail smtp pop imap mx ns1 ns2 ns3 dns ftp sftp vpn remote owa autodiscover \
+    portal intranet extranet admin panel cpanel whm webdisk api api2 dev develop test \
+    testing qa staging stage pre preprod prod beta demo app apps m mobile static cdn img \
+    images media assets files docs wiki blog shop store tienda pagos pay login sso auth id \
+    cuenta clientes git gitlab jenkins ci jira confluence grafana kibana monitor status \
+    backup db mysql sql old new legacy crm erp campus aula moodle correo mail2 exchange \
+    lyncdiscover sip meet chat support soporte help ayuda news noticias web www2 ws proxy \
+    gateway";
+
+/// Subdominio descubierto y las fuentes que lo aportaron.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Subdomain {
+    pub name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub ips: Vec<String>,
+    pub sources: Vec<String>,
+}
+
+/// Resultado de la enumeración, listo para `detalles_tecnicos.subdominios` del dominio padre.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SubdomainReport {
+    pub domain: String,
+    /// IPs que devuelve el comodín (`*.dominio`), si existe
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub wildcard_ips: Vec<String>,
+    pub ct_names: usize,
+    pub wordlist_size: usize,
+    pub subdomains: Vec<Subdomain>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub errors: BTreeMap<String, String>,
+}
+
+/// Parámetros de la búsqueda (todos opcionales desde el agente).
+#[derive(Debug, Clone, Default)]
+pub struct SubdomainOptions {
+    /// Ruta a una wordlist (una etiqueta por línea). Vacío = wordlist interna.
+    pub wordlist: Option<String>,
+    pub concurrency: Option<usize>,
+}
+
+/// Normaliza el dominio raíz (sin esquema, ruta, `www.` ni punto final).
+fn normalize_domain(query: &str) -> Option<String> {
+    let domain = query
+        .trim()
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .split('/')
+        .next()
+        .unwrap_or("")
+        .trim_start_matches("*.")
+        .trim_end_matches('.')
+        .to_lowercase();
+    let domain = domain.strip_prefix("www.").unwrap_or(&domain).to_string();
+
+    let valid = domain.contains('.')
+        && domain.parse::<IpAddr>().is_err()
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-');
+    if valid {
+        Some(domain)
+    } else {
+        None
+    }
+}
+
+/// Extrae los nombres pertenecientes a `domain` de la respuesta JSON de crt.sh.
+/// Cada entrada trae `name_value` con uno o varios nombres separados por salto de línea.
+pub fn parse_ct_names(body: &str, domain: &str) -> Vec<String> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(body).unwrap_or_default();
+    let suffix = format!(".{}", domain);
+
+    let mut names = BTreeSet::new();
+    for entry in &entries {
+        for field in ["name_value", "common_name"] {
+            let Some(value) = entry.get(field).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            for raw in value.lines() {
+                let name = raw.trim().trim_start_matches("*.").to_lowercase();
+                if name.ends_with(&suffix)
+                    && name
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
+                {
+                    names.insert(name);
+                }
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Carga la wordlist indicada o devuelve la interna.
+fn load_wordlist(path: Option<&str>) -> Result<Vec<String>, String> {
+    let Some(path) = path.map(str::trim).filter(|p| !p.is_empty()) else {
+        return Ok(DEFAULT_WORDLIST
+            .split_whitespace()
+            .map(String::from)
+            .collect());
+    };
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        eprintln!(
+            "ERROR [subdomains]: Failed to read wordlist {}: {}",
+            path, e
+        );
+        format!("No se pudo leer la wordlist '{}'.", path)
+    })?;
+
+    let words: BTreeSet<String> = content
+        .lines()
+        .map(|l| l.trim().trim_end_matches('.').to_lowercase())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter(|l| {
+            l.chars()
+                .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_')
+        })
+        .take(MAX_WORDLIST_ENTRIES)
+        .collect();
+    Ok(words.into_iter().collect())
+}
+
+/// Resuelve un nombre a sus IPs; `None` si no existe o no tiene registros A/AAAA.
+async fn resolve(resolver: &TokioAsyncResolver, name: &str) -> Option<Vec<String>> {
+    let ips: Vec<String> = resolver
+        .lookup_ip(name)
+        .await
+        .ok()?
+        .iter()
+        .map(|ip| ip.to_string())
+        .collect();
+    if ips.is_empty() {
+        None
+    } else {
+        Some(ips)
+    }
+}
+
+/// Resuelve muchos nombres en paralelo respetando el límite de concurrencia.
+async fn resolve_all(
+    resolver: &TokioAsyncResolver,
+    names: Vec<String>,
+    concurrency: usize,
+) -> Vec<(String, Option<Vec<String>>)> {
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut set = JoinSet::new();
+    for name in names {
+        let resolver = resolver.clone();
+        let semaphore = semaphore.clone();
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            let ips = resolve(&resolver, &name).await;
+            (name, ips)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = set.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results
+}
+
+/// Un candidato de fuerza bruta que sólo resuelve a IPs del comodín es un falso positivo.
+fn is_wildcard_hit(ips: &[String], wildcard_ips: &BTreeSet<String>) -> bool {
+    !wildcard_ips.is_empty() && ips.iter().all(|ip| wildcard_ips.contains(ip))
+}
+
+/// Consulta los logs de Certificate Transparency (crt.sh) para el dominio.
+async fn fetch_ct_names(domain: &str, config: &OsintConfig) -> Result<Vec<String>, String> {
+    let client = crate::tools::get_http_client(config).await;
+    let url = format!(
+        "https://crt.sh/?q={}&output=json",
+        urlencoding::encode(&format!("%.{}", domain))
+    );
+
+    let resp = client.get(&url).send().await.map_err(|e| {
+        eprintln!("ERROR [subdomains]: crt.sh request failed: {}", e);
+        "No se pudo consultar crt.sh.".to_string()
+    })?;
+    if !resp.status().is_success() {
+        return Err(format!("crt.sh respondió {}", resp.status()));
+    }
+
+    let body = resp.text().await.map_err(|e| e.to_string())?;
+    Ok(parse_ct_names(&body, domain))
+}
+
+/// Descubre subdominios combinando Certificate Transparency y fuerza bruta DNS.
+pub async fn discover(
+    query: &str,
+    options: &SubdomainOptions,
+    config: &OsintConfig,
+) -> Result<SubdomainReport, String> {
+    let domain = normalize_domain(query)
+        .ok_or_else(|| "Dominio inválido para enumerar subdominios.".to_string())?;
+    let words = load_wordlist(options.wordlist.as_deref())?;
+    let (resolver, _) = crate::dns::build_resolver(config)?;
+    let concurrency = options
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+
+    let mut report = SubdomainReport {
+        domain: domain.clone(),
+        wordlist_size: words.len(),
+        ..Default::default()
+    };
+    // nombre -> (ips, fuentes)
+    let mut found: BTreeMap<String, (Vec<String>, BTreeSet<&str>)> = BTreeMap::new();
+
+    // 1. Certificate Transparency
+    match fetch_ct_names(&domain, config).await {
+        Ok(names) => {
+            report.ct_names = names.len();
+            for (name, ips) in resolve_all(&resolver, names, concurrency).await {
+                let entry = found.entry(name).or_default();
+                entry.0 = ips.unwrap_or_default();
+                entry.1.insert(CT_SOURCE);
+            }
+        }
+        // Sin CT seguimos con la fuerza bruta; el error queda registrado en el reporte
+        Err(e) => {
+            report.errors.insert(CT_SOURCE.to_string(), e);
+        }
+    }
+
+    // 2. Fuerza bruta con detección de comodín
+    let mut wildcard_ips = BTreeSet::new();
+    for _ in 0..WILDCARD_PROBES {
+        let probe = format!("{}.{}", Uuid::new_v4().simple(), domain);
+        if let Some(ips) = resolve(&resolver, &probe).await {
+            wildcard_ips.extend(ips);
+        }
+    }
+    report.wildcard_ips = wildcard_ips.iter().cloned().collect();
+
+    let candidates: Vec<String> = words.iter().map(|w| format!("{}.{}", w, domain)).collect();
+    for (name, ips) in resolve_all(&resolver, candidates, concurrency).await {
+        let Some(ips) = ips else { continue };
+        if is_wildcard_hit(&ips, &wildcard_ips) {
+            continue;
+        }
+        let entry = found.entry(name).or_default();
+        if entry.0.is_empty() {
+            entry.0 = ips;
+        }
+        entry.1.insert(WORDLIST_SOURCE);
+    }
+
+    report.subdomains = found
+        .into_iter()
+        .map(|(name, (ips, sources))| Subdomain {
+            name,
+            ips,
+            sources: sources.into_iter().map(String::from).collect(),
+        })
+        .collect();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_nombres_de_crt_sh() {
+        let body = r#"[
+            {"common_name": "ejemplo.com.ar", "name_value": "ejemplo.com.ar\nwww.ejemplo.com.ar"},
+            {"common_name": "*.api.ejemplo.com.ar", "name_value": "*.api.ejemplo.com.ar"},
+            {"common_name": "otro.com", "name_value": "mail.otro.com\nMAIL.Ejemplo.com.ar"}
+        ]"#;
+
+        let names = parse_ct_names(body, "ejemplo.com.ar");
+        assert_eq!(
+            names,
+            vec![
+                "api.ejemplo.com.ar".to_string(),
+                "mail.ejemplo.com.ar".to_string(),
+                "www.ejemplo.com.ar".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_descarta_coincidencias_de_comodin() {
+        let wildcard: BTreeSet<String> = ["10.0.0.1".to_string()].into_iter().collect();
+        assert!(is_wildcard_hit(&["10.0.0.1".to_string()], &wildcard));
+        assert!(!is_wildcard_hit(
+            &["10.0.0.1".to_string(), "10.0.0.2".to_string()],
+            &wildcard
+        ));
+        assert!(!is_wildcard_hit(
+            &["10.0.0.1".to_string()],
+            &BTreeSet::new()
+        ));
+    }
+}
@@ -0,0 +1,381 @@
+// src-tauri/src/tls.rs
+//
+// Inspección de certificados TLS para pivotear hacia infraestructura relacionada.
+//
+// Flujo:
+//   1. Se conecta a host:puerto (respetando el proxy SOCKS/Tor) y se hace el handshake TLS
+//      aceptando cualquier certificado: interesa inspeccionarlo aunque sea inválido.
+//   2. En paralelo se valida la cadena contra las raíces de webpki para informar si es confiable.
+//   3. Cada certificado de la cadena se parsea (sujeto, emisor, serie, vigencia, SANs, clave,
+//      huella SHA-256) y se devuelve un `TlsReport`.
+
+use crate::models::OsintConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{DigitallySignedStruct, SignatureScheme};
+use tokio_rustls::TlsConnector;
+use x509_parser::prelude::*;
+
+const DEFAULT_TLS_PORT: u16 = 443;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+    /// Días hasta el vencimiento (negativo si ya venció)
+    pub days_remaining: i64,
+    pub self_signed: bool,
+    pub is_ca: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub san_dns: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub san_ip: Vec<String>,
+    pub key_type: String,
+    pub key_bits: usize,
+    pub signature_algorithm: String,
+    pub sha256: String,
+}
+
+/// Resultado de la inspección, listo para `detalles_tecnicos.tls`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TlsReport {
+    pub host: String,
+    pub port: u16,
+    pub protocol: String,
+    pub cipher_suite: String,
+    /// La cadena valida contra las raíces públicas para este nombre
+    pub trusted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_error: Option<String>,
+    /// Dominios únicos de los SANs del certificado final (sin el prefijo `*.`)
+    pub san_domains: Vec<String>,
+    /// Cadena presentada por el servidor: el primero es el certificado final
+    pub chain: Vec<CertificateInfo>,
+}
+
+/// Verificador que acepta cualquier certificado pero registra si la validación real pasó.
+#[derive(Debug)]
+struct InspectingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    outcome: Mutex<Option<Result<(), String>>>,
+}
+
+impl ServerCertVerifier for InspectingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        if let Ok(mut outcome) = self.outcome.lock() {
+            *outcome = Some(result);
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Separa "host:puerto" (o una URL) en sus partes; el puerto por defecto es 443.
+fn parse_endpoint(target: &str, port: Option<u16>) -> Option<(String, u16)> {
+    let t = target
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or("");
+
+    let (host, parsed_port) = if let Some(rest) = t.strip_prefix('[') {
+        // IPv6 con corchetes: [::1]:8443
+        let (h, after) = rest.split_once(']')?;
+        (
+            h.to_string(),
+            after.strip_prefix(':').and_then(|p| p.parse().ok()),
+        )
+    } else if t.matches(':').count() == 1 {
+        let (h, p) = t.split_once(':')?;
+        (h.to_string(), Some(p.parse().ok()?))
+    } else {
+        (t.to_string(), None)
+    };
+
+    let host = host.trim_end_matches('.').to_lowercase();
+    let valid = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == ':');
+    if !valid {
+        return None;
+    }
+    Some((host, port.or(parsed_port).unwrap_or(DEFAULT_TLS_PORT)))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn oid_name(oid: &x509_parser::der_parser::oid::Oid) -> String {
+    oid2sn(oid, oid_registry())
+        .map(String::from)
+        .unwrap_or_else(|_| oid.to_id_string())
+}
+
+fn asn1_to_rfc3339(time: &ASN1Time) -> (String, Option<DateTime<Utc>>) {
+    let parsed = DateTime::<Utc>::from_timestamp(time.timestamp(), 0);
+    (parsed.map(|d| d.to_rfc3339()).unwrap_or_default(), parsed)
+}
+
+/// Parsea un certificado DER a la vista resumida que se guarda en el caso.
+pub fn describe_certificate(der: &[u8]) -> Result<CertificateInfo, String> {
+    let (_, cert) =
+        parse_x509_certificate(der).map_err(|e| format!("Certificado ilegible: {}", e))?;
+
+    let (not_before, _) = asn1_to_rfc3339(&cert.validity().not_before);
+    let (not_after, expiry) = asn1_to_rfc3339(&cert.validity().not_after);
+    let days_remaining = expiry
+        .map(|d| (d - Utc::now()).num_days())
+        .unwrap_or_default();
+
+    let mut san_dns = Vec::new();
+    let mut san_ip = Vec::new();
+    if let Ok(Some(san)) = cert.subject_alternative_name() {
+        for name in &san.value.general_names {
+            match name {
+                GeneralName::DNSName(dns) => san_dns.push(dns.to_lowercase()),
+                GeneralName::IPAddress(bytes) => {
+                    let ip = match bytes.len() {
+                        4 => <[u8; 4]>::try_from(*bytes).ok().map(IpAddr::from),
+                        16 => <[u8; 16]>::try_from(*bytes).ok().map(IpAddr::from),
+                        _ => None,
+                    };
+                    if let Some(ip) = ip {
+                        san_ip.push(ip.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let spki = cert.public_key();
+    let (key_type, key_bits) = match spki.parsed() {
+        Ok(x509_parser::public_key::PublicKey::RSA(rsa)) => ("RSA".to_string(), rsa.key_size()),
+        Ok(x509_parser::public_key::PublicKey::EC(ec)) => {
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .and_then(|p| p.as_oid().ok())
+                .map(|oid| oid_name(&oid));
+            let label = match curve {
+                Some(curve) => format!("EC ({})", curve),
+                None => "EC".to_string(),
+            };
+            (label, ec.key_size())
+        }
+        Ok(other) => (oid_name(&spki.algorithm.algorithm), other.key_size()),
+        Err(_) => (oid_name(&spki.algorithm.algorithm), 0),
+    };
+
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.raw_serial_as_string(),
+        not_before,
+        not_after,
+        days_remaining,
+        self_signed: cert.subject() == cert.issuer(),
+        is_ca: cert.is_ca(),
+        san_dns,
+        san_ip,
+        key_type,
+        key_bits,
+        signature_algorithm: oid_name(&cert.signature_algorithm.algorithm),
+        sha256: to_hex(&Sha256::digest(der)),
+    })
+}
+
+/// Dominios únicos de los SANs, sin comodines, listos para convertirse en objetivos.
+fn san_domains(leaf: &CertificateInfo) -> Vec<String> {
+    let unique: BTreeSet<String> = leaf
+        .san_dns
+        .iter()
+        .map(|d| d.trim_start_matches("*.").trim_end_matches('.').to_string())
+        .filter(|d| d.contains('.'))
+        .collect();
+    unique.into_iter().collect()
+}
+
+/// Conecta a host:puerto, obtiene la cadena completa y la describe.
+pub async fn inspect(
+    target: &str,
+    port: Option<u16>,
+    config: &OsintConfig,
+) -> Result<TlsReport, String> {
+    let (host, port) = parse_endpoint(target, port)
+        .ok_or_else(|| "Destino TLS inválido: se espera 'host' o 'host:puerto'.".to_string())?;
+
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let roots = Arc::new(rustls::RootCertStore::from_iter(
+        webpki_roots::TLS_SERVER_ROOTS.iter().cloned(),
+    ));
+    let inner = WebPkiServerVerifier::builder_with_provider(roots, provider.clone())
+        .build()
+        .map_err(|e| format!("No se pudo inicializar el verificador TLS: {}", e))?;
+    let verifier = Arc::new(InspectingVerifier {
+        inner,
+        outcome: Mutex::new(None),
+    });
+
+    let client_config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+
+    let server_name = ServerName::try_from(host.clone())
+        .map_err(|_| format!("Nombre de servidor inválido: {}", host))?;
+
+    let stream = crate::tools::open_tcp_stream(&host, port, config, CONNECT_TIMEOUT).await?;
+    let tls = tokio::time::timeout(
+        HANDSHAKE_TIMEOUT,
+        TlsConnector::from(Arc::new(client_config)).connect(server_name, stream),
+    )
+    .await
+    .map_err(|_| format!("{}:{} no completó el handshake TLS a tiempo", host, port))?
+    .map_err(|e| {
+        eprintln!(
+            "ERROR [tls]: Handshake with {}:{} failed: {}",
+            host, port, e
+        );
+        format!("Falló el handshake TLS con {}:{}: {}", host, port, e)
+    })?;
+
+    let (_, session) = tls.get_ref();
+    let chain: Vec<CertificateInfo> = session
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|der| describe_certificate(der.as_ref()).ok())
+        .collect();
+    if chain.is_empty() {
+        return Err(format!("{}:{} no presentó certificados.", host, port));
+    }
+
+    let outcome = verifier.outcome.lock().ok().and_then(|o| o.clone());
+    let validation_error = match &outcome {
+        Some(Err(e)) => Some(e.clone()),
+        Some(Ok(())) => None,
+        None => Some("La cadena no llegó a validarse".to_string()),
+    };
+
+    Ok(TlsReport {
+        host,
+        port,
+        protocol: session
+            .protocol_version()
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        cipher_suite: session
+            .negotiated_cipher_suite()
+            .and_then(|c| c.suite().as_str())
+            .unwrap_or_default()
+            .to_string(),
+        trusted: validation_error.is_none(),
+        validation_error,
+        san_domains: san_domains(&chain[0]),
+        chain,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separa_host_y_puerto() {
+        assert_eq!(
+            parse_endpoint("ejemplo.com.ar", None),
+            Some(("ejemplo.com.ar".to_string(), 443))
+        );
+        assert_eq!(
+            parse_endpoint("https://Mail.Ejemplo.com.ar:8443/login", None),
+            Some(("mail.ejemplo.com.ar".to_string(), 8443))
+        );
+        assert_eq!(
+            parse_endpoint("[2001:db8::1]:993", None),
+            Some(("2001:db8::1".to_string(), 993))
+        );
+        assert_eq!(
+            parse_endpoint("10.0.0.1", Some(993)),
+            Some(("10.0.0.1".to_string(), 993))
+        );
+        assert_eq!(parse_endpoint("ejemplo.com;rm", None), None);
+    }
+
+    #[test]
+    fn test_dominios_de_sans() {
+        let leaf = CertificateInfo {
+            san_dns: vec![
+                "*.ejemplo.com.ar".into(),
+                "ejemplo.com.ar".into(),
+                "webmail.ejemplo.com.ar".into(),
+                "localhost".into(),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            san_domains(&leaf),
+            vec![
+                "ejemplo.com.ar".to_string(),
+                "webmail.ejemplo.com.ar".to_string()
+            ]
+        );
+    }
+}
@@ -1,13 +1,21 @@
 use crate::models::{OsintConfig, OsintResult};
-use dns_lookup::lookup_host;
-use reqwest::{Client, Proxy};
-use std::fs::File;
-use std::io::BufReader;
+use reqwest::{Client, ClientBuilder, Proxy};
+use std::time::Duration;
 use tauri::async_runtime::spawn_blocking;
+use tokio::net::TcpStream;
 use tokio::process::Command as AsyncCommand;
+use tokio_socks::tcp::Socks5Stream;
 
 /// Obtiene un cliente HTTP configurado con proxy si está presente en la configuración.
-async fn get_http_client(config: &OsintConfig) -> Client {
+pub async fn get_http_client(config: &OsintConfig) -> Client {
+    http_client_builder(config)
+        .build()
+        .unwrap_or_else(|_| Client::new())
+}
+
+/// Builder base de `get_http_client` (timeout, User-Agent, proxy) para herramientas que
+/// necesitan ajustar algo más, por ejemplo seguir las redirecciones a mano.
+pub fn http_client_builder(config: &OsintConfig) -> ClientBuilder {
     let mut builder = Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
@@ -20,7 +28,66 @@ async fn get_http_client(config: &OsintConfig) -> Client {
         }
     }
 
-    builder.build().unwrap_or_else(|_| Client::new())
+    builder
+}
+
+const NON_SOCKS_PROXY_ERROR: &str =
+    "El proxy configurado no es SOCKS5 y no puede transportar esta conexión; configure un proxy socks5:// o desactívelo.";
+
+/// `true` si el proxy configurado es SOCKS5 (el único que transporta TCP crudo).
+fn is_socks_proxy(proxy: &str) -> bool {
+    proxy.starts_with("socks5://") || proxy.starts_with("socks5h://")
+}
+
+/// Conexión TCP directa o por el proxy SOCKS5. Un puerto cerrado del otro lado del proxy
+/// llega como `ErrorKind::ConnectionRefused`, igual que en una conexión directa.
+async fn connect_tcp(host: &str, port: u16, config: &OsintConfig) -> std::io::Result<TcpStream> {
+    let proxy = config.proxy_url.trim();
+    if let Some(rest) = proxy
+        .strip_prefix("socks5h://")
+        .or_else(|| proxy.strip_prefix("socks5://"))
+    {
+        let rest = rest.trim_end_matches('/');
+        let stream = match rest.rsplit_once('@') {
+            Some((credentials, proxy_addr)) => {
+                let (user, pass) = credentials.split_once(':').unwrap_or((credentials, ""));
+                Socks5Stream::connect_with_password(proxy_addr, (host, port), user, pass).await
+            }
+            None => Socks5Stream::connect(rest, (host, port)).await,
+        };
+        stream.map(|s| s.into_inner()).map_err(|e| match e {
+            tokio_socks::Error::ConnectionRefused => {
+                std::io::Error::from(std::io::ErrorKind::ConnectionRefused)
+            }
+            other => std::io::Error::other(other),
+        })
+    } else if !proxy.is_empty() {
+        eprintln!(
+            "ERROR [net]: El proxy '{}' no soporta TCP crudo; conexión a {}:{} cancelada",
+            proxy, host, port
+        );
+        Err(std::io::Error::other(NON_SOCKS_PROXY_ERROR))
+    } else {
+        TcpStream::connect((host, port)).await
+    }
+}
+
+/// Abre una conexión TCP cruda (WHOIS, banners, etc.) respetando el proxy SOCKS5 configurado.
+/// Un proxy HTTP no puede transportar estos protocolos: en ese caso se rechaza la conexión
+/// en lugar de salir directo y exponer la IP del analista.
+pub async fn open_tcp_stream(
+    host: &str,
+    port: u16,
+    config: &OsintConfig,
+    timeout: Duration,
+) -> Result<TcpStream, String> {
+    match tokio::time::timeout(timeout, connect_tcp(host, port, config)).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!(
+            "Tiempo de espera agotado conectando a {}:{}",
+            host, port
+        )),
+    }
 }
 
 /// Valida si un target (IP o Dominio) es seguro para ser usado en comandos de sistema.
@@ -33,7 +100,7 @@ fn is_safe_target(target: &str) -> bool {
         && !target.is_empty()
 }
 
-pub async fn perform_ping(target: &str) -> OsintResult {
+pub async fn perform_ping(target: &str, options: &crate::ping::PingOptions) -> OsintResult {
     if !is_safe_target(target) {
         return OsintResult {
             success: false,
@@ -42,347 +109,694 @@ pub async fn perform_ping(target: &str) -> OsintResult {
         };
     }
 
-    let output = AsyncCommand::new("ping")
-        .args(["-n", "4", target])
-        .output()
-        .await;
+    match crate::ping::ping(target, options).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    match output {
-        Ok(out) => {
-            let result = String::from_utf8_lossy(&out.stdout).to_string();
-            OsintResult {
-                success: out.status.success(),
-                data: result,
-                error: if out.status.success() {
-                    None
-                } else {
-                    Some("La solicitud de eco (ping) no pudo completarse.".into())
-                },
+/// Puertos TCP más frecuentes, ordenados por probabilidad de estar abiertos
+/// (base: nmap-services, más servicios modernos de bases de datos y contenedores).
+const TOP_PORTS: &[u16] = &[
+    80, 23, 443, 21, 22, 25, 3389, 110, 445, 139, 143, 53, 135, 3306, 8080, 1723, 111, 995, 993,
+    5900, 1025, 587, 8888, 199, 1720, 465, 548, 113, 81, 6001, 10000, 514, 5060, 179, 1026, 2000,
+    8443, 8000, 32768, 554, 26, 1433, 49152, 2001, 515, 8008, 49154, 1027, 5666, 646, 5000, 5631,
+    631, 49153, 8081, 2049, 88, 79, 5800, 106, 2121, 1110, 49155, 6000, 513, 990, 5357, 427, 49156,
+    543, 544, 5101, 144, 7, 389, 5432, 6379, 27017, 9200, 11211, 5601, 9000, 9090, 2375, 2376,
+    6443, 10250, 1521, 5985, 5986, 3000, 4443, 7001, 8880, 1883, 5672, 15672, 25565, 8181, 9443,
+];
+const DEFAULT_PORT_SPEC: &str = "top100";
+const DEFAULT_SCAN_CONCURRENCY: usize = 200;
+/// A través de un proxy SOCKS (ej: Tor) no conviene abrir cientos de circuitos a la vez
+const PROXY_SCAN_CONCURRENCY: usize = 16;
+const MAX_SCAN_CONCURRENCY: usize = 1000;
+const DEFAULT_SCAN_TIMEOUT_MS: u64 = 1500;
+/// Por debajo todo parece cerrado; por encima el escaneo se eterniza
+const MIN_SCAN_TIMEOUT_MS: u64 = 100;
+const MAX_SCAN_TIMEOUT_MS: u64 = 10_000;
+const BANNER_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_BANNER_CHARS: usize = 256;
+/// Puertos HTTP en claro: si no saludan solos se les envía un HEAD para obtener el banner
+const HTTP_PORTS: &[u16] = &[
+    80, 81, 3000, 5000, 8000, 8008, 8080, 8081, 8880, 8888, 9000, 9090,
+];
+
+/// Parámetros del escaneo de puertos (todos opcionales desde el agente).
+#[derive(Debug, Clone, Default)]
+pub struct PortScanOptions {
+    /// "top100", "top20", "1-1024", "22,80,443" o combinaciones separadas por coma
+    pub ports: Option<String>,
+    pub concurrency: Option<usize>,
+    /// Se acota a 100-10000 ms
+    pub timeout_ms: Option<u64>,
+    pub grab_banners: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct PortResult {
+    pub port: u16,
+    pub state: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub banner: Option<String>,
+    pub rtt_ms: f64,
+}
+
+/// Resultado del escaneo, listo para `detalles_tecnicos.puertos` del objetivo IP.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct PortScanReport {
+    pub target: String,
+    pub ip: String,
+    pub via_proxy: bool,
+    pub scanned: usize,
+    pub open: Vec<PortResult>,
+    pub closed: usize,
+    pub filtered: usize,
+    pub duration_ms: u64,
+}
+
+/// Interpreta la especificación de puertos. Admite presets `topN` (N hasta 100), rangos y listas.
+fn parse_port_spec(spec: &str) -> Result<Vec<u16>, String> {
+    let mut ports = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let lower = part.to_lowercase();
+        if let Some(n) = lower.strip_prefix("top") {
+            let n: usize = n
+                .trim_start_matches('-')
+                .parse()
+                .map_err(|_| format!("Preset de puertos inválido: '{}'", part))?;
+            if n == 0 || n > TOP_PORTS.len() {
+                return Err(format!(
+                    "Preset de puertos inválido: '{}' (admite top1 a top{})",
+                    part,
+                    TOP_PORTS.len()
+                ));
+            }
+            ports.extend(TOP_PORTS.iter().take(n));
+        } else if let Some((start, end)) = part.split_once('-') {
+            let start: u16 = start
+                .trim()
+                .parse()
+                .map_err(|_| format!("Rango de puertos inválido: '{}'", part))?;
+            let end: u16 = end
+                .trim()
+                .parse()
+                .map_err(|_| format!("Rango de puertos inválido: '{}'", part))?;
+            if start == 0 || start > end {
+                return Err(format!("Rango de puertos inválido: '{}'", part));
+            }
+            ports.extend(start..=end);
+        } else {
+            match part.parse::<u16>() {
+                Ok(port) if port > 0 => ports.push(port),
+                _ => return Err(format!("Puerto inválido: '{}'", part)),
             }
         }
-        Err(e) => {
-            eprintln!("ERROR [system]: Internal ping failure: {}", e);
-            OsintResult {
-                success: false,
-                data: String::new(),
-                error: Some("Ocurrió un error interno al ejecutar el comando de red.".into()),
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    ports.retain(|p| seen.insert(*p));
+    if ports.is_empty() {
+        return Err("No se indicó ningún puerto para escanear.".to_string());
+    }
+    Ok(ports)
+}
+
+/// Nombre del servicio habitual en un puerto conocido.
+fn service_name(port: u16) -> Option<&'static str> {
+    let name = match port {
+        21 => "ftp",
+        22 => "ssh",
+        23 => "telnet",
+        25 | 587 | 465 => "smtp",
+        53 => "dns",
+        80 | 81 | 8000 | 8008 | 8080 | 8081 | 8880 | 8888 => "http",
+        110 | 995 => "pop3",
+        111 => "rpcbind",
+        135 => "msrpc",
+        139 | 445 => "smb",
+        143 | 993 => "imap",
+        389 => "ldap",
+        443 | 4443 | 8443 => "https",
+        1433 => "mssql",
+        1521 => "oracle",
+        1723 => "pptp",
+        1883 => "mqtt",
+        2049 => "nfs",
+        2375 | 2376 => "docker",
+        3000 => "http-dev",
+        3306 => "mysql",
+        3389 => "rdp",
+        5060 => "sip",
+        5432 => "postgresql",
+        5601 => "kibana",
+        5672 => "amqp",
+        5900 | 5800 => "vnc",
+        5985 | 5986 => "winrm",
+        6379 => "redis",
+        6443 | 10250 => "kubernetes",
+        9200 => "elasticsearch",
+        11211 => "memcached",
+        15672 => "rabbitmq-admin",
+        25565 => "minecraft",
+        27017 => "mongodb",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Limpia un banner para guardarlo: sin caracteres de control y con largo acotado.
+fn sanitize_banner(raw: &[u8]) -> Option<String> {
+    let text: String = String::from_utf8_lossy(raw)
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.chars().take(MAX_BANNER_CHARS).collect())
+    }
+}
+
+/// Lee el saludo del servicio. Si no saluda solo (o es un puerto HTTP conocido) se le envía
+/// un HEAD, así también se identifican servidores web en puertos no estándar.
+async fn grab_banner(stream: &mut TcpStream, port: u16) -> Option<String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut buf = [0u8; 1024];
+    if !HTTP_PORTS.contains(&port) {
+        if let Ok(Ok(n)) = tokio::time::timeout(BANNER_TIMEOUT, stream.read(&mut buf)).await {
+            if n > 0 {
+                return sanitize_banner(&buf[..n]);
             }
+            // Cerró sin saludar: no hay nada más que leer
+            return None;
         }
     }
+
+    stream
+        .write_all(b"HEAD / HTTP/1.0\r\nUser-Agent: Mozilla/5.0\r\n\r\n")
+        .await
+        .ok()?;
+    match tokio::time::timeout(BANNER_TIMEOUT, stream.read(&mut buf)).await {
+        Ok(Ok(n)) if n > 0 => {
+            let text = String::from_utf8_lossy(&buf[..n]).to_string();
+            if !text.starts_with("HTTP/") {
+                return sanitize_banner(&buf[..n]);
+            }
+            // De la respuesta HTTP sólo interesan la línea de estado y el header Server
+            let summary: Vec<&str> = text
+                .lines()
+                .filter(|l| l.starts_with("HTTP/") || l.to_lowercase().starts_with("server:"))
+                .collect();
+            sanitize_banner(summary.join(" | ").as_bytes())
+        }
+        _ => None,
+    }
+}
+
+/// Escaneo TCP connect asíncrono. Respeta el proxy SOCKS de la configuración (Tor).
+pub async fn scan_ports(
+    target: &str,
+    options: &PortScanOptions,
+    config: &OsintConfig,
+) -> Result<PortScanReport, String> {
+    let target = target.trim();
+    if target.is_empty() || !is_safe_target(target) {
+        return Err("Target inválido o potencialmente malicioso identificado.".to_string());
+    }
+    let ports = parse_port_spec(options.ports.as_deref().unwrap_or(DEFAULT_PORT_SPEC))?;
+
+    let proxy = config.proxy_url.trim();
+    let via_proxy = is_socks_proxy(proxy);
+    // Escanear directo (y resolver por el DNS local) expondría la IP del analista
+    if !proxy.is_empty() && !via_proxy {
+        return Err(NON_SOCKS_PROXY_ERROR.to_string());
+    }
+
+    // Con SOCKS el nombre se resuelve del otro lado: no se filtra la consulta DNS
+    let ip = match target.parse::<std::net::IpAddr>() {
+        Ok(ip) => ip.to_string(),
+        Err(_) if via_proxy => target.to_string(),
+        Err(_) => tokio::net::lookup_host((target, 0))
+            .await
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .map(|addr| addr.ip().to_string())
+            .ok_or_else(|| format!("No se pudo resolver '{}'.", target))?,
+    };
+
+    let max_concurrency = if via_proxy {
+        PROXY_SCAN_CONCURRENCY
+    } else {
+        MAX_SCAN_CONCURRENCY
+    };
+    let concurrency = options
+        .concurrency
+        .unwrap_or(DEFAULT_SCAN_CONCURRENCY)
+        .clamp(1, max_concurrency);
+    let timeout = Duration::from_millis(
+        options
+            .timeout_ms
+            .unwrap_or(DEFAULT_SCAN_TIMEOUT_MS)
+            .clamp(MIN_SCAN_TIMEOUT_MS, MAX_SCAN_TIMEOUT_MS),
+    );
+
+    let started = std::time::Instant::now();
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency));
+    let mut set = tokio::task::JoinSet::new();
+    for port in ports.iter().copied() {
+        let semaphore = semaphore.clone();
+        let host = ip.clone();
+        let config = config.clone();
+        let grab = options.grab_banners;
+        set.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok();
+            let probe_start = std::time::Instant::now();
+            let connected = tokio::time::timeout(timeout, connect_tcp(&host, port, &config))
+                .await
+                .unwrap_or_else(|_| Err(std::io::ErrorKind::TimedOut.into()));
+            let rtt_ms = (probe_start.elapsed().as_secs_f64() * 1000.0 * 1000.0).round() / 1000.0;
+
+            match connected {
+                Ok(mut stream) => {
+                    let banner = if grab {
+                        grab_banner(&mut stream, port).await
+                    } else {
+                        None
+                    };
+                    (port, "open", banner, rtt_ms)
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                    (port, "closed", None, rtt_ms)
+                }
+                Err(_) => (port, "filtered", None, rtt_ms),
+            }
+        });
+    }
+
+    let mut report = PortScanReport {
+        target: target.to_string(),
+        ip,
+        via_proxy,
+        scanned: ports.len(),
+        ..Default::default()
+    };
+    while let Some(joined) = set.join_next().await {
+        let Ok((port, state, banner, rtt_ms)) = joined else {
+            continue;
+        };
+        match state {
+            "open" => report.open.push(PortResult {
+                port,
+                state: state.to_string(),
+                service: service_name(port).map(String::from),
+                banner,
+                rtt_ms,
+            }),
+            "closed" => report.closed += 1,
+            _ => report.filtered += 1,
+        }
+    }
+    report.open.sort_by_key(|p| p.port);
+    report.duration_ms = started.elapsed().as_millis() as u64;
+    Ok(report)
+}
+
+pub async fn port_scan(
+    target: &str,
+    options: &PortScanOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match scan_ports(target, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
 }
 
 pub async fn perform_whois(target: &str, config: &OsintConfig) -> OsintResult {
-    let parts: Vec<&str> = target.split('.').collect();
-    if parts.len() < 2 {
-        return OsintResult {
+    match crate::whois::lookup(target, config).await {
+        Ok(record) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&record).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some("Invalid domain".into()),
-        };
+            error: Some(e),
+        },
     }
+}
 
-    let client = get_http_client(config).await;
-    let res = client
-        .get(format!("https://networkcalc.com/api/dns/lookup/{}", target))
-        .send()
-        .await;
+pub async fn perform_dns_lookup(target: &str, config: &OsintConfig) -> OsintResult {
+    match crate::dns::lookup(target, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    match res {
-        Ok(resp) => match resp.text().await {
-            Ok(text) => OsintResult {
-                success: true,
-                data: text,
-                error: None,
-            },
-            Err(e) => OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some(e.to_string()),
-            },
+pub async fn discover_subdomains(
+    target: &str,
+    options: &crate::subdomains::SubdomainOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::subdomains::discover(target, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
         },
         Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some(format!("HTTP Whois failed: {}", e)),
+            error: Some(e),
         },
     }
 }
 
-pub async fn perform_dns_lookup(target: &str) -> OsintResult {
-    let target_owned = target.to_string();
-    let result = spawn_blocking(move || lookup_host(&target_owned)).await;
+pub async fn inspect_tls(target: &str, port: Option<u16>, config: &OsintConfig) -> OsintResult {
+    match crate::tls::inspect(target, port, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    match result {
-        Ok(lookup_res) => match lookup_res {
-            Ok(ips) => {
-                let ip_strings: Vec<String> = ips.iter().map(|ip| ip.to_string()).collect();
-                OsintResult {
-                    success: true,
-                    data: ip_strings.join("\n"),
-                    error: None,
-                }
-            }
-            Err(e) => OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some(e.to_string()),
-            },
+pub async fn http_fingerprint(target: &str, config: &OsintConfig) -> OsintResult {
+    match crate::fingerprint::fingerprint(target, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
         },
         Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some(format!("Task execution failed: {}", e)),
+            error: Some(e),
         },
     }
 }
 
 pub async fn extract_metadata(path: String) -> OsintResult {
-    let file = match File::open(&path) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("ERROR [metadata]: Failed to open file: {} - {}", path, e);
-            return OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some("No se pudo abrir el archivo especificado.".into()),
-            };
-        }
-    };
-
-    let mut reader = BufReader::new(file);
-    let exifreader = exif::Reader::new();
-
-    match exifreader.read_from_container(&mut reader) {
-        Ok(exif) => {
-            let mut data = String::new();
-            for f in exif.fields() {
-                data.push_str(&format!(
-                    "{} ({}): {}\n",
-                    f.tag,
-                    f.ifd_num,
-                    f.display_value().with_unit(&exif)
-                ));
-            }
-            OsintResult {
-                success: true,
-                data,
-                error: None,
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [metadata]: EXIF read failure: {}", e);
-            OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some(
-                    "No se encontraron metadatos EXIF o el formato no es soportado.".into(),
-                ),
-            }
-        }
+    let result = spawn_blocking(move || crate::metadata::extract(&path))
+        .await
+        .unwrap_or_else(|e| Err(format!("Error interno al leer metadatos: {}", e)));
+    match result {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
 
-pub async fn web_scrape_search(query: String, config: &OsintConfig) -> OsintResult {
-    let url = format!(
-        "https://html.duckduckgo.com/html/?q={}",
-        urlencoding::encode(&query)
-    );
-
-    let client = get_http_client(config).await;
+/// Búsqueda web multi-motor (ver `websearch`).
+pub async fn web_scrape_search(
+    query: &str,
+    options: &crate::websearch::SearchOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::websearch::search(query, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    match client.get(&url).send().await {
-        Ok(res) => {
-            if let Ok(html) = res.text().await {
-                let document = scraper::Html::parse_document(&html);
-                let result_selector = scraper::Selector::parse(".result").unwrap();
-                let title_selector = scraper::Selector::parse(".result__title").unwrap();
-                let snippet_selector = scraper::Selector::parse(".result__snippet").unwrap();
-                let link_selector = scraper::Selector::parse(".result__url").unwrap();
-
-                let mut output = format!("🔍 **Resultados de Búsqueda para '{}':**\n\n", query);
-                let mut count = 0;
-
-                for element in document.select(&result_selector) {
-                    if count >= 5 {
-                        break;
-                    }
+pub async fn search_username(
+    username: &str,
+    options: &crate::usernames::UsernameOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::usernames::search(username, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-                    let title = element
-                        .select(&title_selector)
-                        .next()
-                        .map(|e| e.text().collect::<String>())
-                        .unwrap_or_default()
-                        .trim()
-                        .to_string();
-                    let snippet = element
-                        .select(&snippet_selector)
-                        .next()
-                        .map(|e| e.text().collect::<String>())
-                        .unwrap_or_default()
-                        .trim()
-                        .to_string();
-                    let link = element
-                        .select(&link_selector)
-                        .next()
-                        .map(|e| e.text().collect::<String>())
-                        .unwrap_or_default()
-                        .trim()
-                        .to_string();
-
-                    if !title.is_empty() {
-                        output.push_str(&format!("**[{}]**\n{}\n🔗 {}\n\n", title, snippet, link));
-                        count += 1;
-                    }
-                }
+/// Emails públicos de un dominio en Hunter.io, con el patrón de la organización.
+pub async fn hunter_domain_search(
+    domain: &str,
+    limit: Option<u32>,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::hunter::domain_search(domain, limit, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-                if count == 0 {
-                    output.push_str("No se encontraron resultados o el scraping fue bloqueado/cambió el formato.");
-                }
+/// Email más probable de una persona en un dominio (Hunter.io).
+pub async fn hunter_email_finder(
+    domain: &str,
+    first_name: &str,
+    last_name: &str,
+    with_pattern: bool,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::hunter::find_email(domain, first_name, last_name, with_pattern, config).await {
+        Ok(result) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&result).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-                OsintResult {
-                    success: true,
-                    data: output,
-                    error: None,
-                }
-            } else {
-                eprintln!("ERROR [scraper]: Text read failure from DuckDuckGO");
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some("No se pudo leer la respuesta del motor de búsqueda.".into()),
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [scraper]: DuckDuckGO request failed: {}", e);
-            OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some("La conexión con el motor de búsqueda falló.".into()),
-            }
-        }
+pub async fn verify_email(email: &str, config: &OsintConfig) -> OsintResult {
+    match crate::email::verify(email, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
 
-pub async fn search_username(username: String, config: &OsintConfig) -> OsintResult {
-    let client = get_http_client(config).await;
-    let mut results = format!("🔍 **Búsqueda de Usuario para '{}':**\n\n", username);
+pub fn analyze_phone(number: &str, region: Option<&str>) -> OsintResult {
+    match crate::phone::analyze(number, region) {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    let platforms = vec![
-        ("GitHub", format!("https://github.com/{}", username)),
-        ("Twitter", format!("https://twitter.com/{}", username)),
-        ("Instagram", format!("https://instagram.com/{}", username)),
-        ("Reddit", format!("https://reddit.com/user/{}", username)),
-        ("TikTok", format!("https://tiktok.com/@{}", username)),
-        (
-            "Steam",
-            format!("https://steamcommunity.com/id/{}", username),
-        ),
-    ];
+pub async fn hash_file(path: &str, config: &OsintConfig) -> OsintResult {
+    match crate::hashing::analyze(path, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    for (name, url) in platforms {
-        match client.get(&url).send().await {
-            Ok(resp) => {
-                let status = resp.status();
-                if status.is_success() {
-                    results.push_str(&format!("✅ {}: Encontrado ( {} )\n", name, url));
-                } else if status == 404 {
-                    // No encontrado
-                } else {
-                    results.push_str(&format!(
-                        "❓ {}: Código {} (Posible bloqueo)\n",
-                        name, status
-                    ));
-                }
-            }
-            Err(_) => results.push_str(&format!("❌ {}: Error de conexión.\n", name)),
-        }
+pub async fn analyze_faces(
+    source: &str,
+    models_dir: &std::path::Path,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::faces::analyze(source, models_dir, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
+}
 
-    OsintResult {
-        success: true,
-        data: results,
-        error: None,
+pub async fn analyze_image(
+    source: &str,
+    store_dir: Option<&std::path::Path>,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::imagehash::analyze(source, store_dir, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
 
 pub async fn virus_total_scan(target: String, config: &OsintConfig) -> OsintResult {
-    if config.virustotal.is_empty() {
-        return OsintResult {
+    match crate::virustotal::lookup(&target, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some("API Key de VirusTotal no configurada en ajustes.".into()),
-        };
+            error: Some(e),
+        },
     }
+}
 
-    let client = get_http_client(config).await;
-    let url = format!("https://www.virustotal.com/api/v3/search?query={}", target);
-
-    match client
-        .get(&url)
-        .header("x-apikey", &config.virustotal)
-        .send()
-        .await
-    {
-        Ok(res) => {
-            if let Ok(text) = res.text().await {
-                OsintResult {
-                    success: true,
-                    data: text,
-                    error: None,
-                }
-            } else {
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some("No se pudo leer la respuesta de VT".into()),
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [virustotal]: Connection failed: {}", e);
-            OsintResult {
+/// Geolocalización con las bases locales; ip-api.com solo si el analista habilitó el
+/// enriquecimiento en línea (la consulta revela la IP investigada a un tercero).
+pub async fn ip_intel(
+    ip: String,
+    resource_dir: &std::path::Path,
+    config: &OsintConfig,
+) -> OsintResult {
+    let (location, mut warnings) = crate::geoip::lookup(&ip, resource_dir, config).await;
+    let mut report = crate::geoip::IpIntelReport {
+        ip: ip.trim().to_string(),
+        ..Default::default()
+    };
+    match location {
+        Ok(location) => report.location = Some(location),
+        Err(e) if !config.online_enrichment => {
+            return OsintResult {
                 success: false,
                 data: "".into(),
-                error: Some(
-                    "Ocurrió un problema al conectar con el servicio de análisis de reputación."
-                        .into(),
-                ),
-            }
+                error: Some(e),
+            };
         }
+        Err(e) => warnings.push(e),
     }
-}
 
-pub async fn ip_intel(ip: String, config: &OsintConfig) -> OsintResult {
-    let client = get_http_client(config).await;
-    // Usamos ip-api.com (limite 45/min para free)
-    let url = format!("http://ip-api.com/json/{}?fields=66842623", ip);
-
-    match client.get(&url).send().await {
-        Ok(res) => {
-            if let Ok(text) = res.text().await {
-                OsintResult {
-                    success: true,
-                    data: text,
-                    error: None,
-                }
-            } else {
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some("Error leyendo IP Intel".into()),
-                }
+    if config.online_enrichment {
+        let client = get_http_client(config).await;
+        // Usamos ip-api.com (limite 45/min para free)
+        let url = format!("http://ip-api.com/json/{}?fields=66842623", report.ip);
+        match client.get(&url).send().await {
+            Ok(res) => match res.json::<serde_json::Value>().await {
+                Ok(online) => report.online = Some(online),
+                Err(_) => warnings.push("Error leyendo IP Intel".to_string()),
+            },
+            Err(e) => {
+                eprintln!("ERROR [ip_intel]: Request failed: {}", e);
+                warnings
+                    .push("La consulta de geolocalización e inteligencia de IP falló.".to_string());
             }
         }
-        Err(e) => {
-            eprintln!("ERROR [ip_intel]: Request failed: {}", e);
-            OsintResult {
+        if report.location.is_none() && report.online.is_none() {
+            return OsintResult {
                 success: false,
                 data: "".into(),
-                error: Some("La consulta de geolocalización e inteligencia de IP falló.".into()),
-            }
+                error: Some(warnings.join(" ")),
+            };
         }
     }
+    report.warnings = warnings;
+
+    OsintResult {
+        success: true,
+        data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+        error: None,
+    }
+}
+
+pub async fn ip_infrastructure(ip: &str, config: &OsintConfig) -> OsintResult {
+    match crate::asn::lookup(ip, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
 }
 
 #[allow(dead_code)]
@@ -601,34 +1015,13 @@ pub async fn browse_url(url: String, config: &OsintConfig) -> OsintResult {
     }
 }
 
+/// Solo arma las consultas (una por línea, con su categoría); `run_dorks` las ejecuta.
 pub async fn generate_dorks(name: String) -> OsintResult {
-    let dorks = vec![
-        format!("site:linkedin.com \"{}\"", name),
-        format!("site:facebook.com \"{}\"", name),
-        format!("site:twitter.com \"{}\"", name),
-        format!("site:instagram.com \"{}\"", name),
-        format!(
-            "\"{}\" filetype:pdf OR filetype:doc OR filetype:xls OR filetype:docx",
-            name
-        ),
-        format!(
-            "\"{}\" intext:\"dni\" OR intext:\"cuil\" OR intext:\"email\" OR intext:\"correo\"",
-            name
-        ),
-        format!(
-            "\"{}\" intext:\"teléfono\" OR intext:\"celular\" OR intext:\"whatsapp\"",
-            name
-        ),
-        format!("\"{}\" \"currículum\" OR \"CV\" OR \"resume\"", name),
-        format!(
-            "\"{}\" \"dirección\" OR \"domicilio\" OR \"vivienda\"",
-            name
-        ),
-        format!(
-            "\"{}\" \"password\" OR \"leak\" OR \"breach\" OR \"filtración\"",
-            name
-        ),
-    ];
+    let kind = crate::dorks::DorkKind::detect(&name);
+    let dorks = crate::dorks::build(&name, kind)
+        .into_iter()
+        .map(|d| format!("[{}] {}", d.category, d.query))
+        .collect::<Vec<_>>();
 
     OsintResult {
         success: true,
@@ -637,61 +1030,87 @@ pub async fn generate_dorks(name: String) -> OsintResult {
     }
 }
 
-pub async fn search_leaks(target: String, config: &OsintConfig) -> OsintResult {
-    let client = get_http_client(config).await;
-    let api_key = &config.hibp_api_key;
+pub async fn wayback_history(
+    target: &str,
+    options: &crate::wayback::WaybackOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::wayback::history(target, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
 
-    if api_key.is_empty() {
-        return OsintResult {
+pub async fn wayback_snapshot(url: &str, timestamp: &str, config: &OsintConfig) -> OsintResult {
+    match crate::wayback::fetch_snapshot(url, timestamp, config).await {
+        Ok(page) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&page).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some("API Key de HIBP no configurada. Esta herramienta requiere una llave válida para funcionar. No se realizarán simulaciones para garantizar la integridad del informe.".into()),
-        };
+            error: Some(e),
+        },
     }
+}
 
-    match client
-        .get(format!(
-            "https://haveibeenpwned.com/api/v3/breachedaccount/{}",
-            target
-        ))
-        .header("hibp-api-key", api_key)
-        .send()
-        .await
-    {
-        Ok(res) => {
-            let status = res.status();
-            if status == 200 {
-                let data = res.text().await.unwrap_or_default();
-                OsintResult {
-                    success: true,
-                    data,
-                    error: None,
-                }
-            } else if status == 404 {
-                OsintResult {
-                    success: true,
-                    data: "No se encontraron filtraciones conocidas.".into(),
-                    error: None,
-                }
-            } else {
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some(format!("HIBP API Error: {}", status)),
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [hibp]: API Request failure: {}", e);
-            OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some(
-                    "No se pudo completar la búsqueda de filtraciones en la base de datos externa."
-                        .into(),
-                ),
-            }
-        }
+pub async fn run_dorks(
+    target: &str,
+    options: &crate::dorks::DorkOptions,
+    config: &OsintConfig,
+) -> OsintResult {
+    match crate::dorks::run(target, options, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
+
+pub async fn search_leaks(target: String, config: &OsintConfig) -> OsintResult {
+    match crate::hibp::lookup_account(&target, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
+    }
+}
+
+/// Cuentas filtradas de un dominio verificado en HIBP.
+pub async fn search_domain_leaks(domain: &str, config: &OsintConfig) -> OsintResult {
+    match crate::hibp::lookup_domain(domain, config).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
 
@@ -740,113 +1159,53 @@ pub async fn social_search(target: String, config: &OsintConfig) -> OsintResult
     }
 }
 
-#[allow(dead_code)]
-pub async fn dark_search(query: String, config: &OsintConfig) -> OsintResult {
-    let client = get_http_client(config).await;
-    // Ahmia es un motor de búsqueda que indexa la Dark Web (.onion)
-    let url = format!("https://ahmia.fi/search/?q={}", urlencoding::encode(&query));
-
-    match client.get(&url).send().await {
-        Ok(resp) => {
-            if let Ok(text) = resp.text().await {
-                // Scrape básico de los resultados
-                let document = scraper::Html::parse_document(&text);
-                let selector = scraper::Selector::parse("li.result").unwrap();
-                let mut results = Vec::new();
-
-                for element in document.select(&selector) {
-                    if let Some(link) = element
-                        .select(&scraper::Selector::parse("cite").unwrap())
-                        .next()
-                    {
-                        results.push(link.text().collect::<String>());
-                    }
-                }
-
-                if results.is_empty() {
-                    return OsintResult {
-                        success: true,
-                        data: "No se encontraron resultados en la Dark Web para esta consulta."
-                            .into(),
-                        error: None,
-                    };
-                }
-
-                OsintResult {
-                    success: true,
-                    data: format!("Resultados Dark Web (vía Ahmia):\n{}", results.join("\n")),
-                    error: None,
-                }
-            } else {
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some("Error leyendo respuesta de Ahmia".into()),
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [dark_search]: Ahmia connection failed: {}", e);
-            OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some("La conexión con el nodo de búsqueda Onion falló.".into()),
-            }
-        }
+/// Búsqueda en Ahmia forzada por Tor (ver `darkweb`).
+pub async fn dark_search(query: &str) -> OsintResult {
+    match crate::darkweb::search(query).await {
+        Ok(report) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&report).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
 
-pub async fn shodan_intel(ip: String, config: &OsintConfig) -> OsintResult {
-    if config.shodan.is_empty() {
-        return OsintResult {
+/// Lee una página .onion por Tor y devuelve título y texto limpio.
+pub async fn fetch_onion(url: &str) -> OsintResult {
+    match crate::darkweb::fetch_page(url).await {
+        Ok(page) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&page).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
             success: false,
             data: "".into(),
-            error: Some("API Key de Shodan no configurada.".into()),
-        };
+            error: Some(e),
+        },
     }
+}
 
-    let client = get_http_client(config).await;
-    let url = format!(
-        "https://api.shodan.io/shodan/host/{}?key={}",
-        ip, config.shodan
-    );
-
-    match client.get(&url).send().await {
-        Ok(res) => {
-            if let Ok(text) = res.text().await {
-                if text.contains("Requires membership") {
-                    return OsintResult {
-                        success: false,
-                        data: "".into(),
-                        error: Some("Error de Shodan: Tu API Key es válida pero esta consulta requiere una membresía paga (Membership/Pro).".into()),
-                    };
-                }
-                OsintResult {
-                    success: true,
-                    data: text,
-                    error: None,
-                }
-            } else {
-                OsintResult {
-                    success: false,
-                    data: "".into(),
-                    error: Some("Error leyendo Shodan Intel".into()),
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("ERROR [shodan]: API request failed: {}", e);
-            OsintResult {
-                success: false,
-                data: "".into(),
-                error: Some(
-                    "Hubo un problema al consultar la base de datos de dispositivos conectados."
-                        .into(),
-                ),
-            }
-        }
+pub async fn shodan_intel(ip: String, config: &OsintConfig) -> OsintResult {
+    match crate::shodan::lookup(&ip, config).await {
+        Ok(host) => OsintResult {
+            success: true,
+            data: serde_json::to_string(&host).unwrap_or_else(|_| "{}".to_string()),
+            error: None,
+        },
+        Err(e) => OsintResult {
+            success: false,
+            data: "".into(),
+            error: Some(e),
+        },
     }
 }
+
 #[allow(dead_code)]
 pub async fn get_system_info() -> OsintResult {
     let mut info = String::from("📊 **Información de Hardware del Sistema:**\n\n");
@@ -999,6 +1358,16 @@ pub async fn run_wsl_command(command: String, sudo_pass: Option<String>) -> Osin
 pub fn clean_technical_noise(text: &str, tool_name: &str) -> String {
     let mut data_map = std::collections::HashMap::<String, serde_json::Value>::new();
 
+    // --- Resultados nativos ya tipados (whois, dns, ...) ---
+    // Vienen como objeto JSON: se anidan tal cual, sin re-parsear texto
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
+        if value.is_object() {
+            data_map.insert(tool_name.to_string(), value);
+            let wrapper = serde_json::json!({ "detalles_tecnicos": data_map });
+            return serde_json::to_string(&wrapper).unwrap_or_else(|_| "{}".to_string());
+        }
+    }
+
     // --- Parser específico para PING ---
     // Detecta el output de ping por sus marcadores característicos
     if text.contains("icmp_seq") || text.contains("bytes from") {
@@ -1130,3 +1499,24 @@ pub fn clean_technical_noise(text: &str, tool_name: &str) -> String {
     let wrapper = serde_json::json!({ "detalles_tecnicos": data_map });
     serde_json::to_string(&wrapper).unwrap_or_else(|_| "{}".to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_especificacion_de_puertos() {
+        assert_eq!(parse_port_spec("22, 80,443").unwrap(), vec![22, 80, 443]);
+        assert_eq!(parse_port_spec("20-23").unwrap(), vec![20, 21, 22, 23]);
+        assert_eq!(parse_port_spec("top5").unwrap(), vec![80, 23, 443, 21, 22]);
+        assert_eq!(
+            parse_port_spec("top3,22,80").unwrap(),
+            vec![80, 23, 443, 22]
+        );
+        assert_eq!(parse_port_spec("top100").unwrap().len(), 100);
+        assert!(parse_port_spec("top1000").is_err());
+        assert!(parse_port_spec("0").is_err());
+        assert!(parse_port_spec("100-10").is_err());
+        assert!(parse_port_spec("ssh").is_err());
+    }
+}
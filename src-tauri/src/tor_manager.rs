@@ -1,22 +1,34 @@
 // src-tauri/src/tor_manager.rs
 use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_shell::ShellExt;
 use tokio::sync::Mutex;
 
+/// Proxy SOCKS del sidecar. `socks5h` para que los .onion se resuelvan dentro de Tor.
+pub const SOCKS_PROXY: &str = "socks5h://127.0.0.1:9050";
+
+/// Solo se marca con "Bootstrapped 100%": con el puerto abierto pero sin circuitos,
+/// una petición podría fallar o, peor, hacerse por otro camino.
+static BOOTSTRAPPED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_bootstrapped() -> bool {
+    BOOTSTRAPPED.load(Ordering::SeqCst)
+}
+
 pub struct TorState {
     pub child: Arc<Mutex<Option<tauri_plugin_shell::process::CommandChild>>>,
 }
 
 pub async fn start_tor(app: &AppHandle) -> Result<(), String> {
     let shell = app.shell();
+    BOOTSTRAPPED.store(false, Ordering::SeqCst);
 
     // Rutas de recursos
     let resource_dir = app.path().resource_dir().map_err(|e| e.to_string())?;
-    let geoip_path = resource_dir.join("resources/tor/geoip");
-    let geoip6_path = resource_dir.join("resources/tor/geoip6");
+    let (geoip_path, geoip6_path) = crate::geoip::tor_geoip_files(&resource_dir);
 
     // Directorio de datos de la app
     let app_data = app.path().app_data_dir().map_err(|e| e.to_string())?;
@@ -51,6 +63,7 @@ pub async fn start_tor(app: &AppHandle) -> Result<(), String> {
                     println!("Tor STDOUT: {}", trimmed);
 
                     if trimmed.contains("Bootstrapped 100%") && !ready_sent {
+                        BOOTSTRAPPED.store(true, Ordering::SeqCst);
                         let _ = tx_ready.send(true).await;
                         ready_sent = true;
                     }
@@ -59,6 +72,9 @@ pub async fn start_tor(app: &AppHandle) -> Result<(), String> {
                     let out = String::from_utf8_lossy(&line);
                     eprintln!("Tor STDERR: {}", out.trim());
                 }
+                tauri_plugin_shell::process::CommandEvent::Terminated(_) => {
+                    BOOTSTRAPPED.store(false, Ordering::SeqCst);
+                }
                 _ => {}
             }
         }
@@ -91,6 +107,7 @@ pub async fn start_tor(app: &AppHandle) -> Result<(), String> {
 pub async fn stop_tor(app: &AppHandle) {
     let state = app.state::<TorState>();
     let mut lock = state.child.lock().await;
+    BOOTSTRAPPED.store(false, Ordering::SeqCst);
     if let Some(child) = lock.take() {
         println!("Deteniendo servicio Tor...");
         let _ = child.kill();
@@ -0,0 +1,351 @@
+// src-tauri/src/usernames.rs
+//
+// Búsqueda de un nombre de usuario en múltiples sitios a partir de un manifiesto JSON.
+//
+// Flujo:
+//   1. Se carga el manifiesto: el interno (`resources/usernames/sites.json`) o uno externo con
+//      el mismo formato que WhatsMyName (`wmn-data.json`), que puede usarse tal cual.
+//   2. Cada sitio define la URL a consultar y las reglas de "existe" (código + texto) y
+//      "no existe" (código + texto opcional). El estado HTTP solo no alcanza: varios sitios
+//      devuelven 200 para perfiles inexistentes.
+//   3. Las consultas corren en paralelo con concurrencia acotada y un intervalo mínimo entre
+//      peticiones para no disparar bloqueos.
+//   4. Las cuentas encontradas se pueden convertir en `SocialProfile` de una persona.
+
+use crate::models::{OsintConfig, SocialProfile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+const BUILTIN_MANIFEST: &str = include_str!("../resources/usernames/sites.json");
+const ACCOUNT_PLACEHOLDER: &str = "{account}";
+const DEFAULT_CONCURRENCY: usize = 10;
+const MAX_CONCURRENCY: usize = 50;
+/// Separación mínima entre el inicio de dos peticiones
+const REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Con el texto buscado en el cuerpo alcanza: no hace falta descargar páginas enteras
+const MAX_BODY_BYTES: usize = 512 * 1024;
+
+/// Entrada del manifiesto (nombres de campo de WhatsMyName).
+#[derive(Debug, Deserialize, Clone)]
+pub struct SiteEntry {
+    pub name: String,
+    pub uri_check: String,
+    /// URL pública del perfil, si difiere de la consultada (ej: una API)
+    #[serde(default)]
+    pub uri_pretty: Option<String>,
+    /// Si está presente la consulta se hace por POST con este cuerpo
+    #[serde(default)]
+    pub post_body: Option<String>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub e_code: u16,
+    #[serde(default)]
+    pub e_string: String,
+    pub m_code: u16,
+    #[serde(default)]
+    pub m_string: String,
+    #[serde(default)]
+    pub cat: String,
+    /// Caracteres que el sitio no admite en el usuario y se quitan antes de consultar
+    #[serde(default)]
+    pub strip_bad_char: String,
+    #[serde(default = "default_valid")]
+    pub valid: bool,
+}
+
+fn default_valid() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    sites: Vec<SiteEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountStatus {
+    Found,
+    Missing,
+    /// La respuesta no coincide con ninguna regla (bloqueo, captcha, cambio del sitio...)
+    Unknown,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SiteResult {
+    pub site: String,
+    pub category: String,
+    pub url: String,
+    pub status: AccountStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct UsernameOptions {
+    /// Ruta a un manifiesto propio; sin ella se usa el interno
+    pub manifest: Option<String>,
+    /// Categorías a consultar (vacío = todas)
+    pub categories: Vec<String>,
+    pub concurrency: Option<usize>,
+}
+
+/// Resultado de la búsqueda, listo para `detalles_tecnicos.usuarios` del objetivo Username.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UsernameReport {
+    pub username: String,
+    pub checked: usize,
+    pub found: Vec<SiteResult>,
+    /// Sitios sin veredicto claro, para revisar a mano
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub uncertain: Vec<SiteResult>,
+    pub missing: usize,
+    pub errors: usize,
+}
+
+impl UsernameReport {
+    /// Cuentas encontradas como perfiles sociales listos para asociar a una persona.
+    pub fn social_profiles(&self) -> Vec<SocialProfile> {
+        self.found
+            .iter()
+            .map(|r| SocialProfile {
+                id: None,
+                platform: r.site.clone(),
+                username: self.username.clone(),
+                url: r.url.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Garantiza un intervalo mínimo entre peticiones aunque corran en paralelo.
+struct Throttle {
+    next: Mutex<Instant>,
+}
+
+impl Throttle {
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        let now = Instant::now();
+        if *next > now {
+            tokio::time::sleep_until(*next).await;
+        }
+        *next = Instant::now() + REQUEST_INTERVAL;
+    }
+}
+
+/// Carga el manifiesto indicado o el interno, descartando sitios marcados como inválidos.
+fn load_manifest(path: Option<&str>) -> Result<Vec<SiteEntry>, String> {
+    let content = match path.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| {
+            eprintln!("ERROR [usernames]: Failed to read manifest {}: {}", path, e);
+            format!("No se pudo leer el manifiesto '{}'.", path)
+        })?,
+        None => BUILTIN_MANIFEST.to_string(),
+    };
+    let manifest: Manifest = serde_json::from_str(&content).map_err(|e| {
+        eprintln!("ERROR [usernames]: Invalid manifest: {}", e);
+        "El manifiesto de sitios no tiene un formato válido.".to_string()
+    })?;
+    Ok(manifest
+        .sites
+        .into_iter()
+        .filter(|s| s.valid && s.uri_check.contains(ACCOUNT_PLACEHOLDER))
+        .collect())
+}
+
+/// Decide el estado según las reglas del sitio. "Existe" pide el código y el texto;
+/// "no existe" pide el código y, si está definido, también el texto.
+fn evaluate(site: &SiteEntry, status: u16, body: &str) -> AccountStatus {
+    let found = status == site.e_code && body.contains(&site.e_string);
+    let missing =
+        status == site.m_code && (site.m_string.is_empty() || body.contains(&site.m_string));
+    match (found, missing) {
+        (true, false) => AccountStatus::Found,
+        (false, true) => AccountStatus::Missing,
+        _ => AccountStatus::Unknown,
+    }
+}
+
+async fn check_site(
+    client: &reqwest::Client,
+    site: &SiteEntry,
+    username: &str,
+    throttle: &Throttle,
+) -> SiteResult {
+    let mut account = username.to_string();
+    account.retain(|c| !site.strip_bad_char.contains(c));
+    let encoded = urlencoding::encode(&account);
+    let check_url = site.uri_check.replace(ACCOUNT_PLACEHOLDER, &encoded);
+    let url = site
+        .uri_pretty
+        .as_deref()
+        .unwrap_or(&site.uri_check)
+        .replace(ACCOUNT_PLACEHOLDER, &encoded);
+
+    let mut result = SiteResult {
+        site: site.name.clone(),
+        category: site.cat.clone(),
+        url,
+        status: AccountStatus::Error,
+        http_status: None,
+        note: None,
+    };
+
+    let mut request = match &site.post_body {
+        Some(body) => client
+            .post(&check_url)
+            .body(body.replace(ACCOUNT_PLACEHOLDER, &account)),
+        None => client.get(&check_url),
+    };
+    for (key, value) in &site.headers {
+        request = request.header(key, value);
+    }
+
+    throttle.wait().await;
+    let mut response = match request.timeout(REQUEST_TIMEOUT).send().await {
+        Ok(r) => r,
+        Err(e) => {
+            result.note = Some(if e.is_timeout() {
+                "Tiempo de espera agotado".to_string()
+            } else {
+                "Error de conexión".to_string()
+            });
+            return result;
+        }
+    };
+
+    let status = response.status().as_u16();
+    let mut body = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_BODY_BYTES {
+            break;
+        }
+    }
+    let body = String::from_utf8_lossy(&body);
+
+    result.http_status = Some(status);
+    result.status = evaluate(site, status, &body);
+    if result.status == AccountStatus::Unknown && status == 429 {
+        result.note = Some("Límite de peticiones del sitio (429)".to_string());
+    }
+    result
+}
+
+/// Busca el usuario en todos los sitios del manifiesto (filtrados por categoría).
+pub async fn search(
+    username: &str,
+    options: &UsernameOptions,
+    config: &OsintConfig,
+) -> Result<UsernameReport, String> {
+    let username = username.trim().trim_start_matches('@').to_string();
+    if username.is_empty() || username.chars().any(char::is_whitespace) {
+        return Err("Nombre de usuario inválido.".to_string());
+    }
+
+    let sites: Vec<SiteEntry> = load_manifest(options.manifest.as_deref())?
+        .into_iter()
+        .filter(|s| {
+            options.categories.is_empty()
+                || options
+                    .categories
+                    .iter()
+                    .any(|c| c.trim().eq_ignore_ascii_case(&s.cat))
+        })
+        .collect();
+    if sites.is_empty() {
+        return Err("Ningún sitio del manifiesto coincide con las categorías pedidas.".to_string());
+    }
+
+    let client = crate::tools::get_http_client(config).await;
+    let concurrency = options
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let throttle = Arc::new(Throttle {
+        next: Mutex::new(Instant::now()),
+    });
+
+    let checked = sites.len();
+    let mut tasks = JoinSet::new();
+    for site in sites {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let throttle = throttle.clone();
+        let username = username.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok()?;
+            Some(check_site(&client, &site, &username, &throttle).await)
+        });
+    }
+
+    let mut report = UsernameReport {
+        username,
+        checked,
+        ..Default::default()
+    };
+    while let Some(joined) = tasks.join_next().await {
+        let Ok(Some(result)) = joined else { continue };
+        match result.status {
+            AccountStatus::Found => report.found.push(result),
+            AccountStatus::Missing => report.missing += 1,
+            AccountStatus::Unknown => report.uncertain.push(result),
+            AccountStatus::Error => {
+                report.errors += 1;
+                report.uncertain.push(result);
+            }
+        }
+    }
+    report.found.sort_by(|a, b| a.site.cmp(&b.site));
+    report.uncertain.sort_by(|a, b| a.site.cmp(&b.site));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifiesto_interno_valido() {
+        let sites = load_manifest(None).unwrap();
+        assert!(sites.len() >= 20);
+        assert!(sites
+            .iter()
+            .all(|s| !s.cat.is_empty() && !s.e_string.is_empty()));
+        assert!(sites.iter().any(|s| s.name == "GitHub"));
+    }
+
+    #[test]
+    fn test_evalua_reglas_del_sitio() {
+        let site: SiteEntry = serde_json::from_str(
+            r#"{"name": "Steam", "uri_check": "https://steamcommunity.com/id/{account}",
+                "e_code": 200, "e_string": "g_rgProfileData",
+                "m_code": 200, "m_string": "could not be found", "cat": "gaming"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            evaluate(&site, 200, "var g_rgProfileData = {...}"),
+            AccountStatus::Found
+        );
+        // Mismo código 200 para el perfil inexistente: decide el texto
+        assert_eq!(
+            evaluate(&site, 200, "The specified profile could not be found."),
+            AccountStatus::Missing
+        );
+        assert_eq!(
+            evaluate(&site, 429, "Too Many Requests"),
+            AccountStatus::Unknown
+        );
+    }
+}
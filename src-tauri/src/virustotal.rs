@@ -0,0 +1,464 @@
+// src-tauri/src/virustotal.rs
+//
+// Reputación en VirusTotal (API v3) según el tipo de objetivo.
+//
+// Flujo:
+//   1. Se detecta el tipo: IP, hash (MD5/SHA-1/SHA-256), URL (http/https) o dominio.
+//   2. Se consulta el objeto en su endpoint (`/domains`, `/ip_addresses`, `/urls` con el
+//      identificador base64url de la URL, `/files`) y se reduce a estadísticas del último
+//      análisis, reputación, categorías y etiquetas.
+//   3. Para dominios e IPs se piden además las resoluciones DNS pasivas y los archivos que
+//      se comunican con ellos; son los pivotes que el orquestador vincula en el caso.
+//   4. Los errores de cuota (HTTP 429) se informan explícitamente: la API pública permite
+//      muy pocas consultas por minuto y el analista tiene que saber por qué no hay datos.
+
+use crate::cases::{RelatedTarget, TargetType};
+use crate::models::OsintConfig;
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+const API_URL: &str = "https://www.virustotal.com/api/v3";
+const GUI_URL: &str = "https://www.virustotal.com/gui";
+/// Máximo de resoluciones y archivos pedidos por pivote
+const PIVOT_LIMIT: usize = 20;
+const FILES_LIMIT: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VtKind {
+    Domain,
+    Ip,
+    Url,
+    File,
+}
+
+impl VtKind {
+    pub fn detect(target: &str) -> Self {
+        let target = target.trim();
+        if target.parse::<IpAddr>().is_ok() {
+            VtKind::Ip
+        } else if matches!(target.len(), 32 | 40 | 64)
+            && target.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            VtKind::File
+        } else if target.starts_with("http://") || target.starts_with("https://") {
+            VtKind::Url
+        } else {
+            VtKind::Domain
+        }
+    }
+
+    /// Ruta del objeto en la API v3.
+    fn path(self, target: &str) -> String {
+        match self {
+            VtKind::Domain => format!("domains/{}", target.to_lowercase()),
+            VtKind::Ip => format!("ip_addresses/{}", target),
+            VtKind::Url => format!("urls/{}", url_id(target)),
+            VtKind::File => format!("files/{}", target.to_lowercase()),
+        }
+    }
+
+    fn gui_path(self, target: &str) -> String {
+        match self {
+            VtKind::Domain => format!("{}/domain/{}", GUI_URL, target.to_lowercase()),
+            VtKind::Ip => format!("{}/ip-address/{}", GUI_URL, target),
+            VtKind::Url => format!("{}/url/{}", GUI_URL, url_id(target)),
+            VtKind::File => format!("{}/file/{}", GUI_URL, target.to_lowercase()),
+        }
+    }
+
+    fn target_type(self) -> TargetType {
+        match self {
+            VtKind::Domain | VtKind::Url => TargetType::Domain,
+            VtKind::Ip => TargetType::IP,
+            VtKind::File => TargetType::Hash,
+        }
+    }
+}
+
+/// Identificador de URL de VirusTotal: base64url sin relleno.
+fn url_id(url: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(url.trim())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct VtStats {
+    pub malicious: u32,
+    pub suspicious: u32,
+    pub harmless: u32,
+    pub undetected: u32,
+}
+
+/// Resolución DNS pasiva: la IP de un dominio o el dominio de una IP.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VtResolution {
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VtFileRef {
+    pub sha256: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub malicious: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VtReport {
+    pub target: String,
+    pub kind: VtKind,
+    /// `false` si VirusTotal nunca vio el objeto
+    pub found: bool,
+    #[serde(default)]
+    pub stats: VtStats,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reputation: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threat_label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_analysis: Option<String>,
+    /// Sólo archivos: tipo según VirusTotal (ej: "MS Word Document")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meaningful_name: Option<String>,
+    /// Sólo archivos: otros nombres con los que se subió
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolutions: Vec<VtResolution>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub communicating_files: Vec<VtFileRef>,
+    /// Pivotes que no se pudieron traer (cuota, permisos...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    pub permalink: String,
+}
+
+impl VtReport {
+    /// "12/94 motores lo marcan como malicioso (trojan.emotet)"
+    pub fn verdict(&self) -> String {
+        if !self.found {
+            return format!("VirusTotal no tiene registros de {}.", self.target);
+        }
+        let total = self.stats.malicious
+            + self.stats.suspicious
+            + self.stats.harmless
+            + self.stats.undetected;
+        format!(
+            "VirusTotal: {}/{} motores lo marcan como malicioso y {} como sospechoso{}{}.",
+            self.stats.malicious,
+            total,
+            self.stats.suspicious,
+            self.threat_label
+                .as_ref()
+                .map(|l| format!(" ({})", l))
+                .unwrap_or_default(),
+            self.reputation
+                .map(|r| format!(", reputación {}", r))
+                .unwrap_or_default()
+        )
+    }
+
+    /// Resoluciones y archivos comunicantes como objetivos vinculados.
+    pub fn related_targets(&self) -> Vec<RelatedTarget> {
+        let source_type = self.kind.target_type();
+        let resolved_type = match self.kind {
+            VtKind::Ip => TargetType::Domain,
+            _ => TargetType::IP,
+        };
+        let mut related: Vec<RelatedTarget> = self
+            .resolutions
+            .iter()
+            .map(|r| RelatedTarget {
+                source: self.target.clone(),
+                source_type: source_type.clone(),
+                name: r.value.clone(),
+                target_type: resolved_type.clone(),
+                relation: "resolucion_pasiva".to_string(),
+            })
+            .collect();
+        related.extend(self.communicating_files.iter().map(|f| RelatedTarget {
+            source: self.target.clone(),
+            source_type: source_type.clone(),
+            name: f.sha256.clone(),
+            target_type: TargetType::Hash,
+            relation: "archivo_comunicante".to_string(),
+        }));
+        related
+    }
+}
+
+/// GET autenticado a la API v3. `Ok(None)` si el objeto no existe (404).
+pub async fn fetch(path: &str, config: &OsintConfig) -> Result<Option<serde_json::Value>, String> {
+    let client = crate::tools::get_http_client(config).await;
+    let response = client
+        .get(format!("{}/{}", API_URL, path))
+        .header("x-apikey", config.virustotal.trim())
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("ERROR [virustotal]: Connection failed: {}", e);
+            "No se pudo conectar con VirusTotal.".to_string()
+        })?;
+
+    match response.status().as_u16() {
+        404 => Ok(None),
+        401 | 403 => Err("VirusTotal rechazó la API key configurada.".to_string()),
+        429 => Err("Cuota de VirusTotal agotada; reintentar más tarde.".to_string()),
+        200 => response.json().await.map(Some).map_err(|e| {
+            eprintln!("ERROR [virustotal]: Invalid response: {}", e);
+            "Respuesta de VirusTotal ilegible.".to_string()
+        }),
+        status => Err(format!("VirusTotal respondió con el código {}.", status)),
+    }
+}
+
+fn date(value: &serde_json::Value) -> Option<String> {
+    value
+        .as_i64()
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+        .map(|d| d.to_rfc3339())
+}
+
+fn stats(value: &serde_json::Value) -> VtStats {
+    let count = |key: &str| value[key].as_u64().unwrap_or(0) as u32;
+    VtStats {
+        malicious: count("malicious"),
+        suspicious: count("suspicious"),
+        harmless: count("harmless"),
+        undetected: count("undetected"),
+    }
+}
+
+/// Reduce el objeto (`data.attributes`) a las partes útiles para el caso.
+fn parse_object(target: &str, kind: VtKind, body: &serde_json::Value) -> VtReport {
+    let attrs = &body["data"]["attributes"];
+    // Las categorías vienen por motor ({"Forcepoint": "phishing", ...}): interesa el conjunto
+    let mut categories: Vec<String> = attrs["categories"]
+        .as_object()
+        .map(|map| {
+            map.values()
+                .filter_map(|v| v.as_str().map(str::to_lowercase))
+                .collect()
+        })
+        .unwrap_or_default();
+    categories.sort();
+    categories.dedup();
+    let text = |value: &serde_json::Value| value.as_str().map(str::to_string);
+
+    VtReport {
+        target: target.to_string(),
+        kind,
+        found: true,
+        stats: stats(&attrs["last_analysis_stats"]),
+        reputation: attrs["reputation"].as_i64(),
+        categories,
+        tags: attrs["tags"]
+            .as_array()
+            .map(|tags| tags.iter().filter_map(text).collect())
+            .unwrap_or_default(),
+        threat_label: text(&attrs["popular_threat_classification"]["suggested_threat_label"]),
+        last_analysis: date(&attrs["last_analysis_date"]),
+        type_description: text(&attrs["type_description"]),
+        meaningful_name: text(&attrs["meaningful_name"]),
+        names: attrs["names"]
+            .as_array()
+            .map(|names| names.iter().filter_map(text).take(20).collect())
+            .unwrap_or_default(),
+        first_seen: date(&attrs["first_submission_date"]),
+        resolutions: Vec::new(),
+        communicating_files: Vec::new(),
+        notes: Vec::new(),
+        permalink: kind.gui_path(target),
+    }
+}
+
+/// Resoluciones de `/resolutions`: del lado de un dominio interesa la IP y viceversa.
+fn parse_resolutions(kind: VtKind, body: &serde_json::Value) -> Vec<VtResolution> {
+    let field = if kind == VtKind::Ip {
+        "host_name"
+    } else {
+        "ip_address"
+    };
+    let mut resolutions: Vec<VtResolution> = Vec::new();
+    for item in body["data"].as_array().into_iter().flatten() {
+        let attrs = &item["attributes"];
+        let Some(value) = attrs[field].as_str().filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if resolutions.iter().all(|r| r.value != value) {
+            resolutions.push(VtResolution {
+                value: value.to_string(),
+                date: date(&attrs["date"]),
+            });
+        }
+    }
+    resolutions
+}
+
+fn parse_files(body: &serde_json::Value) -> Vec<VtFileRef> {
+    body["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let attrs = &item["attributes"];
+            Some(VtFileRef {
+                sha256: item["id"].as_str()?.to_string(),
+                name: attrs["meaningful_name"].as_str().map(str::to_string),
+                malicious: stats(&attrs["last_analysis_stats"]).malicious,
+            })
+        })
+        .collect()
+}
+
+/// Consulta el objetivo en VirusTotal y, para dominios e IPs, sus pivotes.
+pub async fn lookup(target: &str, config: &OsintConfig) -> Result<VtReport, String> {
+    if config.virustotal.trim().is_empty() {
+        return Err("API Key de VirusTotal no configurada en ajustes.".to_string());
+    }
+    let target = target.trim();
+    let kind = VtKind::detect(target);
+    let path = kind.path(target);
+
+    let Some(body) = fetch(&path, config).await? else {
+        return Ok(VtReport {
+            target: target.to_string(),
+            kind,
+            found: false,
+            stats: VtStats::default(),
+            reputation: None,
+            categories: Vec::new(),
+            tags: Vec::new(),
+            threat_label: None,
+            last_analysis: None,
+            type_description: None,
+            meaningful_name: None,
+            names: Vec::new(),
+            first_seen: None,
+            resolutions: Vec::new(),
+            communicating_files: Vec::new(),
+            notes: Vec::new(),
+            permalink: kind.gui_path(target),
+        });
+    };
+    let mut report = parse_object(target, kind, &body);
+
+    if matches!(kind, VtKind::Domain | VtKind::Ip) {
+        // Secuencial a propósito: con la cuota pública, un 429 corta los pivotes restantes
+        match fetch(
+            &format!("{}/resolutions?limit={}", path, PIVOT_LIMIT),
+            config,
+        )
+        .await
+        {
+            Ok(body) => {
+                report.resolutions = body
+                    .map(|b| parse_resolutions(kind, &b))
+                    .unwrap_or_default()
+            }
+            Err(e) => {
+                report.notes.push(format!("Resoluciones: {}", e));
+                return Ok(report);
+            }
+        }
+        match fetch(
+            &format!("{}/communicating_files?limit={}", path, FILES_LIMIT),
+            config,
+        )
+        .await
+        {
+            Ok(body) => {
+                report.communicating_files = body.map(|b| parse_files(&b)).unwrap_or_default()
+            }
+            Err(e) => report.notes.push(format!("Archivos comunicantes: {}", e)),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detecta_tipo_y_ruta() {
+        assert_eq!(VtKind::detect("8.8.8.8"), VtKind::Ip);
+        assert_eq!(VtKind::detect("2001:db8::1"), VtKind::Ip);
+        assert_eq!(
+            VtKind::detect("44d88612fea8a8f36de82e1278abb02f"),
+            VtKind::File
+        );
+        assert_eq!(VtKind::detect("https://ejemplo.com/login"), VtKind::Url);
+        assert_eq!(VtKind::detect("Ejemplo.com"), VtKind::Domain);
+
+        assert_eq!(VtKind::Domain.path("Ejemplo.com"), "domains/ejemplo.com");
+        assert_eq!(
+            VtKind::Url.path("http://www.ejemplo.com/"),
+            "urls/aHR0cDovL3d3dy5lamVtcGxvLmNvbS8"
+        );
+    }
+
+    #[test]
+    fn test_parsea_veredicto_y_pivotes() {
+        let object = serde_json::json!({
+            "data": {"attributes": {
+                "last_analysis_stats": {"malicious": 5, "suspicious": 1, "harmless": 60, "undetected": 20},
+                "reputation": -12,
+                "categories": {"Forcepoint ThreatSeeker": "Phishing", "BitDefender": "phishing", "Sophos": "spam"},
+                "last_analysis_date": 1700000000
+            }}
+        });
+        let mut report = parse_object("ejemplo.com", VtKind::Domain, &object);
+        assert_eq!(report.stats.malicious, 5);
+        assert_eq!(report.reputation, Some(-12));
+        assert_eq!(report.categories, vec!["phishing", "spam"]);
+        assert!(report.verdict().starts_with("VirusTotal: 5/86 motores"));
+
+        let resolutions = serde_json::json!({"data": [
+            {"attributes": {"ip_address": "1.2.3.4", "host_name": "ejemplo.com", "date": 1700000000}},
+            {"attributes": {"ip_address": "1.2.3.4", "host_name": "ejemplo.com", "date": 1600000000}},
+            {"attributes": {"ip_address": "5.6.7.8", "host_name": "ejemplo.com"}}
+        ]});
+        report.resolutions = parse_resolutions(VtKind::Domain, &resolutions);
+        report.communicating_files = parse_files(&serde_json::json!({"data": [
+            {"id": "abc123", "attributes": {"meaningful_name": "dropper.exe", "last_analysis_stats": {"malicious": 40}}}
+        ]}));
+
+        let related = report.related_targets();
+        assert_eq!(related.len(), 3);
+        assert_eq!(related[0].name, "1.2.3.4");
+        assert_eq!(related[0].target_type, TargetType::IP);
+        assert_eq!(related[2].name, "abc123");
+        assert_eq!(related[2].relation, "archivo_comunicante");
+
+        let file = serde_json::json!({"data": {"attributes": {
+            "last_analysis_stats": {"malicious": 41, "suspicious": 2, "harmless": 0, "undetected": 20},
+            "popular_threat_classification": {"suggested_threat_label": "trojan.emotet/doc"},
+            "type_description": "MS Word Document",
+            "names": ["factura_marzo.doc", "invoice.doc"],
+            "first_submission_date": 1_700_000_000
+        }}});
+        let sha256 = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        let report = parse_object(sha256, VtKind::detect(sha256), &file);
+        assert_eq!(report.kind, VtKind::File);
+        assert_eq!(report.stats.malicious, 41);
+        assert_eq!(report.threat_label.as_deref(), Some("trojan.emotet/doc"));
+        assert_eq!(report.names.len(), 2);
+        assert_eq!(
+            report.first_seen.as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+        assert!(report.related_targets().is_empty());
+    }
+}
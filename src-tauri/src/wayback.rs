@@ -0,0 +1,590 @@
+// src-tauri/src/wayback.rs
+//
+// Historial de un sitio en el archivo web (Wayback Machine) a través de su API CDX.
+//
+// Flujo:
+//   1. Se consulta el índice CDX del dominio o URL: cada fila es una captura con fecha,
+//      URL original, tipo MIME, código HTTP y digest (hash del contenido capturado).
+//      `collapse=digest` deja solo las capturas cuyo contenido cambió y el límite negativo
+//      se queda con el final del índice, así lo recortado es lo más viejo.
+//   2. Las capturas se agrupan por URL y se ordenan por fecha. Un digest distinto al de la
+//      captura anterior de la misma URL marca un cambio de contenido.
+//   3. Por URL se resume primera/última captura, cantidad de versiones y el último código
+//      HTTP: una página que termina en 404 es un buen candidato a contenido borrado.
+//   4. Una captura elegida se descarga en su versión original (modificador `id_`, sin la
+//      barra del archivo) y se devuelve como texto limpio.
+//
+// La URL base es configurable (`wayback_url`) para apuntar a un espejo o a un servicio
+// local compatible en entornos sin salida a Internet.
+
+use crate::models::OsintConfig;
+use reqwest::Url;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BASE_URL: &str = "https://web.archive.org";
+const DEFAULT_LIMIT: usize = 500;
+const MAX_LIMIT: usize = 5000;
+const MAX_TEXT_CHARS: usize = 20_000;
+/// URLs y cambios que se guardan en el caso; el listado completo queda en la respuesta
+const CASE_SUMMARY_LIMIT: usize = 20;
+const MAX_PAGE_BYTES: usize = 5 * 1024 * 1024;
+/// Columnas pedidas al CDX, en este orden
+const CDX_FIELDS: &str = "timestamp,original,mimetype,statuscode,digest,length";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchType {
+    /// Solo la URL indicada
+    Exact,
+    /// Todo lo que cuelga de la URL indicada
+    Prefix,
+    /// Todas las URLs del host
+    Host,
+    /// El dominio y todos sus subdominios
+    Domain,
+}
+
+impl MatchType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "exact" | "exacta" | "url" => Some(Self::Exact),
+            "prefix" | "prefijo" => Some(Self::Prefix),
+            "host" => Some(Self::Host),
+            "domain" | "dominio" => Some(Self::Domain),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Prefix => "prefix",
+            Self::Host => "host",
+            Self::Domain => "domain",
+        }
+    }
+
+    /// Un dominio suelto cubre sus subdominios; una URL con ruta, solo esa página.
+    pub fn detect(target: &str) -> Self {
+        let target = target.trim();
+        let rest = target.split_once("://").map(|(_, r)| r).unwrap_or(target);
+        match rest.split_once('/') {
+            Some((_, path)) if !path.is_empty() => Self::Exact,
+            _ => Self::Domain,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WaybackOptions {
+    /// Sin él se deduce del objetivo
+    pub match_type: Option<MatchType>,
+    /// Desde/hasta como prefijo de fecha: "2019", "201906", "20190615"
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotChange {
+    /// Primera captura de la URL en el rango consultado
+    First,
+    /// El contenido difiere de la captura anterior
+    Changed,
+    /// Mismo digest que la captura anterior
+    Same,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Snapshot {
+    pub timestamp: String,
+    /// Fecha ISO 8601 (UTC) de la captura
+    pub date: String,
+    pub url: String,
+    pub mime: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+    pub archive_url: String,
+    pub change: SnapshotChange,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UrlHistory {
+    pub url: String,
+    pub first_seen: String,
+    pub last_seen: String,
+    pub captures: usize,
+    /// Contenidos distintos a lo largo del tiempo
+    pub versions: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_status: Option<u16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WaybackReport {
+    pub target: String,
+    pub match_type: MatchType,
+    pub snapshots: Vec<Snapshot>,
+    pub urls: Vec<UrlHistory>,
+    /// Capturas cuyo contenido cambió respecto de la anterior
+    pub changes: usize,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl WaybackReport {
+    /// Resumen para `detalles_tecnicos.wayback`: fechas, totales, las URLs con más versiones
+    /// y los cambios más recientes. Las capturas sueltas no entran al contexto del agente.
+    pub fn case_details(&self) -> serde_json::Value {
+        let mut urls: Vec<&UrlHistory> = self.urls.iter().collect();
+        urls.sort_by_key(|u| std::cmp::Reverse(u.versions));
+        let mut changes: Vec<&Snapshot> = self
+            .snapshots
+            .iter()
+            .filter(|s| s.change == SnapshotChange::Changed)
+            .collect();
+        changes.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+
+        serde_json::json!({
+            "alcance": self.match_type,
+            "primera_captura": self.snapshots.iter().map(|s| &s.date).min(),
+            "ultima_captura": self.snapshots.iter().map(|s| &s.date).max(),
+            "capturas": self.snapshots.len(),
+            "urls_distintas": self.urls.len(),
+            "cambios": self.changes,
+            "recortado": self.truncated,
+            "urls": urls
+                .iter()
+                .take(CASE_SUMMARY_LIMIT)
+                .map(|u| format!(
+                    "{} ({} versiones, {} a {}{})",
+                    u.url,
+                    u.versions,
+                    u.first_seen,
+                    u.last_seen,
+                    u.last_status.map(|s| format!(", último HTTP {}", s)).unwrap_or_default()
+                ))
+                .collect::<Vec<_>>(),
+            "ultimos_cambios": changes
+                .iter()
+                .take(CASE_SUMMARY_LIMIT)
+                .map(|s| format!("{} {}", s.date, s.archive_url))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedPage {
+    pub url: String,
+    pub timestamp: String,
+    pub archive_url: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub mime: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+fn base_url(config: &OsintConfig) -> String {
+    let base = config.wayback_url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        DEFAULT_BASE_URL.to_string()
+    } else {
+        base.to_string()
+    }
+}
+
+/// Dirección de una captura dentro del archivo. `id_` pide el contenido original.
+fn snapshot_url(base: &str, timestamp: &str, url: &str, original: bool) -> String {
+    format!(
+        "{}/web/{}{}/{}",
+        base,
+        timestamp,
+        if original { "id_" } else { "" },
+        url
+    )
+}
+
+/// "20190615083000" -> "2019-06-15T08:30:00Z"
+fn timestamp_to_iso(timestamp: &str) -> String {
+    // El CDX puede devolver fechas abreviadas; se completan con ceros
+    let padded = format!("{:0<14}", timestamp);
+    chrono::NaiveDateTime::parse_from_str(&padded, "%Y%m%d%H%M%S")
+        .map(|dt| dt.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+/// Solo dígitos, hasta 14 (AAAAMMDDhhmmss).
+fn clean_date(date: &str) -> Option<String> {
+    let digits: String = date
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .take(14)
+        .collect();
+    (digits.len() >= 4).then_some(digits)
+}
+
+/// Convierte la respuesta JSON del CDX (primera fila = cabecera) en capturas.
+fn parse_cdx(base: &str, body: &str) -> Result<Vec<Snapshot>, String> {
+    if body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<Vec<String>> =
+        serde_json::from_str(body).map_err(|_| "Respuesta CDX inválida".to_string())?;
+    let mut rows = rows.into_iter();
+    let Some(header) = rows.next() else {
+        return Ok(Vec::new());
+    };
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (Some(ts_i), Some(url_i)) = (column("timestamp"), column("original")) else {
+        return Err("Respuesta CDX sin columnas timestamp/original".to_string());
+    };
+    let (mime_i, status_i, digest_i, length_i) = (
+        column("mimetype"),
+        column("statuscode"),
+        column("digest"),
+        column("length"),
+    );
+    let field = |row: &Vec<String>, i: Option<usize>| {
+        i.and_then(|i| row.get(i)).cloned().unwrap_or_default()
+    };
+
+    Ok(rows
+        .filter(|row| row.len() == header.len())
+        .map(|row| {
+            let timestamp = field(&row, Some(ts_i));
+            let url = field(&row, Some(url_i));
+            Snapshot {
+                date: timestamp_to_iso(&timestamp),
+                archive_url: snapshot_url(base, &timestamp, &url, false),
+                mime: field(&row, mime_i),
+                // Las redirecciones capturadas o revisitas traen "-"
+                status: field(&row, status_i).parse().ok(),
+                digest: field(&row, digest_i),
+                length: field(&row, length_i).parse().ok(),
+                change: SnapshotChange::First,
+                timestamp,
+                url,
+            }
+        })
+        .collect())
+}
+
+/// Ordena por URL y fecha, marca los cambios de contenido y resume cada URL.
+fn analyze(mut snapshots: Vec<Snapshot>) -> (Vec<Snapshot>, Vec<UrlHistory>, usize) {
+    snapshots.sort_by(|a, b| a.url.cmp(&b.url).then(a.timestamp.cmp(&b.timestamp)));
+
+    let mut urls: Vec<UrlHistory> = Vec::new();
+    let mut changes = 0;
+    let mut previous: Option<(String, String)> = None;
+    for snapshot in snapshots.iter_mut() {
+        snapshot.change = match &previous {
+            Some((url, digest)) if *url == snapshot.url => {
+                if *digest == snapshot.digest {
+                    SnapshotChange::Same
+                } else {
+                    SnapshotChange::Changed
+                }
+            }
+            _ => SnapshotChange::First,
+        };
+        previous = Some((snapshot.url.clone(), snapshot.digest.clone()));
+
+        match urls.last_mut() {
+            Some(history) if history.url == snapshot.url => {
+                history.last_seen = snapshot.date.clone();
+                history.captures += 1;
+                if snapshot.change == SnapshotChange::Changed {
+                    history.versions += 1;
+                    changes += 1;
+                }
+                if snapshot.status.is_some() {
+                    history.last_status = snapshot.status;
+                }
+            }
+            _ => urls.push(UrlHistory {
+                url: snapshot.url.clone(),
+                first_seen: snapshot.date.clone(),
+                last_seen: snapshot.date.clone(),
+                captures: 1,
+                versions: 1,
+                last_status: snapshot.status,
+            }),
+        }
+    }
+    (snapshots, urls, changes)
+}
+
+/// Parámetros del CDX. Una fila por cambio de contenido (`collapse=digest`) y límite
+/// negativo para quedarse con las últimas filas del índice, con una de más para detectar
+/// el recorte.
+fn cdx_params(
+    target: &str,
+    match_type: MatchType,
+    limit: usize,
+    options: &WaybackOptions,
+) -> Vec<(&'static str, String)> {
+    let mut params: Vec<(&str, String)> = vec![
+        ("url", target.to_string()),
+        ("output", "json".to_string()),
+        ("fl", CDX_FIELDS.to_string()),
+        ("matchType", match_type.as_str().to_string()),
+        ("collapse", "digest".to_string()),
+        ("limit", format!("-{}", limit + 1)),
+    ];
+    if let Some(from) = options.from.as_deref().and_then(clean_date) {
+        params.push(("from", from));
+    }
+    if let Some(to) = options.to.as_deref().and_then(clean_date) {
+        params.push(("to", to));
+    }
+    params
+}
+
+/// Lista las capturas de un dominio o URL en el archivo web.
+pub async fn history(
+    target: &str,
+    options: &WaybackOptions,
+    config: &OsintConfig,
+) -> Result<WaybackReport, String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err("Debe indicar un dominio o una URL.".to_string());
+    }
+    let match_type = options
+        .match_type
+        .unwrap_or_else(|| MatchType::detect(target));
+    let limit = options.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let base = base_url(config);
+
+    let params = cdx_params(target, match_type, limit, options);
+    let client = crate::tools::get_http_client(config).await;
+    let body = client
+        .get(format!("{}/cdx/search/cdx", base))
+        .query(&params)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| {
+            eprintln!("ERROR [wayback]: CDX request failed: {}", e);
+            "No se pudo consultar el archivo web.".to_string()
+        })?
+        .text()
+        .await
+        .map_err(|_| "Error leyendo respuesta del archivo web".to_string())?;
+
+    let mut snapshots = parse_cdx(&base, &body).map_err(|e| {
+        eprintln!("ERROR [wayback]: {}", e);
+        "El archivo web devolvió una respuesta inesperada.".to_string()
+    })?;
+    // Se pide una fila de más para saber si quedaron capturas afuera; sobran las más viejas
+    let truncated = snapshots.len() > limit;
+    snapshots.drain(..snapshots.len().saturating_sub(limit));
+
+    let (snapshots, urls, changes) = analyze(snapshots);
+    Ok(WaybackReport {
+        target: target.to_string(),
+        match_type,
+        snapshots,
+        urls,
+        changes,
+        truncated,
+    })
+}
+
+/// Descarga una captura y la devuelve como texto limpio.
+pub async fn fetch_snapshot(
+    url: &str,
+    timestamp: &str,
+    config: &OsintConfig,
+) -> Result<ArchivedPage, String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("Debe indicar la URL capturada.".to_string());
+    }
+    let timestamp = clean_date(timestamp)
+        .ok_or_else(|| "La fecha de la captura debe tener formato AAAAMMDDhhmmss.".to_string())?;
+    let base = base_url(config);
+
+    let client = crate::tools::get_http_client(config).await;
+    let mut response = client
+        .get(snapshot_url(&base, &timestamp, url, true))
+        .send()
+        .await
+        .map_err(|e| {
+            eprintln!("ERROR [wayback]: Snapshot fetch failed for {}: {}", url, e);
+            "No se pudo descargar la captura del archivo web.".to_string()
+        })?;
+    let status = response.status().as_u16();
+    if status == 404 {
+        return Err("El archivo web no tiene una captura de esa URL.".to_string());
+    }
+
+    // El archivo redirige a la captura más cercana: la fecha real va en la URL final
+    let final_url = response.url().clone();
+    let served = final_url
+        .path()
+        .strip_prefix("/web/")
+        .and_then(|rest| rest.split('/').next())
+        .map(|ts| ts.trim_end_matches("id_"))
+        .filter(|ts| !ts.is_empty() && ts.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_string)
+        .unwrap_or(timestamp);
+    let mime = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or("").trim().to_string())
+        .unwrap_or_default();
+    let mut bytes = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > MAX_PAGE_BYTES {
+            break;
+        }
+    }
+
+    let raw = String::from_utf8_lossy(&bytes);
+    let (title, mut text) = if mime.is_empty() || mime.contains("html") {
+        crate::darkweb::page_text(&Html::parse_document(&raw))
+    } else {
+        (None, raw.trim().to_string())
+    };
+    let truncated = text.chars().count() > MAX_TEXT_CHARS;
+    if truncated {
+        text = text.chars().take(MAX_TEXT_CHARS).collect();
+    }
+    Ok(ArchivedPage {
+        url: url.to_string(),
+        archive_url: snapshot_url(&base, &served, url, false),
+        timestamp: served,
+        status,
+        mime,
+        title,
+        text,
+        truncated,
+    })
+}
+
+/// Host del objetivo, para guardar el historial en el objetivo Dominio del caso.
+pub fn target_host(target: &str) -> String {
+    let target = target.trim();
+    let with_scheme = if target.contains("://") {
+        target.to_string()
+    } else {
+        format!("http://{}", target)
+    };
+    Url::parse(&with_scheme)
+        .ok()
+        .and_then(|u| {
+            u.host_str()
+                .map(|h| h.trim_start_matches("www.").to_string())
+        })
+        .unwrap_or_else(|| target.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_cdx_y_detecta_cambios() {
+        let body = r#"[["timestamp","original","mimetype","statuscode","digest","length"],
+            ["20190101000000","http://ejemplo.com/","text/html","200","AAA","1000"],
+            ["20200101000000","http://ejemplo.com/contacto","text/html","200","CCC","500"],
+            ["20190601000000","http://ejemplo.com/","text/html","200","AAA","1000"],
+            ["20210101000000","http://ejemplo.com/contacto","text/html","404","DDD","300"],
+            ["20200601000000","http://ejemplo.com/","text/html","-","BBB","1200"]]"#;
+        let snapshots = parse_cdx("http://localhost:8080", body).unwrap();
+        assert_eq!(snapshots.len(), 5);
+        assert_eq!(snapshots[0].date, "2019-01-01T00:00:00Z");
+        assert_eq!(
+            snapshots[0].archive_url,
+            "http://localhost:8080/web/20190101000000/http://ejemplo.com/"
+        );
+        assert_eq!(snapshots[4].status, None);
+
+        let (snapshots, urls, changes) = analyze(snapshots);
+        let home: Vec<_> = snapshots
+            .iter()
+            .filter(|s| s.url == "http://ejemplo.com/")
+            .map(|s| s.change)
+            .collect();
+        assert_eq!(
+            home,
+            vec![
+                SnapshotChange::First,
+                SnapshotChange::Same,
+                SnapshotChange::Changed
+            ]
+        );
+        assert_eq!(changes, 2);
+        assert_eq!(urls.len(), 2);
+        assert_eq!(urls[0].versions, 2);
+        assert_eq!(urls[0].last_status, Some(200));
+        // La página de contacto terminó en 404: probablemente borrada
+        assert_eq!(urls[1].last_status, Some(404));
+        assert_eq!(urls[1].last_seen, "2021-01-01T00:00:00Z");
+
+        let report = WaybackReport {
+            target: "ejemplo.com".to_string(),
+            match_type: MatchType::Domain,
+            snapshots,
+            urls,
+            changes,
+            truncated: false,
+        };
+        let details = report.case_details();
+        assert_eq!(details["primera_captura"], "2019-01-01T00:00:00Z");
+        assert_eq!(details["ultima_captura"], "2021-01-01T00:00:00Z");
+        assert_eq!(details["capturas"], 5);
+        assert!(details.get("snapshots").is_none());
+        assert!(details["ultimos_cambios"][0]
+            .as_str()
+            .unwrap()
+            .starts_with("2021-01-01T00:00:00Z"));
+
+        assert!(parse_cdx("", "").unwrap().is_empty());
+        assert!(parse_cdx("", "<html>").is_err());
+    }
+
+    #[test]
+    fn test_base_configurable_y_tipo_de_busqueda() {
+        let mut config = OsintConfig::default();
+        assert_eq!(base_url(&config), DEFAULT_BASE_URL);
+        config.wayback_url = "http://127.0.0.1:9000/ ".to_string();
+        assert_eq!(base_url(&config), "http://127.0.0.1:9000");
+        assert_eq!(
+            snapshot_url(&base_url(&config), "20200101000000", "http://a.com/x", true),
+            "http://127.0.0.1:9000/web/20200101000000id_/http://a.com/x"
+        );
+
+        assert_eq!(MatchType::detect("ejemplo.com"), MatchType::Domain);
+        assert_eq!(MatchType::detect("https://ejemplo.com/"), MatchType::Domain);
+        assert_eq!(
+            MatchType::detect("https://ejemplo.com/equipo.html"),
+            MatchType::Exact
+        );
+        assert_eq!(clean_date("2019-06"), Some("201906".to_string()));
+        assert_eq!(clean_date("19"), None);
+        assert_eq!(target_host("https://www.ejemplo.com/equipo"), "ejemplo.com");
+
+        let options = WaybackOptions {
+            from: Some("2019".to_string()),
+            ..Default::default()
+        };
+        let params = cdx_params("ejemplo.com", MatchType::Domain, 500, &options);
+        assert!(params.contains(&("collapse", "digest".to_string())));
+        assert!(params.contains(&("limit", "-501".to_string())));
+        assert!(params.contains(&("from", "2019".to_string())));
+    }
+}
@@ -0,0 +1,642 @@
+// src-tauri/src/websearch.rs
+//
+// Búsqueda web en varios motores a la vez, con resultados tipados.
+//
+// Flujo:
+//   1. Se consulta cada motor en paralelo (DuckDuckGo HTML, Bing, Brave, Mojeek y, si está
+//      configurada, una instancia propia de SearXNG en formato JSON).
+//   2. Cada motor se pagina hasta `pages`. El desplazamiento de la página siguiente es la
+//      cantidad de resultados que ya devolvió ese motor, así no importa su tamaño de página.
+//   3. Idioma y región viajan en los parámetros propios de cada motor y en Accept-Language.
+//   4. Los resultados se intercalan por posición y se deduplican por URL normalizada; si otro
+//      motor devolvió la misma URL queda anotado en `also_found_by`.
+
+use crate::models::OsintConfig;
+use base64::{engine::general_purpose, Engine as _};
+use reqwest::{Client, Url};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::task::JoinSet;
+
+pub const MAX_PAGES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchEngine {
+    DuckDuckGo,
+    Bing,
+    Brave,
+    Mojeek,
+    Searxng,
+}
+
+impl SearchEngine {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "duckduckgo" | "ddg" => Some(Self::DuckDuckGo),
+            "bing" => Some(Self::Bing),
+            "brave" => Some(Self::Brave),
+            "mojeek" => Some(Self::Mojeek),
+            "searxng" | "searx" => Some(Self::Searxng),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DuckDuckGo => "duckduckgo",
+            Self::Bing => "bing",
+            Self::Brave => "brave",
+            Self::Mojeek => "mojeek",
+            Self::Searxng => "searxng",
+        }
+    }
+}
+
+/// Parámetros de la búsqueda (todos opcionales desde el agente).
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Motores a consultar (vacío = todos los disponibles)
+    pub engines: Vec<SearchEngine>,
+    /// Páginas por motor (1 por defecto, máximo `MAX_PAGES`)
+    pub pages: Option<u32>,
+    /// Idioma ISO 639-1 (ej: "es")
+    pub language: Option<String>,
+    /// País ISO 3166-1 alfa-2 (ej: "ar")
+    pub region: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub snippet: String,
+    pub engine: String,
+    /// Posición dentro del motor (1 = primer resultado)
+    pub rank: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub also_found_by: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EngineError {
+    pub engine: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SearchReport {
+    pub query: String,
+    pub engines: Vec<String>,
+    pub results: Vec<SearchResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<EngineError>,
+}
+
+/// Parámetros de idioma/región ya normalizados.
+#[derive(Debug, Clone, Default)]
+struct Locale {
+    language: Option<String>,
+    region: Option<String>,
+}
+
+impl Locale {
+    fn from_options(options: &SearchOptions) -> Self {
+        let clean = |v: &Option<String>| {
+            v.as_deref()
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| s.len() == 2 && s.chars().all(|c| c.is_ascii_alphabetic()))
+        };
+        Self {
+            language: clean(&options.language),
+            region: clean(&options.region),
+        }
+    }
+
+    /// Valor de Accept-Language (ej: "es-AR,es;q=0.9")
+    fn accept_language(&self) -> Option<String> {
+        let lang = self.language.as_deref()?;
+        Some(match &self.region {
+            Some(region) => format!("{}-{},{};q=0.9", lang, region.to_uppercase(), lang),
+            None => lang.to_string(),
+        })
+    }
+}
+
+/// URL de la página `page` (desde 0). `collected` es lo que ya devolvió el motor.
+fn page_url(
+    engine: SearchEngine,
+    searxng: &str,
+    query: &str,
+    page: u32,
+    collected: usize,
+    locale: &Locale,
+) -> Option<Url> {
+    let mut url = match engine {
+        SearchEngine::DuckDuckGo => Url::parse("https://html.duckduckgo.com/html/").ok()?,
+        SearchEngine::Bing => Url::parse("https://www.bing.com/search").ok()?,
+        SearchEngine::Brave => Url::parse("https://search.brave.com/search").ok()?,
+        SearchEngine::Mojeek => Url::parse("https://www.mojeek.com/search").ok()?,
+        SearchEngine::Searxng => {
+            let base = searxng.trim().trim_end_matches('/');
+            if base.is_empty() {
+                return None;
+            }
+            Url::parse(&format!("{}/search", base)).ok()?
+        }
+    };
+
+    {
+        let mut params = url.query_pairs_mut();
+        params.append_pair("q", query);
+        let lang = locale.language.as_deref();
+        let region = locale.region.as_deref();
+        match engine {
+            SearchEngine::DuckDuckGo => {
+                if page > 0 {
+                    params.append_pair("s", &collected.to_string());
+                    params.append_pair("dc", &(collected + 1).to_string());
+                }
+                // kl = región-idioma ("ar-es"); "wt-wt" es sin región
+                if let Some(lang) = lang {
+                    params.append_pair("kl", &format!("{}-{}", region.unwrap_or("wt"), lang));
+                }
+            }
+            SearchEngine::Bing => {
+                if page > 0 {
+                    params.append_pair("first", &(collected + 1).to_string());
+                }
+                if let Some(lang) = lang {
+                    params.append_pair("setlang", lang);
+                }
+                if let Some(region) = region {
+                    params.append_pair("cc", region);
+                }
+            }
+            SearchEngine::Brave => {
+                params.append_pair("source", "web");
+                if page > 0 {
+                    params.append_pair("offset", &page.to_string());
+                }
+                if let Some(region) = region {
+                    params.append_pair("country", region);
+                }
+            }
+            SearchEngine::Mojeek => {
+                if page > 0 {
+                    params.append_pair("s", &(collected + 1).to_string());
+                }
+                if let Some(lang) = lang {
+                    params.append_pair("lb", lang);
+                }
+                if let Some(region) = region {
+                    params.append_pair("arc", region);
+                }
+            }
+            SearchEngine::Searxng => {
+                params.append_pair("format", "json");
+                params.append_pair("pageno", &(page + 1).to_string());
+                if let Some(lang) = lang {
+                    let language = match region {
+                        Some(region) => format!("{}-{}", lang, region.to_uppercase()),
+                        None => lang.to_string(),
+                    };
+                    params.append_pair("language", &language);
+                }
+            }
+        }
+    }
+    Some(url)
+}
+
+fn text_of(element: Option<ElementRef>) -> String {
+    element
+        .map(|e| e.text().collect::<Vec<_>>().join(" "))
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default()
+}
+
+/// Deshace las redirecciones de seguimiento de DuckDuckGo (`uddg=`) y Bing (`u=a1<base64>`).
+fn resolve_link(engine: SearchEngine, base: &str, href: &str) -> Option<String> {
+    let url = Url::parse(base).ok()?.join(href).ok()?;
+    let target = match engine {
+        SearchEngine::DuckDuckGo if url.path().starts_with("/l/") => url
+            .query_pairs()
+            .find(|(k, _)| k == "uddg")
+            .map(|(_, v)| v.into_owned()),
+        SearchEngine::Bing if url.path().starts_with("/ck/") => url
+            .query_pairs()
+            .find(|(k, _)| k == "u")
+            .and_then(|(_, v)| {
+                let encoded = v.strip_prefix("a1")?.to_string();
+                general_purpose::URL_SAFE_NO_PAD
+                    .decode(encoded.trim_end_matches('='))
+                    .ok()
+                    .and_then(|b| String::from_utf8(b).ok())
+            }),
+        _ => Some(url.to_string()),
+    }?;
+    let parsed = Url::parse(&target).ok()?;
+    // Enlaces internos del motor (anuncios, "más resultados", etc.)
+    let own_host = Url::parse(base)
+        .ok()?
+        .host_str()?
+        .trim_start_matches("www.")
+        .to_string();
+    let host = parsed.host_str()?.trim_start_matches("www.");
+    let internal = host == own_host || host.ends_with(&format!(".{}", own_host));
+    if !matches!(parsed.scheme(), "http" | "https") || internal {
+        return None;
+    }
+    Some(parsed.to_string())
+}
+
+/// Extrae (título, url, snippet) de una página de resultados HTML.
+fn parse_html(engine: SearchEngine, html: &str) -> Vec<(String, String, String)> {
+    let (base, item, link, snippet) = match engine {
+        SearchEngine::DuckDuckGo => (
+            "https://duckduckgo.com/",
+            ".result:not(.result--ad)",
+            "a.result__a",
+            ".result__snippet",
+        ),
+        SearchEngine::Bing => (
+            "https://www.bing.com/",
+            "li.b_algo",
+            "h2 a",
+            ".b_caption p, p",
+        ),
+        SearchEngine::Brave => (
+            "https://search.brave.com/",
+            "div.snippet[data-type=web]",
+            "a[href]",
+            ".snippet-description, .generic-snippet .content, .description",
+        ),
+        SearchEngine::Mojeek => (
+            "https://www.mojeek.com/",
+            "ul.results-standard > li",
+            "h2 a, a.title",
+            "p.s",
+        ),
+        SearchEngine::Searxng => return Vec::new(),
+    };
+    let (Ok(item_sel), Ok(link_sel), Ok(snippet_sel)) = (
+        Selector::parse(item),
+        Selector::parse(link),
+        Selector::parse(snippet),
+    ) else {
+        return Vec::new();
+    };
+    let title_sel = Selector::parse(".title, .snippet-title").ok();
+
+    let document = Html::parse_document(html);
+    let mut results = Vec::new();
+    for element in document.select(&item_sel) {
+        let Some(anchor) = element.select(&link_sel).next() else {
+            continue;
+        };
+        let Some(url) = anchor
+            .value()
+            .attr("href")
+            .and_then(|href| resolve_link(engine, base, href))
+        else {
+            continue;
+        };
+        // En Brave el <a> envuelve título y URL visible: el título va en su propio nodo
+        let title = match (engine, &title_sel) {
+            (SearchEngine::Brave, Some(sel)) => text_of(element.select(sel).next()),
+            _ => text_of(Some(anchor)),
+        };
+        if title.is_empty() {
+            continue;
+        }
+        results.push((title, url, text_of(element.select(&snippet_sel).next())));
+    }
+    results
+}
+
+fn parse_searxng(body: &str) -> Vec<(String, String, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return Vec::new();
+    };
+    json["results"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|r| {
+                    Some((
+                        r["title"].as_str()?.trim().to_string(),
+                        r["url"].as_str()?.to_string(),
+                        r["content"].as_str().unwrap_or("").trim().to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Clave de deduplicación: sin esquema, sin `www.`, sin fragmento ni barra final.
+pub fn normalize_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.trim().to_lowercase();
+    };
+    parsed.set_fragment(None);
+    let host = parsed
+        .host_str()
+        .unwrap_or("")
+        .trim_start_matches("www.")
+        .to_lowercase();
+    let query = parsed
+        .query()
+        .map(|q| format!("?{}", q))
+        .unwrap_or_default();
+    format!("{}{}{}", host, parsed.path().trim_end_matches('/'), query)
+}
+
+/// Intercala los resultados de todos los motores por posición y elimina URLs repetidas.
+fn merge(mut all: Vec<SearchResult>, engine_order: &[SearchEngine]) -> Vec<SearchResult> {
+    let position = |name: &str| {
+        engine_order
+            .iter()
+            .position(|e| e.as_str() == name)
+            .unwrap_or(usize::MAX)
+    };
+    all.sort_by_key(|r| (r.rank, position(&r.engine)));
+
+    let mut merged: Vec<SearchResult> = Vec::new();
+    let mut keys: Vec<String> = Vec::new();
+    for result in all {
+        let key = normalize_url(&result.url);
+        match keys.iter().position(|k| *k == key) {
+            Some(i) => {
+                let kept = &mut merged[i];
+                if kept.engine != result.engine && !kept.also_found_by.contains(&result.engine) {
+                    kept.also_found_by.push(result.engine);
+                }
+                if kept.snippet.is_empty() {
+                    kept.snippet = result.snippet;
+                }
+            }
+            None => {
+                keys.push(key);
+                merged.push(result);
+            }
+        }
+    }
+    merged
+}
+
+async fn search_engine(
+    client: Client,
+    engine: SearchEngine,
+    searxng: String,
+    query: String,
+    pages: u32,
+    locale: Locale,
+) -> Result<Vec<SearchResult>, String> {
+    let mut results: Vec<SearchResult> = Vec::new();
+    for page in 0..pages {
+        let url = page_url(engine, &searxng, &query, page, results.len(), &locale)
+            .ok_or_else(|| "Instancia de SearXNG no configurada.".to_string())?;
+        let mut request = client.get(url);
+        if let Some(accept) = locale.accept_language() {
+            request = request.header("Accept-Language", accept);
+        }
+        let response = request.send().await.map_err(|e| {
+            eprintln!(
+                "ERROR [websearch]: {} request failed: {}",
+                engine.as_str(),
+                e
+            );
+            "No se pudo conectar con el motor.".to_string()
+        })?;
+        let status = response.status();
+        if status.as_u16() == 429 || status.as_u16() == 403 {
+            if results.is_empty() {
+                return Err(format!(
+                    "El motor bloqueó la consulta (código {}).",
+                    status.as_u16()
+                ));
+            }
+            break;
+        }
+        if !status.is_success() {
+            return Err(format!(
+                "El motor respondió con el código {}.",
+                status.as_u16()
+            ));
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|_| "No se pudo leer la respuesta del motor de búsqueda.".to_string())?;
+
+        let parsed = match engine {
+            SearchEngine::Searxng => parse_searxng(&body),
+            _ => parse_html(engine, &body),
+        };
+        if parsed.is_empty() {
+            if page == 0 && body.to_lowercase().contains("captcha") {
+                return Err("El motor pidió un captcha (scraping bloqueado).".to_string());
+            }
+            break;
+        }
+        let before = results.len();
+        for (title, url, snippet) in parsed {
+            if results.iter().any(|r| r.url == url) {
+                continue;
+            }
+            let rank = results.len() as u32 + 1;
+            results.push(SearchResult {
+                title,
+                url,
+                snippet,
+                engine: engine.as_str().to_string(),
+                rank,
+                also_found_by: Vec::new(),
+            });
+        }
+        // La página no trajo nada nuevo: el motor no tiene más resultados
+        if results.len() == before {
+            break;
+        }
+    }
+    Ok(results)
+}
+
+/// Busca `query` en los motores pedidos y devuelve los resultados fusionados.
+pub async fn search(
+    query: &str,
+    options: &SearchOptions,
+    config: &OsintConfig,
+) -> Result<SearchReport, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Debe indicar un término de búsqueda.".to_string());
+    }
+
+    let mut engines = options.engines.clone();
+    if engines.is_empty() {
+        engines = vec![
+            SearchEngine::DuckDuckGo,
+            SearchEngine::Bing,
+            SearchEngine::Brave,
+            SearchEngine::Mojeek,
+        ];
+        if !config.searxng_url.trim().is_empty() {
+            engines.push(SearchEngine::Searxng);
+        }
+    }
+    // Sin repetidos, respetando el orden pedido (define el intercalado de resultados)
+    let mut seen = HashSet::new();
+    engines.retain(|e| seen.insert(*e));
+    let pages = options.pages.unwrap_or(1).clamp(1, MAX_PAGES);
+    let locale = Locale::from_options(options);
+    let client = crate::tools::get_http_client(config).await;
+
+    let mut set = JoinSet::new();
+    for &engine in &engines {
+        let (client, searxng, query, locale) = (
+            client.clone(),
+            config.searxng_url.clone(),
+            query.to_string(),
+            locale.clone(),
+        );
+        set.spawn(async move {
+            let outcome = search_engine(client, engine, searxng, query, pages, locale).await;
+            (engine, outcome)
+        });
+    }
+
+    let mut all = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(joined) = set.join_next().await {
+        let Ok((engine, outcome)) = joined else {
+            continue;
+        };
+        match outcome {
+            Ok(results) => all.extend(results),
+            Err(error) => errors.push(EngineError {
+                engine: engine.as_str().to_string(),
+                error,
+            }),
+        }
+    }
+
+    if all.is_empty() && errors.len() == engines.len() {
+        let detail = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.engine, e.error))
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(format!("Ningún motor de búsqueda respondió ({}).", detail));
+    }
+    Ok(SearchReport {
+        query: query.to_string(),
+        engines: engines.iter().map(|e| e.as_str().to_string()).collect(),
+        results: merge(all, &engines),
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_motores_y_deshace_redirecciones() {
+        let ddg = r#"<div class="result result--ad"><a class="result__a" href="https://duckduckgo.com/y.js?ad=1">Anuncio</a></div>
+            <div class="result"><h2><a class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fwww.ejemplo.com.ar%2Fcontacto&amp;rut=x">Ejemplo  SA</a></h2>
+            <a class="result__snippet">Teléfono y dirección</a></div>"#;
+        assert_eq!(
+            parse_html(SearchEngine::DuckDuckGo, ddg),
+            vec![(
+                "Ejemplo SA".to_string(),
+                "https://www.ejemplo.com.ar/contacto".to_string(),
+                "Teléfono y dirección".to_string()
+            )]
+        );
+
+        // u=a1 + base64url("https://ejemplo.com.ar/")
+        let bing = r#"<ol id="b_results"><li class="b_algo"><h2><a href="https://www.bing.com/ck/a?!&amp;&amp;p=abc&amp;u=a1aHR0cHM6Ly9lamVtcGxvLmNvbS5hci8&amp;ntb=1">Ejemplo</a></h2>
+            <div class="b_caption"><p>Sitio oficial</p></div></li></ol>"#;
+        let parsed = parse_html(SearchEngine::Bing, bing);
+        assert_eq!(parsed[0].1, "https://ejemplo.com.ar/");
+        assert_eq!(parsed[0].2, "Sitio oficial");
+
+        // Sólo se descartan el host del motor y sus subdominios
+        let base = "https://www.bing.com/search";
+        assert_eq!(
+            resolve_link(SearchEngine::Bing, base, "https://ads.bing.com/x"),
+            None
+        );
+        assert_eq!(
+            resolve_link(SearchEngine::Bing, base, "https://notbing.com/"),
+            Some("https://notbing.com/".to_string())
+        );
+
+        let searx = r#"{"results":[{"title":"Uno","url":"https://uno.com/","content":"c"},{"url":"https://sin-titulo.com/"}]}"#;
+        assert_eq!(parse_searxng(searx).len(), 1);
+    }
+
+    #[test]
+    fn test_paginacion_idioma_y_deduplicacion() {
+        let locale = Locale::from_options(&SearchOptions {
+            language: Some("ES".into()),
+            region: Some("ar".into()),
+            ..Default::default()
+        });
+        assert_eq!(locale.accept_language().as_deref(), Some("es-AR,es;q=0.9"));
+
+        let bing = page_url(SearchEngine::Bing, "", "juan perez", 1, 10, &locale).unwrap();
+        assert_eq!(
+            bing.as_str(),
+            "https://www.bing.com/search?q=juan+perez&first=11&setlang=es&cc=ar"
+        );
+        let ddg = page_url(SearchEngine::DuckDuckGo, "", "x", 0, 0, &locale).unwrap();
+        assert!(ddg.as_str().ends_with("q=x&kl=ar-es"));
+        let searx = page_url(
+            SearchEngine::Searxng,
+            "http://localhost:8888/",
+            "x",
+            2,
+            0,
+            &locale,
+        );
+        assert_eq!(
+            searx.unwrap().as_str(),
+            "http://localhost:8888/search?q=x&format=json&pageno=3&language=es-AR"
+        );
+        assert!(page_url(SearchEngine::Searxng, " ", "x", 0, 0, &locale).is_none());
+
+        let result = |url: &str, engine: &str, rank: u32| SearchResult {
+            title: url.to_string(),
+            url: url.to_string(),
+            snippet: String::new(),
+            engine: engine.to_string(),
+            rank,
+            also_found_by: Vec::new(),
+        };
+        let merged = merge(
+            vec![
+                result("https://b.com/", "bing", 2),
+                result("https://www.a.com/x/", "bing", 1),
+                result("http://a.com/x#top", "mojeek", 1),
+                result("https://c.com/", "duckduckgo", 1),
+            ],
+            &[
+                SearchEngine::DuckDuckGo,
+                SearchEngine::Bing,
+                SearchEngine::Mojeek,
+            ],
+        );
+        let urls: Vec<&str> = merged.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["https://c.com/", "https://www.a.com/x/", "https://b.com/"]
+        );
+        assert_eq!(merged[1].also_found_by, vec!["mojeek".to_string()]);
+    }
+}
@@ -0,0 +1,541 @@
+// src-tauri/src/whois.rs
+//
+// Cliente WHOIS nativo (puerto 43) con seguimiento de referencias.
+//
+// Flujo:
+//   1. Se clasifica la consulta (dominio, IP o ASN).
+//   2. Se elige el servidor inicial: mapa de TLDs conocidos (incluye NIC.ar) o IANA.
+//   3. Se siguen las referencias (`refer:`, `Registrar WHOIS Server:`, `ReferralServer:`)
+//      hasta el registrador o el RIR que tiene el dato definitivo.
+//   4. Cada respuesta se parsea a un `WhoisRecord` tipado; la más específica pisa a la anterior.
+
+use crate::models::OsintConfig;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const WHOIS_PORT: u16 = 43;
+const IANA_SERVER: &str = "whois.iana.org";
+/// Cantidad máxima de saltos de referencia (registry -> registrar, IANA -> RIR -> NIR, etc.)
+const MAX_REFERRALS: usize = 3;
+/// Tope de bytes leídos por respuesta para no colgarse con servidores verborrágicos
+const MAX_RESPONSE_BYTES: u64 = 256 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const READ_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum WhoisQueryKind {
+    #[default]
+    Domain,
+    Ip,
+    Asn,
+}
+
+/// Resultado WHOIS normalizado, listo para guardarse en `detalles_tecnicos.whois`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WhoisRecord {
+    pub query: String,
+    pub kind: WhoisQueryKind,
+    /// Servidores consultados en orden (el último es la fuente definitiva)
+    pub servers: Vec<String>,
+    pub registered: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registrar: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registrant: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub nameservers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub status: Vec<String>,
+    /// Rango de red (CIDR / inetnum) para consultas de IP
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abuse_email: Option<String>,
+}
+
+impl WhoisRecord {
+    /// Incorpora los campos de una respuesta más específica (la del registrador pisa a la del registry).
+    fn merge(&mut self, other: WhoisRecord) {
+        fn take(slot: &mut Option<String>, value: Option<String>) {
+            if value.is_some() {
+                *slot = value;
+            }
+        }
+
+        take(&mut self.registrar, other.registrar);
+        take(&mut self.registrant, other.registrant);
+        take(&mut self.organization, other.organization);
+        take(&mut self.country, other.country);
+        take(&mut self.created, other.created);
+        take(&mut self.updated, other.updated);
+        take(&mut self.expires, other.expires);
+        take(&mut self.network, other.network);
+        take(&mut self.network_name, other.network_name);
+        take(&mut self.asn, other.asn);
+        take(&mut self.abuse_email, other.abuse_email);
+
+        if !other.nameservers.is_empty() {
+            self.nameservers = other.nameservers;
+        }
+        if !other.status.is_empty() {
+            self.status = other.status;
+        }
+    }
+
+    fn has_data(&self) -> bool {
+        self.registrar.is_some()
+            || self.created.is_some()
+            || !self.nameservers.is_empty()
+            || self.network.is_some()
+            || self.asn.is_some()
+            || self.organization.is_some()
+    }
+}
+
+/// Campos escalares reconocidos. El orden de las llaves define la prioridad:
+/// si una respuesta trae varias, gana la que aparece primero en la lista.
+const SCALAR_FIELDS: &[(&str, &[&str])] = &[
+    (
+        "registrar",
+        &["registrar", "sponsoring registrar", "registrar name"],
+    ),
+    (
+        "registrant",
+        &[
+            "registrant name",
+            "registrant organization",
+            "registrant organisation",
+            "registrant",
+            "holder",
+            "name",
+        ],
+    ),
+    (
+        "organization",
+        &[
+            "orgname",
+            "org-name",
+            "organization",
+            "organisation",
+            "owner",
+        ],
+    ),
+    ("country", &["registrant country", "country"]),
+    (
+        "created",
+        &[
+            "creation date",
+            "created on",
+            "domain registration date",
+            "registration time",
+            "registered",
+            "regdate",
+            "created",
+        ],
+    ),
+    (
+        "updated",
+        &[
+            "updated date",
+            "last updated",
+            "last-modified",
+            "last modified",
+            "changed",
+            "updated",
+        ],
+    ),
+    (
+        "expires",
+        &[
+            "registry expiry date",
+            "registrar registration expiration date",
+            "expiration date",
+            "expiry date",
+            "expires on",
+            "paid-till",
+            "expires",
+            "expire",
+        ],
+    ),
+    (
+        "network",
+        &["cidr", "inetnum", "inet6num", "netrange", "route", "route6"],
+    ),
+    ("network_name", &["netname", "net-name"]),
+    ("asn", &["aut-num", "originas", "origin", "asnumber"]),
+    (
+        "abuse_email",
+        &[
+            "orgabuseemail",
+            "abuse-mailbox",
+            "registrar abuse contact email",
+        ],
+    ),
+];
+
+const NAMESERVER_KEYS: &[&str] = &["name server", "nserver", "nameserver", "nameservers"];
+const STATUS_KEYS: &[&str] = &["domain status", "status", "state"];
+const REFERRAL_KEYS: &[&str] = &["refer", "whois", "registrar whois server", "referralserver"];
+
+/// Clasifica la consulta y la normaliza (minúsculas, sin esquema ni ruta).
+fn classify(query: &str) -> Option<(WhoisQueryKind, String)> {
+    let q = query.trim();
+    if q.parse::<IpAddr>().is_ok() {
+        return Some((WhoisQueryKind::Ip, q.to_string()));
+    }
+
+    let upper = q.to_uppercase();
+    if let Some(num) = upper.strip_prefix("AS") {
+        if !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()) {
+            return Some((WhoisQueryKind::Asn, upper));
+        }
+    }
+
+    let domain = q
+        .trim_start_matches("http://")
+        .trim_start_matches("https://")
+        .split('/')
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('.')
+        .to_lowercase();
+    let domain = domain.strip_prefix("www.").unwrap_or(&domain).to_string();
+
+    let valid = domain.contains('.')
+        && domain
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '.' || c == '-');
+    if valid {
+        Some((WhoisQueryKind::Domain, domain))
+    } else {
+        None
+    }
+}
+
+/// Servidor autoritativo conocido para algunos TLDs frecuentes; el resto se resuelve vía IANA.
+fn tld_server(domain: &str) -> Option<&'static str> {
+    let tld = domain.rsplit('.').next()?;
+    match tld {
+        "com" | "net" => Some("whois.verisign-grs.com"),
+        "org" => Some("whois.publicinterestregistry.org"),
+        "edu" => Some("whois.educause.edu"),
+        "ar" => Some("whois.nic.ar"),
+        "br" => Some("whois.registro.br"),
+        "cl" => Some("whois.nic.cl"),
+        "mx" => Some("whois.mx"),
+        _ => None,
+    }
+}
+
+/// Cada servidor tiene sus mañas para el formato de la consulta.
+fn format_query(server: &str, kind: WhoisQueryKind, query: &str) -> String {
+    match (server, kind) {
+        ("whois.arin.net", WhoisQueryKind::Ip) => format!("n + {}", query),
+        ("whois.arin.net", WhoisQueryKind::Asn) => {
+            format!("a + {}", query.trim_start_matches("AS"))
+        }
+        // Sin el prefijo "domain" Verisign devuelve coincidencias parciales de nameservers
+        ("whois.verisign-grs.com", WhoisQueryKind::Domain) => format!("domain {}", query),
+        _ => query.to_string(),
+    }
+}
+
+/// Extrae el servidor al que la respuesta nos deriva, si lo hay.
+fn find_referral(raw: &str, current: &str) -> Option<String> {
+    for line in raw.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if !REFERRAL_KEYS.contains(&key.trim().to_lowercase().as_str()) {
+            continue;
+        }
+
+        let value = value.trim();
+        // rwhois usa otro protocolo; no lo seguimos
+        if value.starts_with("rwhois://") {
+            continue;
+        }
+        let server = value
+            .trim_start_matches("whois://")
+            .trim_end_matches('/')
+            .trim_end_matches(":43")
+            .to_lowercase();
+
+        if !server.is_empty() && server != current && !server.contains(' ') {
+            return Some(server);
+        }
+    }
+    None
+}
+
+/// Intenta llevar las fechas a ISO-8601; si el formato es desconocido se conserva el original.
+fn normalize_date(value: &str) -> String {
+    let v = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(v) {
+        return dt
+            .with_timezone(&Utc)
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string();
+    }
+    for fmt in [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(v, fmt) {
+            return dt.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        }
+    }
+    for fmt in ["%Y-%m-%d", "%d-%b-%Y", "%Y%m%d", "%d/%m/%Y", "%Y.%m.%d"] {
+        if let Ok(d) = NaiveDate::parse_from_str(v, fmt) {
+            return d.format("%Y-%m-%d").to_string();
+        }
+    }
+    v.to_string()
+}
+
+/// Parsea el texto crudo de una respuesta WHOIS a un registro tipado.
+pub fn parse_response(raw: &str, kind: WhoisQueryKind) -> WhoisRecord {
+    let mut record = WhoisRecord {
+        kind,
+        ..Default::default()
+    };
+    // (prioridad, valor) por campo escalar
+    let mut best: Vec<Option<(usize, String)>> = vec![None; SCALAR_FIELDS.len()];
+
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty()
+            || trimmed.starts_with('%')
+            || trimmed.starts_with('#')
+            || trimmed.starts_with(">>>")
+        {
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        if NAMESERVER_KEYS.contains(&key.as_str()) {
+            let ns = value
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .trim_end_matches('.')
+                .to_lowercase();
+            if !ns.is_empty() && !record.nameservers.contains(&ns) {
+                record.nameservers.push(ns);
+            }
+            continue;
+        }
+
+        if STATUS_KEYS.contains(&key.as_str()) {
+            // Los estados EPP vienen con la URL de ICANN pegada: "clientTransferProhibited https://..."
+            let status = value
+                .split(" http")
+                .next()
+                .unwrap_or(value)
+                .trim()
+                .to_string();
+            if !status.is_empty() && !record.status.contains(&status) {
+                record.status.push(status);
+            }
+            continue;
+        }
+
+        for (idx, (_, keys)) in SCALAR_FIELDS.iter().enumerate() {
+            if let Some(priority) = keys.iter().position(|k| *k == key) {
+                let better = match &best[idx] {
+                    Some((current, _)) => priority < *current,
+                    None => true,
+                };
+                if better {
+                    best[idx] = Some((priority, value.to_string()));
+                }
+                break;
+            }
+        }
+    }
+
+    for ((field, _), value) in SCALAR_FIELDS.iter().zip(best) {
+        let Some((_, value)) = value else {
+            continue;
+        };
+        match *field {
+            "registrar" => record.registrar = Some(value),
+            "registrant" => record.registrant = Some(value),
+            "organization" => record.organization = Some(value),
+            "country" => record.country = Some(value.to_uppercase()),
+            "created" => record.created = Some(normalize_date(&value)),
+            "updated" => record.updated = Some(normalize_date(&value)),
+            "expires" => record.expires = Some(normalize_date(&value)),
+            "network" => record.network = Some(value),
+            "network_name" => record.network_name = Some(value),
+            "asn" => record.asn = Some(value.to_uppercase()),
+            "abuse_email" => record.abuse_email = Some(value.to_lowercase()),
+            _ => {}
+        }
+    }
+
+    record.registered = record.has_data();
+    record
+}
+
+/// Envía una consulta a un servidor WHOIS y devuelve la respuesta cruda.
+async fn query_server(server: &str, query: &str, config: &OsintConfig) -> Result<String, String> {
+    let mut stream =
+        crate::tools::open_tcp_stream(server, WHOIS_PORT, config, CONNECT_TIMEOUT).await?;
+
+    stream
+        .write_all(format!("{}\r\n", query).as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut buf = Vec::new();
+    let mut limited = (&mut stream).take(MAX_RESPONSE_BYTES);
+    match tokio::time::timeout(READ_TIMEOUT, limited.read_to_end(&mut buf)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => return Err(e.to_string()),
+        // Algunos servidores no cierran la conexión: nos quedamos con lo recibido
+        Err(_) if !buf.is_empty() => {}
+        Err(_) => return Err(format!("{} no respondió a tiempo", server)),
+    }
+
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+/// Resuelve una consulta WHOIS siguiendo las referencias hasta la fuente definitiva.
+pub async fn lookup(query: &str, config: &OsintConfig) -> Result<WhoisRecord, String> {
+    let (kind, normalized) = classify(query).ok_or_else(|| {
+        "Consulta WHOIS inválida: se espera un dominio, una IP o un ASN (ej: AS15169).".to_string()
+    })?;
+
+    let mut server = match kind {
+        WhoisQueryKind::Domain => tld_server(&normalized).unwrap_or(IANA_SERVER).to_string(),
+        WhoisQueryKind::Ip | WhoisQueryKind::Asn => IANA_SERVER.to_string(),
+    };
+
+    let mut record = WhoisRecord {
+        query: normalized.clone(),
+        kind,
+        ..Default::default()
+    };
+
+    for _ in 0..=MAX_REFERRALS {
+        if record.servers.contains(&server) {
+            break;
+        }
+
+        let raw =
+            match query_server(&server, &format_query(&server, kind, &normalized), config).await {
+                Ok(raw) => raw,
+                Err(e) => {
+                    eprintln!("ERROR [whois]: Query to {} failed: {}", server, e);
+                    if record.servers.is_empty() {
+                        return Err(format!(
+                            "No se pudo consultar el servidor WHOIS {}.",
+                            server
+                        ));
+                    }
+                    // El registry ya respondió: nos quedamos con esos datos aunque falle el registrador
+                    break;
+                }
+            };
+        record.servers.push(server.clone());
+
+        // IANA sólo sirve para derivar: sus campos describen al TLD/RIR, no al objetivo
+        if server != IANA_SERVER {
+            record.merge(parse_response(&raw, kind));
+        }
+
+        match find_referral(&raw, &server) {
+            Some(next) => server = next,
+            None => break,
+        }
+    }
+
+    record.registered = record.has_data();
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsea_respuesta_nic_ar() {
+        let raw = "% La información a la que estás accediendo se provee exclusivamente\n\
+                   domain:\t\texample.com.ar\n\
+                   registrant:\t20123456789\n\
+                   registrar:\tnicar\n\
+                   registered:\t2010-05-10 00:00:00\n\
+                   changed:\t2023-04-01 10:20:30.123456\n\
+                   expire:\t\t2025-05-10 00:00:00\n\
+                   \n\
+                   nserver:\tns1.example.com.ar ()\n\
+                   nserver:\tns2.example.com.ar ()\n";
+
+        let record = parse_response(raw, WhoisQueryKind::Domain);
+        assert!(record.registered);
+        assert_eq!(record.registrar.as_deref(), Some("nicar"));
+        assert_eq!(record.registrant.as_deref(), Some("20123456789"));
+        assert_eq!(record.created.as_deref(), Some("2010-05-10T00:00:00Z"));
+        assert_eq!(record.expires.as_deref(), Some("2025-05-10T00:00:00Z"));
+        assert_eq!(
+            record.nameservers,
+            vec!["ns1.example.com.ar", "ns2.example.com.ar"]
+        );
+    }
+
+    #[test]
+    fn test_detecta_referencia_de_registrador() {
+        let raw = "   Domain Name: EXAMPLE.COM\n\
+                   Registrar WHOIS Server: whois.iana.org\n\
+                   Name Server: A.IANA-SERVERS.NET\n\
+                   Domain Status: clientDeleteProhibited https://icann.org/epp#clientDeleteProhibited\n";
+
+        assert_eq!(
+            find_referral(raw, "whois.verisign-grs.com").as_deref(),
+            Some("whois.iana.org")
+        );
+        let record = parse_response(raw, WhoisQueryKind::Domain);
+        assert_eq!(record.nameservers, vec!["a.iana-servers.net"]);
+        assert_eq!(record.status, vec!["clientDeleteProhibited"]);
+    }
+
+    #[test]
+    fn test_clasifica_consultas() {
+        assert_eq!(classify("8.8.8.8").map(|c| c.0), Some(WhoisQueryKind::Ip));
+        assert_eq!(
+            classify("as15169"),
+            Some((WhoisQueryKind::Asn, "AS15169".to_string()))
+        );
+        assert_eq!(
+            classify("https://www.Example.com.ar/contacto"),
+            Some((WhoisQueryKind::Domain, "example.com.ar".to_string()))
+        );
+        assert!(classify("rm -rf /").is_none());
+    }
+}
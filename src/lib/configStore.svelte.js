@@ -38,6 +38,12 @@ class ConfigStore {
     telegram_active: false,
     ollama_url: "http://localhost:11434",
     ollama_model: "llama3.2:latest",
+    dns_server: "",
+    dns_over_tcp: false,
+    searxng_url: "",
+    wayback_url: "",
+    geoip_database: "",
+    online_enrichment: false,
   });
 
   availableModels = $state([]);
@@ -25,7 +25,7 @@ export const osintTools = [
   {
     id: "email-verify",
     name: "Verificador de Email",
-    description: "Valida la existencia de un email y comprueba filtraciones.",
+    description: "Valida sintaxis, MX y buzón SMTP de un email y detecta catch-all y desechables.",
     icon: "📧",
     status: "ready",
   },
@@ -60,4 +60,20 @@ export const osintTools = [
     icon: "👥",
     status: "ready",
   },
+  {
+    id: "dork-runner",
+    name: "Dorks",
+    description:
+      "Ejecuta búsquedas avanzadas sobre una persona, dominio, email o teléfono y guarda en el caso los resultados útiles.",
+    icon: "🎯",
+    status: "beta",
+  },
+  {
+    id: "wayback-history",
+    name: "Archivo Web",
+    description:
+      "Capturas históricas de un sitio en la Wayback Machine, cambios de contenido y páginas borradas.",
+    icon: "🕰️",
+    status: "ready",
+  },
 ];